@@ -8,5 +8,5 @@
     :phoenix
   ],
   plugins: [Spark.Formatter, Phoenix.LiveView.HTMLFormatter],
-  inputs: ["*.{heex,ex,exs}", "{config,lib,test}/**/*.{heex,ex,exs}"]
+  inputs: ["*.{heex,ex,exs}", "{bench,config,lib,test}/**/*.{heex,ex,exs}"]
 ]
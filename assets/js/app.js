@@ -25,10 +25,21 @@ import {LiveSocket} from "phoenix_live_view"
 import {hooks as colocatedHooks} from "phoenix-colocated/panko"
 import topbar from "../vendor/topbar"
 
+// State of the sessions page kept across visits (see SessionsLive): the page
+// pushes its filters and open projects, opening a session from it is noted
+// here, and the whole state goes back with every connect.
+const viewKey = "panko:sessions-view"
+const loadView = () => {
+  try { return JSON.parse(localStorage.getItem(viewKey)) || {} } catch { return {} }
+}
+const saveView = changes => {
+  try { localStorage.setItem(viewKey, JSON.stringify({...loadView(), ...changes})) } catch {}
+}
+
 const csrfToken = document.querySelector("meta[name='csrf-token']").getAttribute("content")
 const liveSocket = new LiveSocket("/live", Socket, {
   longPollFallbackMs: 2500,
-  params: {_csrf_token: csrfToken},
+  params: () => ({_csrf_token: csrfToken, view_state: JSON.stringify(loadView())}),
   hooks: {...colocatedHooks},
 })
 
@@ -54,6 +65,89 @@ window.addEventListener("phx:copy", (e) => {
   navigator.clipboard.writeText(text)
 })
 
+// Copy buttons on rendered code blocks (see ContentRenderer.render_markdown/1)
+window.addEventListener("click", (e) => {
+  const button = e.target.closest("[data-copy-code]")
+  if (button) navigator.clipboard.writeText(button.nextElementSibling.textContent)
+})
+
+// Move between session blocks with j/k, outside form fields
+window.addEventListener("keydown", (e) => {
+  if (!["j", "k"].includes(e.key) || e.ctrlKey || e.metaKey || e.altKey) return
+  if (e.target.closest("input, textarea, select, [contenteditable]")) return
+
+  const blocks = Array.from(document.querySelectorAll("[role=feed] article"))
+  if (blocks.length === 0) return
+
+  const current = blocks.findIndex(block => block.contains(document.activeElement))
+  const next = e.key === "j" ? current + 1 : current - 1
+  const block = blocks[Math.max(0, Math.min(next, blocks.length - 1))]
+
+  e.preventDefault()
+  block.tabIndex = -1
+  block.focus()
+  block.scrollIntoView({block: "start", behavior: "smooth"})
+})
+
+// Tab reuse for `mix panko.open`: a page it opened carries an `opened` token
+// and tells other tabs on the same page, passing on its follow mode. A tab that
+// answers takes over follow mode and the new tab tries to close itself.
+// Browsers only let script-opened tabs close, so where that fails the new tab
+// simply stays a working session page.
+if ("BroadcastChannel" in window) {
+  const tabs = new BroadcastChannel("panko-tabs")
+  const params = new URLSearchParams(location.search)
+  const token = params.get("opened")
+
+  tabs.addEventListener("message", ({data}) => {
+    if (data.type === "open" && data.path === location.pathname) {
+      const follow = document.getElementById("follow-toggle")
+      if (data.follow && follow && follow.getAttribute("aria-pressed") !== "true") follow.click()
+      tabs.postMessage({type: "reused", token: data.token})
+    } else if (data.type === "reused" && token && data.token === token) {
+      window.close()
+    }
+  })
+
+  if (token) {
+    params.delete("opened")
+    const query = params.toString()
+    history.replaceState(history.state, "", location.pathname + (query ? `?${query}` : ""))
+    tabs.postMessage({type: "open", path: location.pathname, token, follow: params.has("follow")})
+  }
+}
+
+// Follow mode on a session page: scroll to the newest block after an update
+window.addEventListener("phx:follow", _e => {
+  const blocks = document.querySelectorAll("[role=feed] article")
+  const last = blocks[blocks.length - 1]
+  if (last) last.scrollIntoView({block: "end", behavior: "smooth"})
+})
+
+// Cycle the agent filter of the sessions page with a, outside form fields
+window.addEventListener("keydown", (e) => {
+  if (e.key !== "a" || e.ctrlKey || e.metaKey || e.altKey) return
+  if (e.target.closest("input, textarea, select, [contenteditable]")) return
+
+  const button = document.querySelector("[data-cycle-agent]")
+  if (button) button.click()
+})
+
+window.addEventListener("phx:save-view", e => saveView(e.detail))
+window.addEventListener("click", e => {
+  const link = e.target.closest("[data-session-link]")
+  if (link) saveView({last_session: link.dataset.sessionLink})
+})
+
+// Bring an element pushed by the server into view, such as the session last
+// opened from the sessions page
+window.addEventListener("phx:focus", e => {
+  const element = document.getElementById(e.detail.id)
+  if (!element) return
+  element.focus()
+  element.scrollIntoView({block: "center"})
+})
+
 // expose liveSocket on window for web console debug logs and latency simulation:
 // >> liveSocket.enableDebug()
 // >> liveSocket.enableLatencySim(1000)  // enabled for duration of browser session
@@ -0,0 +1,35 @@
+// Inlined into HTML exports (see Panko.Sessions.Exporters.Html), which are
+// opened without a server, so this must not import anything.
+
+// Copy buttons on rendered code blocks
+window.addEventListener("click", (e) => {
+  const button = e.target.closest("[data-copy-code]")
+  if (button) navigator.clipboard.writeText(button.nextElementSibling.textContent)
+})
+
+// Open or close every collapsible section at once
+window.addEventListener("click", (e) => {
+  const button = e.target.closest("[data-toggle-all]")
+  if (!button) return
+
+  const open = button.getAttribute("aria-pressed") !== "true"
+  document.querySelectorAll("main details").forEach(details => { details.open = open })
+  button.setAttribute("aria-pressed", String(open))
+})
+
+// Move between session blocks with j/k
+window.addEventListener("keydown", (e) => {
+  if (!["j", "k"].includes(e.key) || e.ctrlKey || e.metaKey || e.altKey) return
+
+  const blocks = Array.from(document.querySelectorAll("[role=feed] article"))
+  if (blocks.length === 0) return
+
+  const current = blocks.findIndex(block => block.contains(document.activeElement))
+  const next = e.key === "j" ? current + 1 : current - 1
+  const block = blocks[Math.max(0, Math.min(next, blocks.length - 1))]
+
+  e.preventDefault()
+  block.tabIndex = -1
+  block.focus()
+  block.scrollIntoView({block: "start", behavior: "smooth"})
+})
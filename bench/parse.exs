@@ -0,0 +1,120 @@
+# Benchmarks the hot paths of reading sessions: parsing one large Claude
+# Code file, finding thousands of session files under the watch paths, and
+# checking all of them.
+#
+#     mix run --no-start bench/parse.exs
+#
+# Each case runs a few times and its median is compared with a budget in
+# milliseconds; the script exits with status 1 when one is over. Budgets
+# can be raised on slow machines with PANKO_BENCH_PARSE_BUDGET,
+# PANKO_BENCH_SCAN_BUDGET and PANKO_BENCH_CHECK_BUDGET.
+
+alias Panko.Sessions.{Check, SessionFiles}
+alias Panko.Sessions.Parsers.ClaudeCode
+
+{:ok, _} = Application.ensure_all_started(:telemetry)
+
+defmodule Bench.Data do
+  def lines(session_id, turns) do
+    1..turns
+    |> Enum.flat_map(fn turn ->
+      ts = ~U[2026-01-01 00:00:00Z] |> DateTime.add(turn) |> DateTime.to_iso8601()
+      common = %{"sessionId" => session_id, "timestamp" => ts, "cwd" => "/home/bench/app"}
+
+      [
+        Map.merge(common, %{
+          "type" => "user",
+          "uuid" => "u#{turn}",
+          "message" => %{"role" => "user", "content" => "Step #{turn}: update the parser"}
+        }),
+        Map.merge(common, %{
+          "type" => "assistant",
+          "uuid" => "a#{turn}",
+          "message" => %{
+            "id" => "msg_#{turn}",
+            "usage" => %{"input_tokens" => 1200, "output_tokens" => 300},
+            "content" => [
+              %{"type" => "thinking", "thinking" => String.duplicate("Considering. ", 40)},
+              %{"type" => "text", "text" => String.duplicate("Here is the change. ", 20)},
+              %{
+                "type" => "tool_use",
+                "id" => "t#{turn}",
+                "name" => "Bash",
+                "input" => %{"command" => "mix test"}
+              }
+            ]
+          }
+        }),
+        Map.merge(common, %{
+          "type" => "user",
+          "uuid" => "r#{turn}",
+          "message" => %{
+            "role" => "user",
+            "content" => [
+              %{
+                "type" => "tool_result",
+                "tool_use_id" => "t#{turn}",
+                "content" => String.duplicate("ok\n", 50)
+              }
+            ]
+          }
+        })
+      ]
+    end)
+    |> Enum.map_join("\n", &Jason.encode!/1)
+  end
+end
+
+defmodule Bench do
+  @runs 5
+
+  def run(name, budget_env, default_budget, fun) do
+    budget = String.to_integer(System.get_env(budget_env, to_string(default_budget)))
+
+    median =
+      1..@runs
+      |> Enum.map(fn _ -> fun |> :timer.tc() |> elem(0) |> div(1000) end)
+      |> Enum.sort()
+      |> Enum.at(div(@runs, 2))
+
+    status = if median <= budget, do: "ok", else: "OVER BUDGET"
+    IO.puts(String.pad_trailing(name, 32) <> "#{median} ms (budget #{budget} ms) #{status}")
+    median <= budget
+  end
+end
+
+dir = Path.join(System.tmp_dir!(), "panko-bench-#{System.unique_integer([:positive])}")
+large = Path.join(dir, "large.jsonl")
+projects = Path.join(dir, "projects")
+
+File.mkdir_p!(dir)
+File.write!(large, Bench.Data.lines("large", 7_000))
+
+for project <- 1..100, session <- 1..20 do
+  path = Path.join([projects, "-home-bench-app#{project}", "s#{session}.jsonl"])
+  File.mkdir_p!(Path.dirname(path))
+  File.write!(path, Bench.Data.lines("p#{project}-s#{session}", 10))
+end
+
+Application.put_env(:panko, :session_watch_paths, projects)
+IO.puts("large.jsonl: #{div(File.stat!(large).size, 1024)} KB; 2000 small sessions\n")
+
+results = [
+  Bench.run("parse large file", "PANKO_BENCH_PARSE_BUDGET", 3_000, fn ->
+    {:ok, _} = ClaudeCode.parse(large)
+  end),
+  Bench.run("parse large file (Jason)", "PANKO_BENCH_PARSE_BUDGET", 3_000, fn ->
+    Application.put_env(:panko, :fast_json, false)
+    {:ok, _} = ClaudeCode.parse(large)
+    Application.delete_env(:panko, :fast_json)
+  end),
+  Bench.run("scan 2000 sessions", "PANKO_BENCH_SCAN_BUDGET", 500, fn ->
+    2_000 = length(SessionFiles.all_files())
+  end),
+  Bench.run("check 2000 sessions", "PANKO_BENCH_CHECK_BUDGET", 10_000, fn ->
+    Check.check_files(SessionFiles.all_files())
+  end)
+]
+
+File.rm_rf!(dir)
+unless Enum.all?(results), do: System.halt(1)
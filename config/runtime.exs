@@ -1,17 +1,104 @@
 import Config
 
+# PANKO_PROFILE=work loads ~/.config/panko/profiles/work.env, KEY=value lines
+# that fill in any variable not already set in the environment. This keeps
+# separate watch paths, keys and databases per profile.
+if profile = System.get_env("PANKO_PROFILE") do
+  dir = System.get_env("PANKO_PROFILE_DIR", Path.expand("~/.config/panko/profiles"))
+  path = Path.join(dir, profile <> ".env")
+
+  unless File.exists?(path), do: raise("PANKO_PROFILE #{profile}: #{path} not found")
+
+  path
+  |> File.read!()
+  |> String.split("\n")
+  |> Enum.map(&String.trim/1)
+  |> Enum.reject(&(&1 == "" or String.starts_with?(&1, "#")))
+  |> Enum.each(fn line ->
+    with [key, value] <- String.split(line, "=", parts: 2),
+         key = key |> String.replace_prefix("export ", "") |> String.trim(),
+         nil <- System.get_env(key) do
+      System.put_env(key, value |> String.trim() |> String.trim("\""))
+    end
+  end)
+end
+
+# Any non-number, such as "unlimited", keeps every stopped share.
+max_share_history =
+  case Integer.parse(System.get_env("PANKO_MAX_SHARE_HISTORY", "unlimited")) do
+    {count, ""} -> count
+    _ -> nil
+  end
+
+allowed_hosts =
+  "PANKO_ALLOWED_HOSTS"
+  |> System.get_env("")
+  |> String.split(",", trim: true)
+  |> Enum.map(&String.trim/1)
+
+trusted_proxies =
+  "PANKO_TRUSTED_PROXIES"
+  |> System.get_env("")
+  |> String.split(",", trim: true)
+  |> Enum.map(&String.trim/1)
+
+# Any non-number, such as "off", disables slow request warnings.
+slow_request_ms =
+  case Integer.parse(System.get_env("PANKO_SLOW_REQUEST_MS", "1000")) do
+    {ms, ""} -> ms
+    _ -> nil
+  end
+
 config :panko,
   session_watch_paths: System.get_env("PANKO_WATCH_PATHS", Path.expand("~/.claude/projects")),
   api_key: System.get_env("PANKO_API_KEY"),
   default_share_expiry: System.get_env("PANKO_DEFAULT_EXPIRY", "7d"),
-  instance_origin_id: System.get_env("PANKO_ORIGIN_ID", "local")
+  max_stopped_share_age: System.get_env("PANKO_MAX_STOPPED_SHARE_AGE", "never"),
+  max_share_history: max_share_history,
+  instance_origin_id: System.get_env("PANKO_ORIGIN_ID", "local"),
+  redact_emails: System.get_env("PANKO_REDACT_EMAILS", "true") == "true",
+  browser_command: System.get_env("PANKO_BROWSER"),
+  locale: System.get_env("PANKO_LOCALE"),
+  editor: System.get_env("PANKO_EDITOR"),
+  share_comments: System.get_env("PANKO_SHARE_COMMENTS", "false") == "true",
+  comment_author: System.get_env("PANKO_COMMENT_NAME", "Owner"),
+  share_snapshots: System.get_env("PANKO_SHARE_SNAPSHOTS", "false") == "true",
+  share_presets: System.get_env("PANKO_SHARE_PRESETS"),
+  download_dir: System.get_env("PANKO_DOWNLOAD_DIR"),
+  notify_events: System.get_env("PANKO_NOTIFY"),
+  team_registry_url: System.get_env("PANKO_TEAM_REGISTRY_URL"),
+  team_registry_token: System.get_env("PANKO_TEAM_REGISTRY_TOKEN"),
+  team_owner: System.get_env("PANKO_TEAM_OWNER", System.get_env("USER")),
+  prune_permanently: System.get_env("PANKO_PRUNE_PERMANENT", "false") == "true",
+  fast_json: System.get_env("PANKO_FAST_JSON", "true") == "true",
+  slow_request_ms: slow_request_ms,
+  allowed_hosts: allowed_hosts,
+  trusted_proxies: trusted_proxies
+
+if allowed_hosts != [] do
+  config :panko, PankoWeb.Endpoint,
+    check_origin: Enum.map(allowed_hosts ++ ["localhost", "127.0.0.1"], &"//#{&1}")
+end
 
 if System.get_env("PHX_SERVER") do
   config :panko, PankoWeb.Endpoint, server: true
 end
 
+# "::" listens on IPv6 and, on most systems, IPv4 too; "::1" on IPv6 loopback.
+http_ip =
+  if address = System.get_env("PANKO_HTTP_IP") do
+    case :inet.parse_address(String.to_charlist(address)) do
+      {:ok, ip} -> ip
+      {:error, _} -> raise "PANKO_HTTP_IP #{inspect(address)} is not an IP address"
+    end
+  end
+
 config :panko, PankoWeb.Endpoint, http: [port: String.to_integer(System.get_env("PORT", "4000"))]
 
+if http_ip do
+  config :panko, PankoWeb.Endpoint, http: [ip: http_ip]
+end
+
 if config_env() == :prod do
   database_url =
     System.get_env("DATABASE_URL") ||
@@ -32,7 +119,7 @@ if config_env() == :prod do
   config :panko, PankoWeb.Endpoint,
     url: [host: host, port: 443, scheme: "https"],
     http: [
-      ip: {0, 0, 0, 0, 0, 0, 0, 0}
+      ip: http_ip || {0, 0, 0, 0, 0, 0, 0, 0}
     ],
     secret_key_base: secret_key_base
 
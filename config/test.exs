@@ -31,3 +31,6 @@ config :phoenix,
 
 # Ash Authentication
 config :panko, :token_signing_secret, "test-only-secret-must-be-at-least-32-bytes-long!"
+
+# HTML exports inline compiled assets, which tests do not build
+config :panko, :export_assets_dir, Path.expand("../test/fixtures/export_assets", __DIR__)
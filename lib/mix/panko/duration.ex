@@ -0,0 +1,29 @@
+defmodule Mix.Panko.Duration do
+  @moduledoc """
+  Duration options of the Panko Mix tasks, such as `--since 7d`, in the
+  format of `Panko.Sharing.Expiry.parse/1`.
+  """
+
+  alias Mix.Panko.ExitStatus
+
+  @doc """
+  Returns the time `duration` ago, or nil when the option is not given.
+
+  Raises a usage error naming `option` when the duration is invalid.
+  """
+  @spec ago!(String.t() | nil, String.t()) :: DateTime.t() | nil
+  def ago!(nil, _option), do: nil
+
+  def ago!(duration, option) do
+    case Panko.Sharing.Expiry.parse(duration) do
+      {:ok, seconds} when is_integer(seconds) ->
+        DateTime.add(DateTime.utc_now(), -seconds)
+
+      _ ->
+        ExitStatus.raise!(
+          :usage,
+          "Invalid #{option} #{inspect(duration)}; use a duration like 12h or 30d"
+        )
+    end
+  end
+end
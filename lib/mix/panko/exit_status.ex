@@ -0,0 +1,50 @@
+defmodule Mix.Panko.ExitStatus do
+  @moduledoc """
+  Exit statuses of the Panko Mix tasks, so scripts can tell failures apart.
+
+  | Status | Meaning |
+  |--------|---------|
+  | 1 | Other failure |
+  | 2 | Invalid arguments or options |
+  | 3 | Session, file or share not found |
+  | 4 | Session file could not be parsed or imported |
+  | 5 | Panko server not reachable |
+  | 6 | Port already in use |
+  | 7 | Team registry not configured or failing |
+  | 8 | Session edits missing from the checked files (`panko.drift`) |
+  | 9 | Privacy findings in a session (`panko.check --privacy`, declined `panko.share`) |
+  """
+
+  @statuses %{
+    usage: 2,
+    not_found: 3,
+    parse_error: 4,
+    unreachable: 5,
+    port_unavailable: 6,
+    registry_error: 7,
+    drift: 8,
+    privacy: 9
+  }
+
+  @type kind ::
+          :usage
+          | :not_found
+          | :parse_error
+          | :unreachable
+          | :port_unavailable
+          | :registry_error
+          | :drift
+          | :privacy
+
+  @doc """
+  Returns the exit status for a kind of failure.
+  """
+  @spec code(kind()) :: pos_integer()
+  def code(kind), do: Map.fetch!(@statuses, kind)
+
+  @doc """
+  Aborts the task with `message` and the exit status of `kind`.
+  """
+  @spec raise!(kind(), String.t()) :: no_return()
+  def raise!(kind, message), do: Mix.raise(message, exit_status: code(kind))
+end
@@ -0,0 +1,63 @@
+defmodule Mix.Panko.Style do
+  @moduledoc """
+  Terminal colors shared by the Panko Mix tasks.
+
+  Tasks accept `--color always|never|auto` (a `color: :string` switch)
+  and call `setup/1` once. With `auto`, the default, output is colored
+  when stdout is a terminal and `NO_COLOR` is unset or empty. The choice
+  is applied to `IO.ANSI.format/1`, so styled chardata degrades to plain
+  text.
+  """
+
+  alias Mix.Panko.ExitStatus
+
+  @doc """
+  Decides whether to color output from the task's options and the
+  environment, and applies the decision.
+  """
+  @spec setup(keyword()) :: boolean()
+  def setup(opts) do
+    enabled = enabled?(opts[:color], System.get_env("NO_COLOR"), IO.ANSI.enabled?())
+    Application.put_env(:elixir, :ansi_enabled, enabled)
+    enabled
+  end
+
+  @doc """
+  Resolves a `--color` value given `NO_COLOR` and whether stdout is a
+  terminal.
+  """
+  @spec enabled?(String.t() | nil, String.t() | nil, boolean()) :: boolean()
+  def enabled?("always", _no_color, _tty), do: true
+  def enabled?("never", _no_color, _tty), do: false
+  def enabled?(auto, no_color, tty) when auto in [nil, "auto"], do: no_color in [nil, ""] and tty
+
+  def enabled?(other, _no_color, _tty),
+    do: ExitStatus.raise!(:usage, "Invalid --color #{inspect(other)}; use always, never or auto")
+
+  @doc """
+  Formats styled chardata such as `[:green, "ok"]` into a string.
+  """
+  @spec format(IO.ANSI.ansidata()) :: String.t()
+  def format(chardata), do: chardata |> IO.ANSI.format() |> IO.chardata_to_string()
+
+  @doc """
+  Colors `text` by outcome: green for `:ok`, yellow for `:warning` and red
+  for `:error`.
+  """
+  @spec status(:ok | :warning | :error, String.t()) :: String.t()
+  def status(:ok, text), do: format([:green, text])
+  def status(:warning, text), do: format([:yellow, text])
+  def status(:error, text), do: format([:red, text])
+
+  @doc """
+  Styles a section heading.
+  """
+  @spec heading(String.t()) :: String.t()
+  def heading(text), do: format([:bright, text])
+
+  @doc """
+  Styles secondary text such as dates and paths.
+  """
+  @spec faint(String.t()) :: String.t()
+  def faint(text), do: format([:faint, text])
+end
@@ -0,0 +1,83 @@
+defmodule Mix.Panko.Timings do
+  @moduledoc """
+  Collects the scan and parse telemetry spans of a task run and reports
+  them for `--timings`.
+
+  Files are parsed in parallel, so the summed parse time can exceed the
+  wall time of the run; both are reported.
+  """
+
+  @events [[:panko, :sessions, :scan, :stop], [:panko, :sessions, :parse, :stop]]
+
+  @type entry :: {:scan | :parse, duration :: integer(), metadata :: map()}
+
+  @doc """
+  Starts collecting spans until the calling process exits.
+  """
+  @spec start() :: pid()
+  def start do
+    {:ok, agent} = Agent.start_link(fn -> {System.monotonic_time(), []} end)
+    :telemetry.attach_many({__MODULE__, agent}, @events, &__MODULE__.handle_event/4, agent)
+    agent
+  end
+
+  @doc false
+  def handle_event([:panko, :sessions, stage, :stop], %{duration: duration}, metadata, agent) do
+    Agent.update(agent, fn {started, entries} ->
+      {started, [{stage, duration, metadata} | entries]}
+    end)
+  end
+
+  @doc """
+  Stops collecting and prints the summary to stderr.
+  """
+  @spec report(pid()) :: :ok
+  def report(agent) do
+    :telemetry.detach({__MODULE__, agent})
+    {started, entries} = Agent.get(agent, & &1)
+    Agent.stop(agent)
+
+    wall = System.monotonic_time() - started
+    Enum.each(summary(Enum.reverse(entries), wall), &IO.puts(:stderr, &1))
+  end
+
+  @doc """
+  Summary lines for collected entries and the wall time of the run, both
+  in native time units.
+  """
+  @spec summary([entry()], integer()) :: [String.t()]
+  def summary(entries, wall) do
+    scans = for {:scan, duration, metadata} <- entries, do: {duration, metadata}
+    parses = for {:parse, duration, metadata} <- entries, do: {duration, metadata}
+
+    Enum.reject(
+      [
+        scans != [] && scan_line(scans),
+        parses != [] && parse_line(parses),
+        "total  #{ms(wall)}"
+      ],
+      &(&1 == false)
+    )
+  end
+
+  defp scan_line(scans) do
+    files = scans |> Enum.map(fn {_duration, metadata} -> metadata[:count] || 0 end) |> Enum.sum()
+    "scan   #{ms(total(scans))}  (#{files} files)"
+  end
+
+  defp parse_line(parses) do
+    {slowest, %{path: path}} = Enum.max_by(parses, &elem(&1, 0))
+    count = length(parses)
+    mean = div(total(parses), count)
+
+    "parse  #{ms(total(parses))}  (#{count} files, mean #{ms(mean)}, " <>
+      "slowest #{ms(slowest)} #{Path.basename(path)})"
+  end
+
+  defp total(spans), do: spans |> Enum.map(&elem(&1, 0)) |> Enum.sum()
+
+  defp ms(native) do
+    micros = System.convert_time_unit(native, :native, :microsecond)
+    :erlang.float_to_binary(micros / 1000, decimals: 1) <> " ms"
+  end
+end
@@ -0,0 +1,160 @@
+defmodule Mix.Tasks.Panko.Check do
+  @moduledoc """
+  Validates session files without importing them.
+
+  Reports files that cannot be parsed, lines that were skipped and tool
+  calls without results. Accepts files, directories (checked recursively)
+  and globs, and checks every session under the watch paths when none are
+  given. Files are checked in parallel and a summary is printed at the end.
+  Exits with status 4 if any file has errors (see `Mix.Panko.ExitStatus`).
+
+  With `--privacy`, the sessions are audited before sharing instead (see
+  `Panko.Sessions.Privacy`): likely secrets, email addresses, internal
+  hosts and large embedded files are listed per file, and the task exits
+  with status 9 when anything was found.
+
+  ## Usage
+
+      mix panko.check ~/.claude/projects/-home-me-app/abc123.jsonl
+      mix panko.check session.jsonl --json
+      mix panko.check ~/.claude/projects/-home-me-app
+      mix panko.check "~/.claude/projects/*/*.jsonl" --errors-only
+      mix panko.check session.jsonl --privacy
+
+  ## Options
+
+    * `--json` - print the results as JSON
+    * `--errors-only` - only print files with errors
+    * `--privacy` - scan for content that should not be shared instead
+    * `--timings` - print scan and parse durations to stderr
+    * `--color` - `always`, `never` or `auto` (default), which colors output
+      on a terminal unless `NO_COLOR` is set
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Mix.Panko.Style
+  alias Mix.Panko.Timings
+  alias Panko.Sessions.Check
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Privacy
+  alias Panko.Sessions.SessionFiles
+
+  @shortdoc "Validates session files"
+
+  @switches [
+    json: :boolean,
+    errors_only: :boolean,
+    privacy: :boolean,
+    color: :string,
+    timings: :boolean
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, paths} =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, paths, []} -> {opts, paths}
+        _ -> ExitStatus.raise!(:usage, "Usage: mix panko.check [FILE|DIR|GLOB...] [--json]")
+      end
+
+    Style.setup(opts)
+    Mix.Task.run("app.config")
+    timings = if opts[:timings], do: Timings.start()
+
+    files =
+      if paths == [],
+        do: SessionFiles.all_files(),
+        else: paths |> Enum.map(&Path.expand/1) |> Check.expand_paths()
+
+    if opts[:privacy],
+      do: check_privacy(files, opts),
+      else: check_files(files, opts, timings)
+  end
+
+  defp check_files(files, opts, timings) do
+    results = Check.check_files(files)
+    shown = if opts[:errors_only], do: Enum.reject(results, & &1.ok), else: results
+
+    if opts[:json] do
+      Mix.shell().info(Jason.encode!(shown, pretty: true))
+    else
+      Enum.each(shown, &print_result/1)
+      print_summary(results)
+    end
+
+    if timings, do: Timings.report(timings)
+
+    unless Enum.all?(results, & &1.ok), do: exit({:shutdown, ExitStatus.code(:parse_error)})
+  end
+
+  defp check_privacy(files, opts) do
+    results =
+      files
+      |> Task.async_stream(&scan/1, ordered: true, timeout: :infinity)
+      |> Enum.map(fn {:ok, result} -> result end)
+
+    if opts[:json] do
+      Mix.shell().info(Jason.encode!(results, pretty: true))
+    else
+      Enum.each(results, &print_findings/1)
+    end
+
+    cond do
+      Enum.any?(results, &(&1.findings != [])) -> exit({:shutdown, ExitStatus.code(:privacy)})
+      Enum.any?(results, & &1.error) -> exit({:shutdown, ExitStatus.code(:parse_error)})
+      true -> :ok
+    end
+  end
+
+  defp scan(path) do
+    case Exporters.load_file(path) do
+      {:ok, session} -> %{path: path, findings: Privacy.scan(session), error: nil}
+      {:error, reason} -> %{path: path, findings: [], error: inspect(reason)}
+    end
+  end
+
+  defp print_findings(%{path: path, error: error}) when is_binary(error),
+    do: Mix.shell().info("#{path}: #{Style.status(:error, "FAILED")} (#{error})")
+
+  defp print_findings(%{path: path, findings: []}),
+    do: Mix.shell().info("#{path}: #{Style.status(:ok, "nothing found")}")
+
+  defp print_findings(%{path: path, findings: findings}) do
+    Mix.shell().info("#{path}: #{Style.status(:warning, Privacy.describe(findings))}")
+
+    for finding <- findings do
+      where = if finding.position, do: "block #{finding.position}", else: "title"
+      times = if finding.count > 1, do: ", #{finding.count} times", else: ""
+
+      Mix.shell().info(
+        "  #{String.pad_trailing(to_string(finding.rule), 16)}#{finding.match}  " <>
+          Style.faint("(#{where}#{times})")
+      )
+    end
+  end
+
+  defp print_summary(results) do
+    failed = Enum.count(results, &(not &1.ok))
+    warned = Enum.count(results, &(&1.ok and &1.diagnostics != []))
+
+    Mix.shell().info("""
+
+    checked   #{length(results)}
+    ok        #{Style.status(:ok, to_string(length(results) - failed - warned))}
+    warnings  #{Style.status(:warning, to_string(warned))}
+    failed    #{Style.status(:error, to_string(failed))}\
+    """)
+  end
+
+  defp print_result(result) do
+    status = if result.ok, do: Style.status(:ok, "ok"), else: Style.status(:error, "FAILED")
+    Mix.shell().info("#{result.path}: #{status} (#{result.blocks} blocks)")
+
+    for diagnostic <- result.diagnostics do
+      line = if diagnostic.line, do: "line #{diagnostic.line}: ", else: ""
+      level = Style.status(diagnostic.level, to_string(diagnostic.level))
+      Mix.shell().info("  #{level}: #{line}#{diagnostic.message}")
+    end
+  end
+end
@@ -0,0 +1,21 @@
+defmodule Mix.Tasks.Panko.Db.Info do
+  @moduledoc """
+  Shows the database Panko uses, its schema version and pending migrations.
+
+  Migrations are applied on startup by the release's `bin/server` script,
+  or with `mix ecto.migrate` in development.
+
+  ## Usage
+
+      mix panko.db.info
+  """
+  use Mix.Task
+
+  @shortdoc "Shows the database schema version and pending migrations"
+
+  @impl Mix.Task
+  def run(_args) do
+    Mix.Task.run("app.config")
+    Panko.Release.print_db_info()
+  end
+end
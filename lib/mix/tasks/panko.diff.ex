@@ -0,0 +1,89 @@
+defmodule Mix.Tasks.Panko.Diff do
+  @moduledoc """
+  Compares two sessions and prints where they diverge.
+
+  Sessions are given as session files or session ids. Prints the prompts
+  side by side up to the first difference, then the tools each session
+  invoked and the files only one of them changed.
+
+  ## Usage
+
+      mix panko.diff attempt-1.jsonl attempt-2.jsonl
+      mix panko.diff 0b6f6c0e-... 5d1e9a7c-... --json
+
+  ## Options
+
+    * `--json` - print the comparison as JSON
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sessions
+  alias Panko.Sessions.Diff
+  alias Panko.Sessions.Exporters
+
+  @shortdoc "Compares two sessions"
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, a, b} =
+      case OptionParser.parse(args, strict: [json: :boolean]) do
+        {opts, [a, b], []} -> {opts, a, b}
+        _ -> ExitStatus.raise!(:usage, "Usage: mix panko.diff SESSION_A SESSION_B [--json]")
+      end
+
+    # Session ids need the database; files are parsed directly.
+    if File.regular?(a) and File.regular?(b) do
+      Mix.Task.run("app.config")
+    else
+      Mix.Task.run("app.start")
+    end
+
+    diff = Diff.compare(load!(a), load!(b))
+
+    if opts[:json] do
+      Mix.shell().info(Jason.encode!(diff, pretty: true))
+    else
+      print(diff)
+    end
+  end
+
+  defp load!(ref) do
+    result =
+      if File.regular?(ref),
+        do: Exporters.load_file(ref),
+        else: Sessions.get_session(ref, load: [:blocks])
+
+    case result do
+      {:ok, session} -> session
+      {:error, _} -> ExitStatus.raise!(:not_found, "No session found for #{inspect(ref)}")
+    end
+  end
+
+  defp print(diff) do
+    case diff.diverges_at do
+      nil ->
+        Mix.shell().info("Prompts: identical (#{length(diff.prompts)})")
+
+      index ->
+        Mix.shell().info("Prompts: same for #{index - 1}, diverge at prompt #{index}")
+        %{a: a, b: b} = Enum.at(diff.prompts, index - 1)
+        Mix.shell().info("  A: #{preview(a)}")
+        Mix.shell().info("  B: #{preview(b)}")
+    end
+
+    Mix.shell().info("\nTools (A / B):")
+
+    for tool <- Enum.sort(Enum.uniq(Map.keys(diff.tools.a) ++ Map.keys(diff.tools.b))) do
+      Mix.shell().info("  #{tool}: #{diff.tools.a[tool] || 0} / #{diff.tools.b[tool] || 0}")
+    end
+
+    Mix.shell().info("\nFiles changed:")
+    Enum.each(diff.files.only_a, &Mix.shell().info("  A only: #{&1}"))
+    Enum.each(diff.files.only_b, &Mix.shell().info("  B only: #{&1}"))
+    Mix.shell().info("  #{length(diff.files.both)} changed in both")
+  end
+
+  defp preview(nil), do: "(no prompt)"
+  defp preview(prompt), do: prompt |> String.split() |> Enum.join(" ") |> String.slice(0, 100)
+end
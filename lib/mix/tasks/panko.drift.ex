@@ -0,0 +1,124 @@
+defmodule Mix.Tasks.Panko.Drift do
+  @moduledoc """
+  Checks whether a session's file edits are present in the working tree
+  or in a commit.
+
+  Every `Write` and `Edit` of the session is compared with the file as it
+  is now (see `Panko.Sessions.Drift`), which shows edits that were
+  reverted or changed afterwards, by hand or by another session. The
+  repository defaults to the session's project directory.
+
+  ## Usage
+
+      mix panko.drift abc123.jsonl
+      mix panko.drift 0b6f6c0e-... --ref HEAD~3
+      mix panko.drift abc123.jsonl --repo ~/code/app --json
+
+  ## Options
+
+    * `--ref` - check the files as of this commit instead of the working tree
+    * `--repo` - repository to check, instead of the session's project
+    * `--json` - print the results as JSON
+    * `--color` - `always`, `never` or `auto` (default), which colors output
+      on a terminal unless `NO_COLOR` is set
+
+  ## Exit status
+
+  Exits with status 8 when an edit was reverted, changed or its file is
+  missing; see `Mix.Panko.ExitStatus`.
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Mix.Panko.Style
+  alias Panko.Sessions
+  alias Panko.Sessions.Drift
+  alias Panko.Sessions.Exporters
+
+  @shortdoc "Checks a session's edits against the working tree or a commit"
+
+  @switches [ref: :string, repo: :string, json: :boolean, color: :string]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, ref} =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, [ref], []} -> {opts, ref}
+        _ -> ExitStatus.raise!(:usage, "Usage: mix panko.drift SESSION [--ref REF] [--repo PATH]")
+      end
+
+    Style.setup(opts)
+
+    # Session ids need the database; files are parsed directly.
+    if File.regular?(ref), do: Mix.Task.run("app.config"), else: Mix.Task.run("app.start")
+
+    session = load!(ref)
+    repo = repo!(opts[:repo] || session.project)
+
+    read =
+      if opts[:ref],
+        do: Drift.git_reader(repo, opts[:ref]),
+        else: Drift.worktree_reader(repo)
+
+    entries = Drift.check(session, read)
+
+    if opts[:json] do
+      Mix.shell().info(Jason.encode!(entries, pretty: true))
+    else
+      print(entries, repo, opts[:ref])
+    end
+
+    if Drift.drifted?(entries), do: exit({:shutdown, ExitStatus.code(:drift)})
+  end
+
+  defp load!(ref) do
+    result =
+      if File.regular?(ref),
+        do: Exporters.load_file(ref),
+        else: Sessions.get_session(ref, load: [:blocks])
+
+    case result do
+      {:ok, session} -> session
+      {:error, _} -> ExitStatus.raise!(:not_found, "No session found for #{inspect(ref)}")
+    end
+  end
+
+  defp repo!(nil), do: ExitStatus.raise!(:usage, "The session has no project; pass --repo PATH")
+
+  defp repo!(path) do
+    path = Path.expand(path)
+
+    if File.dir?(path),
+      do: path,
+      else: ExitStatus.raise!(:not_found, "No such directory: #{path}")
+  end
+
+  defp print([], _repo, _ref), do: Mix.shell().info("The session made no file edits")
+
+  defp print(entries, repo, ref) do
+    Mix.shell().info(Style.heading("#{repo} (#{ref || "working tree"})"))
+
+    for entry <- entries do
+      status = entry.status |> Atom.to_string() |> String.pad_trailing(10)
+      path = Path.relative_to(entry.path, repo)
+
+      Mix.shell().info(
+        "  #{styled(entry.status, status)}#{path}  " <>
+          Style.faint("(#{entry.tool}, block #{entry.position})")
+      )
+    end
+
+    summary =
+      entries
+      |> Drift.summary()
+      |> Enum.reject(fn {_status, count} -> count == 0 end)
+      |> Enum.map_join(", ", fn {status, count} -> "#{count} #{status}" end)
+
+    Mix.shell().info("\n" <> summary)
+  end
+
+  defp styled(:present, text), do: Style.status(:ok, text)
+  defp styled(:superseded, text), do: Style.faint(text)
+  defp styled(:reverted, text), do: Style.status(:error, text)
+  defp styled(_status, text), do: Style.status(:warning, text)
+end
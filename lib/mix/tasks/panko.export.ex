@@ -0,0 +1,254 @@
+defmodule Mix.Tasks.Panko.Export do
+  @moduledoc """
+  Exports a session file, or every session in a project, to a standalone
+  document.
+
+  Writes to stdout unless `--output` is given. Several session files, or
+  `--dir`, write one file per session into a directory instead (see
+  `Panko.Sessions.Exporters.Download`), never overwriting existing files.
+
+  ## Usage
+
+      mix panko.export ~/.claude/projects/my-app/session.jsonl
+      mix panko.export session.jsonl --format html -o session.html
+      mix panko.export session.jsonl -f markdown --no-thinking --no-tool-output
+      mix panko.export session.jsonl -f markdown --only user_prompt,assistant_response
+      mix panko.export session.jsonl -f json --range 10..40 --since 2026-03-09T12:00:00Z
+      mix panko.export session.jsonl -f summary --llm-prompt | llm
+      mix panko.export session.jsonl -f markdown --no-emails
+      mix panko.export session.jsonl --template incident-report.md.eex -o report.md
+      mix panko.export ~/.claude/projects/my-app/*.jsonl -f markdown --dir ~/exports
+      mix panko.export --project ~/code/my-app -o my-app.md
+      mix panko.export -p ~/.claude/projects/-home-me-code-my-app --bundle zip -o my-app.zip
+
+  ## Options
+
+    * `--format` / `-f` - export format (default: `html`, or `markdown` inside a zip bundle)
+    * `--project` / `-p` - export every session of a project, given either its
+      session directory or its working directory
+    * `--bundle` - with `--project`, `markdown` for one concatenated document
+      (default) or `zip` for one file per session plus an index
+    * `--output` / `-o` - file to write instead of stdout
+    * `--dir` / `-d` - directory to write session files into, defaulting to
+      `PANKO_DOWNLOAD_DIR` or the current directory when several files are given
+    * `--no-thinking` - drop thinking blocks
+    * `--no-tool-output` - drop tool outputs
+    * `--no-system` - strip system reminders and command caveats
+    * `--only` - comma-separated block types to keep
+    * `--exclude` - comma-separated block types to drop
+    * `--range` - block positions to keep, e.g. `10..40`
+    * `--since` / `--until` - ISO 8601 timestamps bounding the blocks kept
+    * `--llm-prompt` - with `summary`, wrap the digest in a summarization prompt
+    * `--template` - render an EEx template with the session, implies `--format template`
+      (see `Panko.Sessions.Exporters.Template` for the available assigns)
+    * `--no-redact` - keep secrets instead of masking them with `[REDACTED]`
+    * `--no-emails` - leave email addresses unredacted
+
+  The number of redacted values is reported on stderr. When writing files,
+  a desktop notification can be sent on completion, see `Panko.Notifications`.
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Exporters.Bundle
+  alias Panko.Sessions.Exporters.Download
+  alias Panko.Sessions.SessionFiles
+
+  @shortdoc "Exports a session file to HTML and other formats"
+
+  @switches [
+    format: :string,
+    output: :string,
+    dir: :string,
+    project: :string,
+    bundle: :string,
+    thinking: :boolean,
+    tool_output: :boolean,
+    system: :boolean,
+    only: :string,
+    exclude: :string,
+    range: :string,
+    since: :string,
+    until: :string,
+    llm_prompt: :boolean,
+    template: :string,
+    redact: :boolean,
+    emails: :boolean
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, paths, _} =
+      OptionParser.parse(args,
+        strict: @switches,
+        aliases: [f: :format, o: :output, d: :dir, p: :project]
+      )
+
+    Mix.Task.run("app.config")
+
+    case {opts[:project], paths} do
+      {nil, [path]} when is_nil(opts[:dir]) -> export_file(path, opts)
+      {nil, [_ | _]} when is_nil(opts[:output]) -> export_files(paths, opts)
+      {project, []} when is_binary(project) -> export_project(project, opts)
+      _ ->
+        ExitStatus.raise!(
+          :usage,
+          "Usage: mix panko.export <file>... [--format FORMAT] [-o OUTPUT | --dir DIR]"
+        )
+    end
+  end
+
+  defp export_file(path, opts) do
+    format = opts[:format] || if(opts[:template], do: "template", else: "html")
+
+    with {:ok, session} <- Exporters.load_file(path),
+         {:ok, document, report} <-
+           Exporters.export_with_report(session, format, export_opts(opts)) do
+      write(document, opts[:output])
+      report_redactions(report)
+    else
+      {:error, :unknown_format} -> raise_unknown_format(format)
+      {:error, :template_required} ->
+        ExitStatus.raise!(:usage, "The template format requires --template PATH")
+      {:error, {:template_error, message}} -> Mix.raise("Template error: #{message}")
+      {:error, :assets_missing} ->
+        Mix.raise("The HTML export needs built assets, run mix assets.build")
+      {:error, reason} ->
+        ExitStatus.raise!(:parse_error, "Failed to export #{path}: #{inspect(reason)}")
+    end
+  end
+
+  defp export_files(paths, opts) do
+    format = opts[:format] || if(opts[:template], do: "template", else: "html")
+    dir = Download.dir(opts[:dir])
+
+    failed =
+      Enum.reject(paths, fn path ->
+        with {:ok, session} <- Exporters.load_file(path),
+             {:ok, written, report} <- Download.write(session, dir, format, export_opts(opts)) do
+          Mix.shell().info("Exported #{path} to #{written}")
+          report_redactions(report)
+          true
+        else
+          {:error, :unknown_format} ->
+            raise_unknown_format(format)
+
+          {:error, reason} ->
+            Mix.shell().error("Failed to export #{path}: #{inspect(reason)}")
+            false
+        end
+      end)
+
+    if length(failed) < length(paths) do
+      exported = length(paths) - length(failed)
+      Panko.Notifications.notify(:export_completed, "Export completed", "#{exported} to #{dir}")
+    end
+
+    if failed != [] do
+      ExitStatus.raise!(:parse_error, "Failed to export #{length(failed)} of #{length(paths)}")
+    end
+  end
+
+  defp export_project(project, opts) do
+    bundle = opts[:bundle] || "markdown"
+    format = opts[:format] || "markdown"
+
+    sessions =
+      project
+      |> SessionFiles.project_files()
+      |> Enum.flat_map(fn path ->
+        case Exporters.load_file(path) do
+          {:ok, session} -> [session]
+          {:error, _reason} -> []
+        end
+      end)
+
+    if sessions == [] do
+      ExitStatus.raise!(:not_found, "No sessions found for project #{project}")
+    end
+
+    case Bundle.build(sessions, bundle, [format: format] ++ export_opts(opts)) do
+      {:ok, document, report} ->
+        write(document, opts[:output])
+        report_redactions(report)
+
+      {:error, :unknown_bundle} ->
+        ExitStatus.raise!(
+          :usage,
+          "Unknown bundle #{inspect(bundle)}. Available: #{Enum.join(Bundle.kinds(), ", ")}"
+        )
+
+      {:error, :unknown_format} ->
+        raise_unknown_format(format)
+
+      {:error, reason} ->
+        ExitStatus.raise!(:parse_error, "Failed to export project #{project}: #{inspect(reason)}")
+    end
+  end
+
+  defp raise_unknown_format(format) do
+    ExitStatus.raise!(
+      :usage,
+      "Unknown format #{inspect(format)}. " <>
+        "Available: #{Enum.join(Exporters.Registry.formats(), ", ")}"
+    )
+  end
+
+  defp export_opts(opts) do
+    opts
+    |> Keyword.take([:thinking, :tool_output, :system, :llm_prompt, :template, :redact, :emails])
+    |> put_parsed(:only, opts[:only], &parse_block_types/1)
+    |> put_parsed(:exclude, opts[:exclude], &parse_block_types/1)
+    |> put_parsed(:range, opts[:range], &parse_range/1)
+    |> put_parsed(:since, opts[:since], &parse_datetime/1)
+    |> put_parsed(:until, opts[:until], &parse_datetime/1)
+  end
+
+  defp put_parsed(opts, _key, nil, _parser), do: opts
+  defp put_parsed(opts, key, value, parser), do: Keyword.put(opts, key, parser.(value))
+
+  defp parse_block_types(value) do
+    known = Map.new(Panko.Sessions.Block.Type.values(), &{Atom.to_string(&1), &1})
+
+    value
+    |> String.split(",", trim: true)
+    |> Enum.map(fn name ->
+      Map.get(known, String.trim(name)) ||
+        ExitStatus.raise!(
+          :usage,
+          "Unknown block type #{inspect(name)}. Available: #{Enum.join(Map.keys(known), ", ")}"
+        )
+    end)
+  end
+
+  defp parse_range(value) do
+    case Regex.run(~r/^(\d+)(?:\.\.|-)(\d+)$/, value) do
+      [_, first, last] -> String.to_integer(first)..String.to_integer(last)//1
+      _ -> ExitStatus.raise!(:usage, "Invalid --range #{inspect(value)}, expected FIRST..LAST")
+    end
+  end
+
+  defp parse_datetime(value) do
+    case DateTime.from_iso8601(value) do
+      {:ok, dt, _offset} -> dt
+      {:error, _} ->
+        ExitStatus.raise!(:usage, "Invalid timestamp #{inspect(value)}, expected ISO 8601")
+    end
+  end
+
+  defp write(document, nil), do: IO.binwrite(document)
+
+  defp write(document, output) do
+    File.write!(output, document)
+    Mix.shell().info("Exported to #{output}")
+    Panko.Notifications.notify(:export_completed, "Export completed", Path.expand(output))
+  end
+
+  defp report_redactions(%{redactions: 0}), do: :ok
+
+  defp report_redactions(%{redactions: total, redactions_by_rule: counts}) do
+    details = Enum.map_join(counts, ", ", fn {rule, n} -> "#{n} #{rule}" end)
+    IO.puts(:stderr, "Redacted #{total} value(s) (#{details})")
+  end
+end
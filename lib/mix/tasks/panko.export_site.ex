@@ -0,0 +1,79 @@
+defmodule Mix.Tasks.Panko.ExportSite do
+  @moduledoc """
+  Renders every session file in a directory into a static website.
+
+  Session files are found recursively, like the session watcher does, and
+  each one becomes an HTML page next to an `index.html` grouped by project.
+
+  ## Usage
+
+      mix panko.export_site ~/.claude/projects -o site/
+      mix panko.export_site ~/.claude/projects/-home-me-code-my-app -o site/ --no-thinking
+
+  ## Options
+
+    * `--output` / `-o` - directory to write the site to (default: `site`)
+    * `--no-thinking` - drop thinking blocks
+    * `--no-tool-output` - drop tool outputs
+    * `--no-system` - strip system reminders and command caveats
+    * `--no-redact` - keep secrets instead of masking them with `[REDACTED]`
+    * `--no-emails` - leave email addresses unredacted
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sessions.Exporters
+
+  @shortdoc "Renders a session archive as a static website"
+
+  @switches [
+    output: :string,
+    thinking: :boolean,
+    tool_output: :boolean,
+    system: :boolean,
+    redact: :boolean,
+    emails: :boolean
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, dir} =
+      case OptionParser.parse(args, strict: @switches, aliases: [o: :output]) do
+        {opts, [dir], []} -> {opts, Path.expand(dir)}
+        _ -> ExitStatus.raise!(:usage, "Usage: mix panko.export_site <dir> [-o OUTPUT]")
+      end
+
+    Mix.Task.run("app.config")
+
+    sessions =
+      Path.join([dir, "**", "*.jsonl"])
+      |> Path.wildcard()
+      |> Enum.flat_map(fn path ->
+        case Exporters.load_file(path) do
+          {:ok, session} ->
+            [session]
+
+          {:error, reason} ->
+            Mix.shell().error("Skipping #{path}: #{inspect(reason)}")
+            []
+        end
+      end)
+
+    if sessions == [], do: ExitStatus.raise!(:not_found, "No sessions found in #{dir}")
+
+    output = opts[:output] || "site"
+    export_opts = Keyword.take(opts, [:thinking, :tool_output, :system, :redact, :emails])
+
+    case Exporters.Site.build(sessions, output, export_opts) do
+      {:ok, %{pages: pages, redactions: redactions}} ->
+        Mix.shell().info("Wrote #{pages} session pages to #{output}")
+        if redactions > 0, do: Mix.shell().info("Redacted #{redactions} value(s)")
+
+      {:error, :assets_missing} ->
+        Mix.raise("The site needs built assets, run mix assets.build")
+
+      {:error, reason} ->
+        Mix.raise("Failed to build site: #{inspect(reason)}")
+    end
+  end
+end
@@ -0,0 +1,209 @@
+defmodule Mix.Tasks.Panko.Gen.Service do
+  @moduledoc """
+  Installs Panko as a user-level service so it keeps running in the background.
+
+  Writes a systemd user unit on Linux or a LaunchAgent on macOS that runs
+  the release's `bin/server` script (which migrates, then starts the
+  server), and enables it. Secrets such as `DATABASE_URL` and
+  `SECRET_KEY_BASE` are read from an environment file of `KEY=value` lines.
+
+  NixOS users should prefer the NixOS module in `nix/module.nix`.
+
+  ## Usage
+
+      MIX_ENV=prod mix release
+      mix panko.gen.service
+      mix panko.gen.service --release /opt/panko --env-file ~/.config/panko/env
+      mix panko.gen.service --launchd --print
+      mix panko.gen.service --uninstall
+
+  ## Options
+
+    * `--release` - release root (default: `_build/prod/rel/panko`)
+    * `--env-file` - environment file (default: `~/.config/panko/env`)
+    * `--systemd` / `--launchd` - service manager (default: detected from the OS)
+    * `--no-enable` - only write the service file
+    * `--print` - print the service file instead of installing it
+    * `--uninstall` - disable and remove the service
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+
+  @shortdoc "Installs Panko as a systemd or launchd user service"
+
+  @label "com.github.jordangarrison.panko"
+
+  @switches [
+    release: :string,
+    env_file: :string,
+    systemd: :boolean,
+    launchd: :boolean,
+    enable: :boolean,
+    print: :boolean,
+    uninstall: :boolean
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    opts =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, [], []} ->
+          opts
+
+        _ ->
+          ExitStatus.raise!(
+            :usage,
+            "Usage: mix panko.gen.service [--release DIR] [--env-file FILE] [--uninstall]"
+          )
+      end
+
+    manager = manager(opts)
+    path = service_path(manager)
+
+    cond do
+      opts[:uninstall] -> uninstall(manager, path)
+      opts[:print] -> manager |> service(opts) |> IO.write()
+      true -> install(manager, path, opts)
+    end
+  end
+
+  defp manager(opts) do
+    cond do
+      opts[:systemd] -> :systemd
+      opts[:launchd] -> :launchd
+      match?({:unix, :darwin}, :os.type()) -> :launchd
+      true -> :systemd
+    end
+  end
+
+  defp service_path(:systemd), do: Path.expand("~/.config/systemd/user/panko.service")
+  defp service_path(:launchd), do: Path.expand("~/Library/LaunchAgents/#{@label}.plist")
+
+  defp install(manager, path, opts) do
+    {server, env_file} = paths(opts)
+
+    unless File.exists?(server) do
+      Mix.shell().error("#{server} not found; build it with `MIX_ENV=prod mix release`")
+    end
+
+    unless File.exists?(env_file) do
+      Mix.shell().error("#{env_file} not found; set DATABASE_URL and SECRET_KEY_BASE there")
+    end
+
+    File.mkdir_p!(Path.dirname(path))
+    File.write!(path, service(manager, opts))
+    Mix.shell().info("Wrote #{path}")
+
+    if Keyword.get(opts, :enable, true) do
+      manager |> enable_commands(path) |> Enum.each(&cmd/1)
+      Mix.shell().info("Panko service enabled")
+    end
+  end
+
+  defp uninstall(manager, path) do
+    if File.exists?(path) do
+      manager |> disable_commands(path) |> Enum.each(&cmd/1)
+      File.rm!(path)
+      Mix.shell().info("Removed #{path}")
+    else
+      Mix.shell().info("No service installed at #{path}")
+    end
+  end
+
+  defp paths(opts) do
+    release = Path.expand(opts[:release] || "_build/prod/rel/panko")
+    env_file = Path.expand(opts[:env_file] || "~/.config/panko/env")
+    {Path.join([release, "bin", "server"]), env_file}
+  end
+
+  defp service(manager, opts) do
+    {server, env_file} = paths(opts)
+    service(manager, server, env_file)
+  end
+
+  defp service(:systemd, server, env_file) do
+    """
+    [Unit]
+    Description=Panko session viewer
+    After=network-online.target
+
+    [Service]
+    Type=exec
+    EnvironmentFile=-#{env_file}
+    Environment=PHX_SERVER=true
+    ExecStart=#{server}
+    Restart=on-failure
+    RestartSec=5
+
+    [Install]
+    WantedBy=default.target
+    """
+  end
+
+  defp service(:launchd, server, env_file) do
+    # launchd has no environment files, so a shell loads it before exec.
+    script = ~s(set -a; [ -f "#{env_file}" ] && . "#{env_file}"; exec "#{server}")
+
+    """
+    <?xml version="1.0" encoding="UTF-8"?>
+    <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+    <plist version="1.0">
+    <dict>
+      <key>Label</key>
+      <string>#{@label}</string>
+      <key>ProgramArguments</key>
+      <array>
+        <string>/bin/sh</string>
+        <string>-c</string>
+        <string>#{xml_escape(script)}</string>
+      </array>
+      <key>EnvironmentVariables</key>
+      <dict>
+        <key>PHX_SERVER</key>
+        <string>true</string>
+      </dict>
+      <key>RunAtLoad</key>
+      <true/>
+      <key>KeepAlive</key>
+      <true/>
+      <key>StandardOutPath</key>
+      <string>#{xml_escape(Path.expand("~/Library/Logs/panko.log"))}</string>
+      <key>StandardErrorPath</key>
+      <string>#{xml_escape(Path.expand("~/Library/Logs/panko.log"))}</string>
+    </dict>
+    </plist>
+    """
+  end
+
+  defp enable_commands(:systemd, _path) do
+    [~w(systemctl --user daemon-reload), ~w(systemctl --user enable --now panko.service)]
+  end
+
+  defp enable_commands(:launchd, path), do: [["launchctl", "load", "-w", path]]
+
+  defp disable_commands(:systemd, _path) do
+    [~w(systemctl --user disable --now panko.service), ~w(systemctl --user daemon-reload)]
+  end
+
+  defp disable_commands(:launchd, path), do: [["launchctl", "unload", "-w", path]]
+
+  defp cmd([command | args] = argv) do
+    unless System.find_executable(command) do
+      Mix.raise("#{command} not found; run `#{Enum.join(argv, " ")}` yourself")
+    end
+
+    case System.cmd(command, args, stderr_to_stdout: true) do
+      {_output, 0} -> :ok
+      {output, status} -> Mix.raise("`#{Enum.join(argv, " ")}` failed (#{status}): #{output}")
+    end
+  end
+
+  defp xml_escape(text) do
+    text
+    |> String.replace("&", "&amp;")
+    |> String.replace("<", "&lt;")
+    |> String.replace(">", "&gt;")
+    |> String.replace("\"", "&quot;")
+  end
+end
@@ -0,0 +1,94 @@
+defmodule Mix.Tasks.Panko.Grep do
+  @moduledoc """
+  Searches session files with a regular expression.
+
+  Searches the given session files, the sessions of `--project`, or every
+  session under the watch paths. Each match is printed as
+  `FILE#BLOCK:LINE: text`, with context lines marked by `-` as in `grep`.
+
+  ## Usage
+
+      mix panko.grep "mix (test|compile)"
+      mix panko.grep -i "auth.*token" --project /home/me/code/my-app -A 2
+      mix panko.grep "TODO" session.jsonl -B 1 -A 1
+
+  ## Options
+
+    * `-A` / `--after` - context lines after each match
+    * `-B` / `--before` - context lines before each match
+    * `-i` / `--ignore-case` - match case-insensitively
+    * `--project` - search the sessions of this project
+    * `--count` - only print the number of matches per file
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sessions.{Exporters, Grep, SessionFiles}
+
+  @shortdoc "Searches session files with a regex"
+
+  @switches [
+    after: :integer,
+    before: :integer,
+    ignore_case: :boolean,
+    project: :string,
+    count: :boolean
+  ]
+
+  @aliases [A: :after, B: :before, i: :ignore_case]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, pattern, files} =
+      case OptionParser.parse(args, strict: @switches, aliases: @aliases) do
+        {opts, [pattern | files], []} -> {opts, pattern, files}
+        _ ->
+          ExitStatus.raise!(:usage, "Usage: mix panko.grep PATTERN [FILE...] [-A N] [-B N] [-i]")
+      end
+
+    Mix.Task.run("app.config")
+
+    regex =
+      case Regex.compile(pattern, if(opts[:ignore_case], do: "iu", else: "u")) do
+        {:ok, regex} -> regex
+        {:error, {reason, _}} -> ExitStatus.raise!(:usage, "Invalid pattern: #{reason}")
+      end
+
+    files =
+      cond do
+        files != [] -> files
+        opts[:project] -> SessionFiles.project_files(opts[:project])
+        true -> SessionFiles.all_files()
+      end
+
+    Enum.each(files, &grep_file(&1, regex, opts))
+  end
+
+  defp grep_file(path, regex, opts) do
+    context = [before: opts[:before] || 0, after: opts[:after] || 0]
+
+    with {:ok, session} <- Exporters.load_file(path),
+         [_ | _] = hits <- Grep.grep(session, regex, context) do
+      if opts[:count] do
+        Mix.shell().info("#{path}: #{length(hits)}")
+      else
+        Enum.each(hits, &print_hit(path, &1))
+      end
+    else
+      [] -> :ok
+      {:error, reason} -> Mix.shell().error("#{path}: #{inspect(reason)}")
+    end
+  end
+
+  defp print_hit(path, hit) do
+    print_context(path, hit.position, hit.before, hit.line - length(hit.before))
+    Mix.shell().info("#{path}##{hit.position}:#{hit.line}: #{hit.text}")
+    print_context(path, hit.position, hit.after, hit.line + 1)
+  end
+
+  defp print_context(path, position, lines, first_line) do
+    lines
+    |> Enum.with_index(first_line)
+    |> Enum.each(fn {text, line} -> Mix.shell().info("#{path}##{position}-#{line}- #{text}") end)
+  end
+end
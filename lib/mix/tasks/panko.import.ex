@@ -0,0 +1,76 @@
+defmodule Mix.Tasks.Panko.Import do
+  @moduledoc """
+  Imports transcripts exported from other tools as Panko sessions.
+
+  Accepts ChatGPT data exports (`conversations.json`), JSON message arrays
+  in the OpenAI or Anthropic API format, and Panko's own JSON exports. A
+  file may hold several conversations; each becomes its own session, which
+  can then be browsed and shared like any other. Importing the same file
+  again updates the sessions instead of duplicating them.
+
+  ## Usage
+
+      mix panko.import ~/Downloads/conversations.json
+      mix panko.import chat.json --project ~/src/app --title "Schema design"
+
+  ## Options
+
+    * `--project` - project path to file the sessions under
+    * `--title` - session title (only when the file holds one conversation)
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sessions
+  alias Panko.Sessions.Parsers.Transcript
+
+  @shortdoc "Imports transcripts from other tools"
+
+  @switches [project: :string, title: :string]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, path} =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, [path], []} -> {opts, Path.expand(path)}
+        _ ->
+          ExitStatus.raise!(
+            :usage,
+            "Usage: mix panko.import FILE [--project PATH] [--title TITLE]"
+          )
+      end
+
+    Mix.Task.run("app.start")
+
+    sessions =
+      case Transcript.parse_all(path) do
+        {:ok, sessions} -> sessions
+        {:error, reason} ->
+          ExitStatus.raise!(:parse_error, "Could not import #{path}: #{inspect(reason)}")
+      end
+
+    if opts[:title] && length(sessions) > 1 do
+      ExitStatus.raise!(:usage, "--title needs a file with a single conversation")
+    end
+
+    for attrs <- sessions do
+      attrs =
+        attrs
+        |> put_present(:project, opts[:project] && Path.expand(opts[:project]))
+        |> put_present(:title, opts[:title])
+
+      case Sessions.upsert_session(attrs) do
+        {:ok, session} ->
+          Mix.shell().info("#{session.id}  #{session.title || "Untitled session"}")
+
+        {:error, error} ->
+          Mix.shell().error("Skipped #{attrs.external_id}: #{Exception.message(error)}")
+      end
+    end
+
+    Mix.shell().info("Imported #{length(sessions)} session(s) from #{path}")
+  end
+
+  defp put_present(attrs, _key, nil), do: attrs
+  defp put_present(attrs, key, value), do: Map.put(attrs, key, value)
+end
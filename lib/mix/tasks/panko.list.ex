@@ -0,0 +1,112 @@
+defmodule Mix.Tasks.Panko.List do
+  @moduledoc """
+  Lists imported sessions, newest first.
+
+  Prints one session per line, so scripts can find sessions and pass their
+  file or id on, e.g. to `mix panko.share`. Token counts and estimated
+  cost come from the usage cached at import.
+
+  ## Usage
+
+      mix panko.list
+      mix panko.list --project /home/me/code/my-app --since 7d
+      mix panko.list --limit 1 --tsv | tail -1 | cut -f1 | xargs mix panko.share
+
+  ## Options
+
+    * `--project` - only sessions of this project
+    * `--since` - only sessions started within this duration, e.g. `12h` or `7d`
+    * `--limit` - at most this many sessions
+    * `--json` - print a JSON array
+    * `--tsv` - print tab-separated values with a header row
+    * `--color` - `always`, `never` or `auto` (default), which colors output
+      on a terminal unless `NO_COLOR` is set
+  """
+  use Mix.Task
+
+  alias Mix.Panko.Duration
+  alias Mix.Panko.ExitStatus
+  alias Mix.Panko.Style
+  alias Panko.Sessions
+
+  @shortdoc "Lists imported sessions"
+
+  @switches [
+    project: :string,
+    since: :string,
+    limit: :integer,
+    json: :boolean,
+    tsv: :boolean,
+    color: :string
+  ]
+
+  # New columns go last, so scripts reading columns by position keep working.
+  @columns ~w(id external_id project title started_at message_count block_count
+              source_path input_tokens output_tokens estimated_cost_usd)a
+
+  @impl Mix.Task
+  def run(args) do
+    opts =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, [], []} ->
+          opts
+
+        _ ->
+          ExitStatus.raise!(
+            :usage,
+            "Usage: mix panko.list [--project PATH] [--since DURATION] [--limit N] [--json|--tsv]"
+          )
+      end
+
+    since = Duration.ago!(opts[:since], "--since")
+    Style.setup(opts)
+    Mix.Task.run("app.start")
+
+    sessions =
+      %{project: opts[:project], since: since}
+      |> Sessions.list_filtered_sessions!()
+      |> then(&if(opts[:limit], do: Enum.take(&1, opts[:limit]), else: &1))
+
+    rows = Enum.map(sessions, &Map.take(&1, @columns))
+
+    cond do
+      opts[:json] -> Mix.shell().info(Jason.encode!(rows, pretty: true))
+      opts[:tsv] -> print_tsv(rows)
+      true -> print_text(sessions)
+    end
+  end
+
+  defp print_tsv(rows) do
+    Mix.shell().info(Enum.join(@columns, "\t"))
+
+    for row <- rows do
+      @columns
+      |> Enum.map(&tsv_value(row[&1]))
+      |> Enum.join("\t")
+      |> Mix.shell().info()
+    end
+  end
+
+  defp print_text([]), do: Mix.shell().info("No sessions found")
+
+  defp print_text(sessions) do
+    for session <- sessions do
+      date = if session.started_at, do: Calendar.strftime(session.started_at, "%Y-%m-%d %H:%M")
+
+      Mix.shell().info(
+        "#{Style.faint(date || "")}  #{session.id}  " <>
+          "#{Style.heading(session.title || "Untitled session")}  " <>
+          "(#{session.message_count || 0} msgs#{cost(session)})  " <>
+          Style.faint(session.project || "")
+      )
+    end
+  end
+
+  defp cost(%{estimated_cost_usd: cost}) when is_float(cost),
+    do: ", $" <> :erlang.float_to_binary(cost, decimals: 2)
+
+  defp cost(_session), do: ""
+
+  defp tsv_value(nil), do: ""
+  defp tsv_value(value), do: value |> to_string() |> String.replace(~r/[\t\n]/, " ")
+end
@@ -0,0 +1,130 @@
+defmodule Mix.Tasks.Panko.Open do
+  @moduledoc """
+  Opens a session in the browser.
+
+  `latest` picks the most recently modified session file under the watch
+  paths, imports it so the viewer is up to date, and opens it. The Panko
+  server must be running to view the page.
+
+  ## Usage
+
+      mix panko.open latest
+      mix panko.open latest --project /home/me/code/my-app
+      mix panko.open 0b6f6c0e-...
+      mix panko.open latest --follow
+      mix panko.open latest --open-with "firefox -P work {url}"
+
+  ## Options
+
+    * `--project` - only consider sessions of this project
+    * `--no-browser` - print the URL without opening a browser
+    * `--follow` - open the page in follow mode, which scrolls to new blocks
+      as the server's session watcher re-imports the file
+    * `--open-with` - command template to open the URL with, overriding
+      `PANKO_BROWSER` for this call
+    * `--no-reuse` - always open a new tab
+
+  The URL opens with the system's default opener, or with `PANKO_BROWSER`
+  when set, a command template such as `firefox -P work {url}`.
+
+  A page opened this way carries a one-off token. When another tab already
+  shows the session, that tab takes over `--follow` and the new one closes
+  itself, so opening the same session repeatedly does not pile up tabs.
+  Browsers only let a tab close itself when a script opened it; elsewhere
+  the new tab stays a working session page.
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sessions
+  alias Panko.Sessions.SessionFiles
+
+  @shortdoc "Opens the latest (or a given) session in the browser"
+
+  @switches [
+    project: :string,
+    browser: :boolean,
+    follow: :boolean,
+    open_with: :string,
+    reuse: :boolean
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, target} =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, [target], []} -> {opts, target}
+        _ -> ExitStatus.raise!(:usage, "Usage: mix panko.open latest|SESSION_ID [--project PATH]")
+      end
+
+    Mix.Task.run("app.start")
+
+    session = find_session!(target, opts)
+    url = PankoWeb.Endpoint.url() <> "/sessions/#{session.id}"
+    url = if opts[:follow], do: url <> "?follow=1", else: url
+
+    Mix.shell().info(url)
+
+    if Keyword.get(opts, :browser, true) do
+      url = if Keyword.get(opts, :reuse, true), do: with_token(url), else: url
+      open_browser(url, opts[:open_with] || Application.get_env(:panko, :browser_command))
+    end
+  end
+
+  defp find_session!("latest", opts) do
+    files =
+      if opts[:project],
+        do: SessionFiles.project_files(opts[:project]),
+        else: SessionFiles.all_files()
+
+    case SessionFiles.latest(files) do
+      nil ->
+        ExitStatus.raise!(:not_found, "No session files found")
+
+      path ->
+        case Sessions.import_from_file(path) do
+          {:ok, session} -> session
+          {:error, reason} ->
+            ExitStatus.raise!(:parse_error, "Failed to import #{path}: #{inspect(reason)}")
+        end
+    end
+  end
+
+  defp find_session!(id, _opts) do
+    case Sessions.get_session(id) do
+      {:ok, session} -> session
+      {:error, _} -> ExitStatus.raise!(:not_found, "No session with id #{inspect(id)}")
+    end
+  end
+
+  # Marks the page as opened by this task, see the tab reuse in app.js.
+  defp with_token(url) do
+    token = 8 |> :crypto.strong_rand_bytes() |> Base.url_encode64(padding: false)
+    url |> URI.parse() |> URI.append_query("opened=" <> token) |> URI.to_string()
+  end
+
+  # A blank template, such as an empty `PANKO_BROWSER`, counts as unset.
+  # The browser is started without waiting for it, as not every browser
+  # forks into the background.
+  defp open_browser(url, template) do
+    {command, args} =
+      case {template && OptionParser.split(template), :os.type()} do
+        {[command | args], _} -> {command, with_url(args, url)}
+        {_, {:unix, :darwin}} -> {"open", [url]}
+        {_, {:win32, _}} -> {"cmd", ["/c", "start", "", url]}
+        {_, _} -> {"xdg-open", [url]}
+      end
+
+    case System.find_executable(command) do
+      nil -> Mix.shell().error("#{command} not found; open the URL yourself")
+      executable -> Port.open({:spawn_executable, executable}, [:nouse_stdio, args: args])
+    end
+  end
+
+  # `{url}` marks where the URL goes; without it the URL is appended.
+  defp with_url(args, url) do
+    if Enum.any?(args, &String.contains?(&1, "{url}")),
+      do: Enum.map(args, &String.replace(&1, "{url}", url)),
+      else: args ++ [url]
+  end
+end
@@ -0,0 +1,105 @@
+defmodule Mix.Tasks.Panko.Prune do
+  @moduledoc """
+  Trashes or archives old and trivial sessions.
+
+  Sessions matching all given criteria are removed from Panko and their
+  files are moved to the desktop trash, into `--archive`, or with
+  `--permanent` (or `PANKO_PRUNE_PERMANENT=true`) deleted. At least one of
+  `--older-than` or `--max-messages` is required. Run with `--dry-run`
+  first to see what would be pruned.
+
+  ## Usage
+
+      mix panko.prune --older-than 90d --dry-run
+      mix panko.prune --max-messages 2
+      mix panko.prune --older-than 180d --project /home/me/code/old-app --archive ~/panko-archive
+
+  ## Options
+
+    * `--older-than` - no activity within this duration, e.g. `90d` or `12w`
+    * `--max-messages` - at most this many prompts and responses
+    * `--project` - only sessions of this project
+    * `--archive` - move files into this directory instead of the trash
+    * `--permanent` - delete files instead of moving them to the trash
+    * `--dry-run` - list the sessions without pruning them
+  """
+  use Mix.Task
+
+  alias Mix.Panko.Duration
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sessions.Prune
+
+  @shortdoc "Trashes or archives old sessions"
+
+  @switches [
+    older_than: :string,
+    max_messages: :integer,
+    project: :string,
+    archive: :string,
+    permanent: :boolean,
+    dry_run: :boolean
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    opts =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, [], []} -> opts
+        _ -> usage!()
+      end
+
+    if is_nil(opts[:older_than]) and is_nil(opts[:max_messages]), do: usage!()
+
+    older_than = Duration.ago!(opts[:older_than], "--older-than")
+    Mix.Task.run("app.start")
+
+    sessions =
+      Prune.candidates(
+        older_than: older_than,
+        max_messages: opts[:max_messages],
+        project: opts[:project]
+      )
+
+    Enum.each(sessions, &Mix.shell().info(describe(&1)))
+
+    cond do
+      sessions == [] ->
+        Mix.shell().info("No sessions to prune")
+
+      opts[:dry_run] ->
+        Mix.shell().info("Would prune #{length(sessions)} session(s)")
+
+      true ->
+        archive = opts[:archive] && Path.expand(opts[:archive])
+        permanent = Keyword.get(opts, :permanent, Application.get_env(:panko, :prune_permanently))
+        {pruned, failed} = Prune.prune(sessions, archive: archive, permanent: permanent)
+
+        for {session, reason} <- failed do
+          Mix.shell().error("Failed to prune #{session.source_path}: #{inspect(reason)}")
+        end
+
+        verb =
+          cond do
+            archive -> "Archived"
+            permanent -> "Deleted"
+            true -> "Trashed"
+          end
+
+        Mix.shell().info("#{verb} #{length(pruned)} session(s), #{length(failed)} failed")
+    end
+  end
+
+  # Unknown switches must not fall through, as a misspelled `--dry-run`
+  # would otherwise prune for real.
+  defp usage! do
+    ExitStatus.raise!(
+      :usage,
+      "Usage: mix panko.prune --older-than DURATION | --max-messages N [--dry-run]"
+    )
+  end
+
+  defp describe(session) do
+    date = Calendar.strftime(session.last_activity_at || session.started_at, "%Y-%m-%d")
+    "#{date}  #{session.message_count || 0} msgs  #{session.title || "Untitled session"}"
+  end
+end
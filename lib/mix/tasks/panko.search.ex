@@ -0,0 +1,71 @@
+defmodule Mix.Tasks.Panko.Search do
+  @moduledoc """
+  Searches all imported sessions for a phrase.
+
+  Matching is case-insensitive. Sessions with the most matches come first,
+  each followed by its matching lines as `#block:line  snippet`.
+
+  ## Usage
+
+      mix panko.search "auth bug"
+      mix panko.search "auth bug" --project /home/me/code/my-app --limit 5
+      mix panko.search "auth bug" --sessions-only --json
+
+  ## Options
+
+    * `--project` - only search sessions of this project
+    * `--limit` - at most this many sessions, the most recent ones (default: 20)
+    * `--sessions-only` - list sessions without matching lines
+    * `--json` - print JSON
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sessions.Search
+
+  @shortdoc "Searches all sessions for a phrase"
+
+  @switches [project: :string, limit: :integer, sessions_only: :boolean, json: :boolean]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, query} =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, [_ | _] = words, []} -> {opts, Enum.join(words, " ")}
+        _ -> ExitStatus.raise!(:usage, "Usage: mix panko.search QUERY [--project PATH] [--json]")
+      end
+
+    Mix.Task.run("app.start")
+
+    results = Search.search(query, project: opts[:project], limit: opts[:limit] || 20)
+
+    cond do
+      opts[:json] -> Mix.shell().info(Jason.encode!(Enum.map(results, &to_map(&1, opts))))
+      results == [] -> Mix.shell().info("No sessions match #{inspect(query)}")
+      true -> Enum.each(results, &print_result(&1, opts))
+    end
+  end
+
+  defp print_result(%{session: session, matches: matches}, opts) do
+    Mix.shell().info(
+      "#{session.title || "Untitled session"}  #{session.id}  (#{length(matches)} matches)"
+    )
+
+    unless opts[:sessions_only] do
+      Enum.each(matches, &Mix.shell().info("  ##{&1.position}:#{&1.line}  #{&1.snippet}"))
+      Mix.shell().info("")
+    end
+  end
+
+  defp to_map(%{session: session, matches: matches}, opts) do
+    %{
+      id: session.id,
+      title: session.title,
+      project: session.project,
+      source_path: session.source_path,
+      started_at: session.started_at,
+      match_count: length(matches),
+      matches: if(opts[:sessions_only], do: nil, else: matches)
+    }
+  end
+end
@@ -0,0 +1,133 @@
+defmodule Mix.Tasks.Panko.Serve do
+  @moduledoc """
+  Starts the Panko server and prints the URL it is reachable at.
+
+  With `--port 0` the OS picks a free port, and with `--print-url` the URL
+  is the only output, so editor plugins can start a viewer and read its
+  address from the first line of stdout.
+
+  When listening on all interfaces (`PANKO_HTTP_IP=0.0.0.0` or `::`), the
+  addresses teammates on the same network or tailnet can use are listed
+  too.
+
+  ## Usage
+
+      mix panko.serve
+      mix panko.serve --port 0 --print-url
+      mix panko.serve -vv
+
+  ## Options
+
+    * `--port` - port to listen on, `0` for any free port (default: `PORT` or 4000)
+    * `--print-url` - print only the URL and silence logs
+    * `-v` / `--verbose` - log each request; `-vv` also logs LiveView events
+      and database queries. Without it only warnings, such as slow requests
+      (`PANKO_SLOW_REQUEST_MS`), are logged
+
+  ## Exit status
+
+  Exits with status 6 when the port is already in use; see
+  `Mix.Panko.ExitStatus`.
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+
+  @shortdoc "Starts the server and prints its URL"
+
+  @switches [port: :integer, print_url: :boolean, verbose: :count]
+
+  @impl Mix.Task
+  def run(args) do
+    opts =
+      case OptionParser.parse(args, strict: @switches, aliases: [v: :verbose]) do
+        {opts, [], []} -> opts
+        _ -> ExitStatus.raise!(:usage, "Usage: mix panko.serve [--port PORT] [--print-url] [-v]")
+      end
+
+    Logger.configure(level: log_level(opts))
+
+    Mix.Task.run("app.config")
+
+    endpoint_config = Application.get_env(:panko, PankoWeb.Endpoint, [])
+    http = Keyword.get(endpoint_config, :http, [])
+    http = if opts[:port], do: Keyword.put(http, :port, opts[:port]), else: http
+
+    Application.put_env(
+      :panko,
+      PankoWeb.Endpoint,
+      Keyword.merge(endpoint_config, server: true, http: http)
+    )
+
+    ensure_port_free!(http)
+    Mix.Task.run("app.start")
+
+    {:ok, {ip, port}} = PankoWeb.Endpoint.server_info(:http)
+    url = "http://#{host(ip)}:#{port}"
+
+    if opts[:print_url] do
+      IO.puts(url)
+    else
+      Mix.shell().info("Panko is running at #{url}")
+      print_lan_urls(ip, port)
+    end
+
+    Process.sleep(:infinity)
+  end
+
+  # Checked up front so a taken port is reported plainly instead of as an
+  # endpoint crash.
+  defp ensure_port_free!(http) do
+    port = Keyword.get(http, :port, 4000)
+    ip_opts =
+      case http[:ip] do
+        nil -> []
+        ip when tuple_size(ip) == 8 -> [:inet6, ip: ip]
+        ip -> [ip: ip]
+      end
+
+    if is_integer(port) and port != 0 do
+      case :gen_tcp.listen(port, [reuseaddr: true] ++ ip_opts) do
+        {:ok, socket} ->
+          :gen_tcp.close(socket)
+
+        {:error, :eaddrinuse} ->
+          ExitStatus.raise!(:port_unavailable, "Port #{port} is already in use")
+
+        {:error, _} ->
+          :ok
+      end
+    end
+  end
+
+  defp log_level(opts) do
+    case {opts[:print_url], opts[:verbose]} do
+      {true, _} -> :error
+      {_, nil} -> :warning
+      {_, 1} -> :info
+      _ -> :debug
+    end
+  end
+
+  defp print_lan_urls(ip, port) do
+    case PankoWeb.LanUrls.list(ip, port) do
+      [] ->
+        :ok
+
+      entries ->
+        Mix.shell().info("On your network:")
+
+        for entry <- entries do
+          label = if entry.tailscale, do: "tailscale", else: entry.interface
+          Mix.shell().info("  #{entry.url}  (#{label})")
+        end
+    end
+  end
+
+  # The server itself speaks plain HTTP; TLS is left to a reverse proxy.
+  defp host(ip) when ip in [{0, 0, 0, 0}, {0, 0, 0, 0, 0, 0, 0, 0}], do: "localhost"
+  defp host({127, 0, 0, 1}), do: "localhost"
+  defp host({0, 0, 0, 0, 0, 0, 0, 1}), do: "localhost"
+  defp host({_, _, _, _} = ip), do: to_string(:inet.ntoa(ip))
+  defp host(ip), do: "[#{:inet.ntoa(ip)}]"
+end
@@ -0,0 +1,209 @@
+defmodule Mix.Tasks.Panko.Share do
+  @moduledoc """
+  Shares a session and prints its public URL.
+
+  The session can be given as a session file, which is imported first, as
+  a session id, or as the agent's own session id (`external_id`).
+
+  Only the URL is written to stdout, so the task can be used in scripts
+  and CI. Errors go to stderr with a non-zero exit status.
+
+  The session is audited first (see `Panko.Sessions.Privacy`). When it
+  contains likely secrets, email addresses, internal hosts or large
+  embedded files, they are listed on stderr and the share needs confirming
+  there, unless `--yes` is given. Declining exits with status 9, as does a
+  run whose stdin is not a terminal, where nobody can confirm: scripts pass
+  `--yes` after checking the session with `mix panko.check --privacy`.
+
+  ## Usage
+
+      mix panko.share ~/.claude/projects/-home-me-app/abc123.jsonl
+      mix panko.share 0b6f6c0e-... --name sprint-demo
+      mix panko.share abc123 --expires 2w
+      mix panko.share abc123 --snapshot
+      mix panko.share abc123 --preset demo
+      URL=$(mix panko.share session.jsonl --quiet --timeout 30)
+
+  ## Options
+
+    * `--name` - a memorable slug, so the share lives at `/s/<name>`.
+      Must be unique and use lowercase letters, digits and dashes.
+    * `--expires` - how long the share stays up, e.g. `12h`, `7d` or `never`
+      (default: `PANKO_DEFAULT_EXPIRY`)
+    * `--snapshot` - show viewers the session as it is now, leaving out
+      anything the agent adds later (default: `PANKO_SHARE_SNAPSHOTS`);
+      `--no-snapshot` always shares it live
+    * `--preset` - start from a preset of `PANKO_SHARE_PRESETS` (see
+      `Panko.Sharing.Presets`); options given as well override it
+    * `--yes` - share without confirming privacy findings
+    * `--json` - print the URL, slug, session id and expiry as JSON
+    * `--quiet` - silence application logs while starting
+    * `--timeout` - wait up to this many seconds for the share URL to be
+      reachable, and fail if it is not
+    * `--color` - `always`, `never` or `auto` (default), which colors output
+      on a terminal unless `NO_COLOR` is set
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Mix.Panko.Style
+  alias Panko.Sessions
+  alias Panko.Sessions.Privacy
+  alias Panko.Sharing
+  alias Panko.Sharing.Presets
+
+  @shortdoc "Shares a session and prints its URL"
+
+  @switches [
+    name: :string,
+    expires: :string,
+    snapshot: :boolean,
+    preset: :string,
+    yes: :boolean,
+    json: :boolean,
+    quiet: :boolean,
+    timeout: :integer,
+    color: :string
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, session_ref} =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, [session_ref], []} -> {opts, session_ref}
+        _ ->
+          ExitStatus.raise!(
+            :usage,
+            "Usage: mix panko.share SESSION [--name NAME] [--expires DURATION]"
+          )
+      end
+
+    if opts[:quiet], do: Logger.configure(level: :error)
+    Style.setup(opts)
+    Mix.Task.run("app.start")
+
+    session = find_session!(session_ref)
+    unless opts[:yes], do: confirm_privacy!(session)
+
+    params =
+      %{slug: opts[:name], expires_in: opts[:expires], snapshot: opts[:snapshot]}
+      |> Map.reject(fn {_key, value} -> is_nil(value) end)
+      |> then(&Map.merge(preset!(opts[:preset]), &1))
+
+    case Sharing.create_share(session.id, params) do
+      {:ok, share} ->
+        url = PankoWeb.Endpoint.url() <> "/s/#{share.slug}"
+        if opts[:timeout], do: await_reachable!(url, opts[:timeout])
+
+        if opts[:json] do
+          %{url: url, slug: share.slug, session_id: session.id, expires_at: share.expires_at}
+          |> Jason.encode!(pretty: true)
+          |> Mix.shell().info()
+        else
+          Mix.shell().info(Style.format([:underline, url]))
+        end
+
+      {:error, error} ->
+        Mix.raise("Could not share session: #{Exception.message(error)}")
+    end
+  end
+
+  defp confirm_privacy!(session) do
+    findings = session |> Ash.load!(:blocks) |> Privacy.scan()
+
+    if findings != [] do
+      Mix.shell().error("This session contains #{Privacy.describe(findings)}:")
+
+      for finding <- Enum.take(findings, 10) do
+        where = if finding.position, do: "block #{finding.position}", else: "title"
+        Mix.shell().error("  #{finding.rule}: #{finding.match} (#{where})")
+      end
+
+      if length(findings) > 10 do
+        Mix.shell().error("  ... and #{length(findings) - 10} more (mix panko.check --privacy)")
+      end
+
+      cond do
+        not interactive?() ->
+          ExitStatus.raise!(:privacy, "Not shared; pass --yes to share without confirming")
+
+        not confirm?("Share it anyway?") ->
+          ExitStatus.raise!(:privacy, "Not shared")
+
+        true ->
+          :ok
+      end
+    end
+  end
+
+  # Asked on stderr, as stdout only ever carries the URL.
+  defp confirm?(question) do
+    IO.write(:stderr, question <> " [y/N] ")
+
+    case IO.gets(:stdio, "") do
+      answer when is_binary(answer) -> String.downcase(String.trim(answer)) in ["y", "yes"]
+      _eof_or_error -> false
+    end
+  end
+
+  defp interactive?, do: Keyword.get(:io.getopts(:standard_io), :stdin, false) == true
+
+  defp preset!(nil), do: %{}
+
+  defp preset!(name) do
+    case Presets.parse(Application.get_env(:panko, :share_presets)) do
+      {:ok, %{^name => params}} ->
+        params
+
+      {:ok, presets} ->
+        known =
+          if presets == %{},
+            do: "PANKO_SHARE_PRESETS is not set",
+            else: "configured: " <> (presets |> Map.keys() |> Enum.sort() |> Enum.join(", "))
+
+        ExitStatus.raise!(:usage, "Unknown preset #{inspect(name)}; #{known}")
+
+      {:error, message} ->
+        ExitStatus.raise!(:usage, "Invalid PANKO_SHARE_PRESETS: #{message}")
+    end
+  end
+
+  # The share is live as soon as it is saved, but only reachable while the
+  # server (or the proxy in front of it) is up.
+  defp await_reachable!(url, timeout) do
+    {:ok, _} = Application.ensure_all_started([:inets, :ssl])
+    deadline = System.monotonic_time(:millisecond) + timeout * 1000
+    await_reachable(url, deadline)
+  end
+
+  defp await_reachable(url, deadline) do
+    options = Panko.HTTP.options(timeout: 5_000)
+
+    case :httpc.request(:get, {String.to_charlist(url), []}, options, []) do
+      {:ok, {{_, 200, _}, _, _}} ->
+        :ok
+
+      _ ->
+        if System.monotonic_time(:millisecond) >= deadline do
+          ExitStatus.raise!(:unreachable, "#{url} is not reachable; is the Panko server running?")
+        end
+
+        Process.sleep(1_000)
+        await_reachable(url, deadline)
+    end
+  end
+
+  defp find_session!(ref) do
+    result =
+      cond do
+        File.regular?(ref) -> Sessions.import_from_file(Path.expand(ref))
+        match?({:ok, _}, Ecto.UUID.cast(ref)) -> Sessions.get_session(ref)
+        true -> Sessions.get_session_by_external_id(ref)
+      end
+
+    case result do
+      {:ok, session} -> session
+      {:error, _} -> ExitStatus.raise!(:not_found, "No session found for #{inspect(ref)}")
+    end
+  end
+end
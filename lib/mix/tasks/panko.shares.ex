@@ -0,0 +1,226 @@
+defmodule Mix.Tasks.Panko.Shares do
+  @moduledoc """
+  Lists and manages shared sessions.
+
+  ## Usage
+
+      mix panko.shares list
+      mix panko.shares show abc12345
+      mix panko.shares stop abc12345
+      mix panko.shares stop-all
+      mix panko.shares prune --dry-run
+      mix panko.shares browse
+      mix panko.shares list --json
+
+  ## Commands
+
+    * `list` - active shares with their URL, session, expiry, views, unique visitors
+      and bytes served, flagging sessions whose source file is gone
+    * `show <slug>` - details of one share, including unpublished ones
+    * `stop <slug>` - unpublish a share
+    * `stop-all` - unpublish every active share
+    * `prune` - delete stopped shares outside the retention policy
+      (`PANKO_MAX_STOPPED_SHARE_AGE` and `PANKO_MAX_SHARE_HISTORY`)
+    * `browse` - live shares of the whole team, from `PANKO_TEAM_REGISTRY_URL`
+
+  ## Options
+
+    * `--json` - print JSON instead of text
+    * `--dry-run` - with `prune`, only list the shares that would be deleted
+    * `--max-age` - with `prune`, override the maximum age of stopped shares
+    * `--max-history` - with `prune`, override the number of stopped shares kept
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Panko.Sharing
+
+  @shortdoc "Lists and manages shared sessions"
+
+  @switches [json: :boolean, dry_run: :boolean, max_age: :string, max_history: :integer]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, command} =
+      case OptionParser.parse(args, strict: @switches) do
+        {opts, command, []} -> {opts, command}
+        _ -> usage!()
+      end
+
+    Mix.Task.run("app.start")
+
+    case command do
+      ["list"] -> list(opts)
+      ["show", slug] -> show(slug, opts)
+      ["stop", slug] -> stop(slug, opts)
+      ["stop-all"] -> stop_all(opts)
+      ["prune"] -> prune(opts)
+      ["browse"] -> browse(opts)
+      _ -> usage!()
+    end
+  end
+
+  defp usage! do
+    ExitStatus.raise!(
+      :usage,
+      "Usage: mix panko.shares list|show SLUG|stop SLUG|stop-all|prune|browse"
+    )
+  end
+
+  defp list(opts) do
+    shares = Sharing.list_active_shares!(load: [:session, :unique_visitors, :status])
+
+    if opts[:json] do
+      print_json(Enum.map(shares, &to_map/1))
+    else
+      if shares == [], do: Mix.shell().info("No active shares")
+      Enum.each(shares, &Mix.shell().info(summary_line(&1)))
+    end
+  end
+
+  defp show(slug, opts) do
+    share = fetch!(slug)
+
+    if opts[:json] do
+      print_json(to_map(share))
+    else
+      share
+      |> to_map()
+      |> Enum.each(fn {key, value} -> Mix.shell().info("#{key}: #{format_value(value)}") end)
+    end
+  end
+
+  defp stop(slug, opts) do
+    case slug |> fetch!() |> Sharing.unpublish_share() do
+      {:ok, share} -> report_stopped([share], opts)
+      {:error, error} -> stop_failed!([{slug, error}])
+    end
+  end
+
+  # A failing share does not keep the others from being stopped.
+  defp stop_all(opts) do
+    {stopped, failed} =
+      Enum.reduce(Sharing.list_active_shares!(), {[], []}, fn share, {stopped, failed} ->
+        case Sharing.unpublish_share(share) do
+          {:ok, share} -> {[share | stopped], failed}
+          {:error, error} -> {stopped, [{share.slug, error} | failed]}
+        end
+      end)
+
+    report_stopped(Enum.reverse(stopped), opts)
+    if failed != [], do: stop_failed!(Enum.reverse(failed))
+  end
+
+  defp stop_failed!(failures) do
+    for {slug, error} <- failures do
+      Mix.shell().error("Failed to stop #{slug}: #{Exception.message(error)}")
+    end
+
+    ExitStatus.raise!(:registry_error, "Failed to stop #{length(failures)} share(s)")
+  end
+
+  defp prune(opts) do
+    retention = Keyword.take(opts, [:dry_run, :max_age, :max_history])
+
+    case Sharing.Retention.prune(retention) do
+      {:ok, pruned} ->
+        verb = if opts[:dry_run], do: "Would delete", else: "Deleted"
+
+        if opts[:json] do
+          print_json(%{dry_run: opts[:dry_run] || false, pruned: Enum.map(pruned, & &1.slug)})
+        else
+          Enum.each(pruned, &Mix.shell().info("#{&1.slug}  stopped #{&1.unshared_at}"))
+          Mix.shell().info("#{verb} #{length(pruned)} stopped share(s)")
+        end
+
+      {:error, :invalid_expiry} ->
+        ExitStatus.raise!(:usage, "Invalid --max-age; use a duration like 30d or never")
+    end
+  end
+
+  defp browse(opts) do
+    case Sharing.TeamRegistry.live_shares() do
+      {:ok, entries} when opts[:json] ->
+        print_json(entries)
+
+      {:ok, []} ->
+        Mix.shell().info("No live team shares")
+
+      {:ok, entries} ->
+        for entry <- entries do
+          owner = entry["owner"] || "unknown"
+          Mix.shell().info("#{entry["url"]}  #{entry["title"] || "Untitled session"}  (#{owner})")
+        end
+
+      {:error, :not_configured} ->
+        ExitStatus.raise!(:registry_error, "Set PANKO_TEAM_REGISTRY_URL to browse team shares")
+
+      {:error, reason} ->
+        ExitStatus.raise!(:registry_error, "Could not fetch team shares: #{inspect(reason)}")
+    end
+  end
+
+  defp report_stopped(shares, opts) do
+    if opts[:json] do
+      print_json(%{stopped: Enum.map(shares, & &1.slug)})
+    else
+      Mix.shell().info("Stopped #{length(shares)} share(s)")
+    end
+  end
+
+  defp fetch!(slug) do
+    case Sharing.get_share(slug, load: [:session, :unique_visitors, :status]) do
+      {:ok, share} -> share
+      {:error, _} -> ExitStatus.raise!(:not_found, "No share with slug #{inspect(slug)}")
+    end
+  end
+
+  defp to_map(share) do
+    %{
+      slug: share.slug,
+      url: url(share),
+      status: share.status,
+      active: share.is_shared,
+      session_id: share.session_id,
+      session_title: share.session.title,
+      project: share.session.project,
+      shared_at: share.shared_at,
+      expires_at: share.expires_at,
+      unshared_at: share.unshared_at,
+      view_count: share.view_count,
+      unique_visitors: share.unique_visitors,
+      bytes_served: share.bytes_served,
+      last_viewed_at: share.last_viewed_at,
+      source_missing: Panko.Sessions.SessionFiles.source_missing?(share.session)
+    }
+  end
+
+  defp summary_line(share) do
+    expires =
+      cond do
+        share.status == :expired -> "expired #{share.expires_at}"
+        share.expires_at -> "expires #{share.expires_at}"
+        true -> "never expires"
+      end
+
+    title = share.session.title || "Untitled session"
+
+    views =
+      "#{share.view_count} views, #{share.unique_visitors} visitors, " <>
+        "#{div(share.bytes_served, 1000)} KB served"
+
+    missing =
+      if Panko.Sessions.SessionFiles.source_missing?(share.session),
+        do: ", source missing",
+        else: ""
+
+    "#{share.slug}  #{url(share)}  #{title}  (#{views}, #{expires}#{missing})"
+  end
+
+  defp url(share), do: PankoWeb.Endpoint.url() <> "/s/#{share.slug}"
+
+  defp format_value(nil), do: "-"
+  defp format_value(value), do: to_string(value)
+
+  defp print_json(data), do: Mix.shell().info(Jason.encode!(data, pretty: true))
+end
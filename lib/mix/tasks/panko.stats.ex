@@ -0,0 +1,151 @@
+defmodule Mix.Tasks.Panko.Stats do
+  @moduledoc """
+  Reports usage statistics of imported sessions.
+
+  By default prints a report of total sessions, messages, tokens and
+  estimated cost, broken down per project, and the busiest days.
+  With `--export csv` it instead emits one row per session with its
+  project, start and end times, message and block counts, token usage,
+  estimated cost and the tools used.
+
+  ## Usage
+
+      mix panko.stats
+      mix panko.stats --since 30d --format json
+      mix panko.stats --export csv > sessions.csv
+      mix panko.stats --export csv --project /home/me/code/my-app -o my-app.csv
+
+  ## Options
+
+    * `--format` - report format: `table` (default), `json` or `csv`
+      (one line per project)
+    * `--export` - export per-session rows instead, currently `csv`
+    * `--project` - only include sessions of this project
+    * `--since` - only include sessions started within this duration, e.g. `30d`
+    * `--output` / `-o` - file to write instead of stdout
+    * `--color` - `always`, `never` or `auto` (default), which colors output
+      on a terminal unless `NO_COLOR` is set
+  """
+  use Mix.Task
+
+  alias Mix.Panko.Duration
+  alias Mix.Panko.ExitStatus
+  alias Mix.Panko.Style
+  alias Panko.Sessions
+  alias Panko.Sessions.Stats
+
+  @shortdoc "Reports per-project and per-session usage statistics"
+
+  @switches [
+    export: :string,
+    format: :string,
+    project: :string,
+    since: :string,
+    output: :string,
+    color: :string
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    opts =
+      case OptionParser.parse(args, strict: @switches, aliases: [o: :output]) do
+        {opts, [], []} ->
+          opts
+
+        _ ->
+          ExitStatus.raise!(
+            :usage,
+            "Usage: mix panko.stats [--format table|json|csv] [--export csv] [-o OUTPUT]"
+          )
+      end
+
+    # Files get plain text whatever the terminal supports.
+    Style.setup(if opts[:output], do: [color: "never"], else: opts)
+
+    render =
+      case {opts[:export], opts[:format] || "table"} do
+        {"csv", _} -> &Stats.to_csv/1
+        {nil, "table"} -> &(&1 |> Stats.report() |> table())
+        {nil, "json"} -> &(&1 |> Stats.report() |> Jason.encode_to_iodata!(pretty: true))
+        {nil, "csv"} -> &(&1 |> Stats.report() |> Map.fetch!(:projects) |> project_csv())
+        {nil, other} ->
+          ExitStatus.raise!(
+            :usage,
+            "Unknown format #{inspect(other)}. Available: table, json, csv"
+          )
+
+        {other, _} ->
+          ExitStatus.raise!(:usage, "Unknown export format #{inspect(other)}. Available: csv")
+      end
+
+    since = Duration.ago!(opts[:since], "--since")
+    Mix.Task.run("app.start")
+
+    filter = %{project: opts[:project], since: since}
+
+    # Only the per-session export has a tools column, which needs every block.
+    rows =
+      if opts[:export] do
+        filter
+        |> Sessions.list_filtered_sessions!(load: [:blocks])
+        |> Enum.map(&Stats.session_row/1)
+      else
+        Stats.stored_rows(filter)
+      end
+
+    output = render.(rows)
+
+    case opts[:output] do
+      nil ->
+        IO.binwrite(output)
+
+      path ->
+        File.write!(path, output)
+        Mix.shell().info("Wrote stats to #{path}")
+    end
+  end
+
+  defp table(report) do
+    [
+      Style.heading("Total") <> "\n",
+      table_rows([{"all projects", report.totals}]),
+      "\n" <> Style.heading("Projects") <> "\n",
+      table_rows(Enum.map(report.projects, &{&1.project, &1})),
+      "\n" <> Style.heading("Busiest days") <> "\n",
+      table_rows(Enum.map(report.busiest_days, &{Date.to_iso8601(&1.date), &1}))
+    ]
+  end
+
+  defp table_rows(rows) do
+    width = rows |> Enum.map(&String.length(elem(&1, 0))) |> Enum.max(fn -> 0 end)
+
+    header =
+      Style.faint(
+        String.pad_trailing("", width) <>
+          "  sessions  messages  input tokens  output tokens  cost (USD)"
+      ) <> "\n"
+
+    lines =
+      Enum.map(rows, fn {label, stats} ->
+        [
+          String.pad_trailing(label, width),
+          String.pad_leading(to_string(stats.sessions), 10),
+          String.pad_leading(to_string(stats.messages), 10),
+          String.pad_leading(to_string(stats.input_tokens), 14),
+          String.pad_leading(to_string(stats.output_tokens), 15),
+          String.pad_leading(format_cost(stats.estimated_cost_usd), 12),
+          "\n"
+        ]
+      end)
+
+    [header | lines]
+  end
+
+  defp project_csv(projects) do
+    columns = [:project, :sessions, :messages, :input_tokens, :output_tokens, :estimated_cost_usd]
+
+    Stats.to_csv(projects, columns)
+  end
+
+  defp format_cost(cost), do: :erlang.float_to_binary(cost / 1, decimals: 2)
+end
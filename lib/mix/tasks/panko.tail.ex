@@ -0,0 +1,211 @@
+defmodule Mix.Tasks.Panko.Tail do
+  @moduledoc """
+  Follows a Claude Code session file and prints new blocks as the agent
+  writes them, like `tail -f`. Only the lines appended since the last
+  read are decoded and parsed.
+
+  Prompts and responses are colored by role, and tool output is collapsed
+  to its first line. Thinking blocks are hidden unless `--thinking` is given.
+
+  ## Usage
+
+      mix panko.tail latest
+      mix panko.tail ~/.claude/projects/-home-me-app/abc123.jsonl
+      mix panko.tail latest --project /home/me/code/my-app --lines 5
+
+  ## Options
+
+    * `--project` - with `latest`, only consider sessions of this project
+    * `--lines` / `-n` - number of existing blocks to print first (default: 10)
+    * `--thinking` - also print thinking blocks
+    * `--interval` - polling interval in milliseconds (default: 500)
+    * `--color` - `always`, `never` or `auto` (default), which colors output
+      on a terminal unless `NO_COLOR` is set
+  """
+  use Mix.Task
+
+  alias Mix.Panko.ExitStatus
+  alias Mix.Panko.Style
+  alias Panko.Sessions.Parsers.ClaudeCode
+  alias Panko.Sessions.SessionFiles
+
+  @shortdoc "Follows a live session in the terminal"
+
+  @switches [
+    project: :string,
+    lines: :integer,
+    thinking: :boolean,
+    interval: :integer,
+    color: :string
+  ]
+
+  @impl Mix.Task
+  def run(args) do
+    {opts, target} =
+      case OptionParser.parse(args, strict: @switches, aliases: [n: :lines]) do
+        {opts, [target], []} -> {opts, target}
+        _ ->
+          ExitStatus.raise!(
+            :usage,
+            "Usage: mix panko.tail latest|FILE [--project PATH] [-n LINES]"
+          )
+      end
+
+    Style.setup(opts)
+    Mix.Task.run("app.config")
+
+    path = resolve!(target, opts)
+    Mix.shell().info(IO.ANSI.format([:faint, "==> #{path} <=="]))
+
+    state = %{offset: 0, partial: "", seen: 0, printed: 0, awaiting_output: MapSet.new()}
+    {state, blocks, results} = read_appended(path, state)
+    skip = max(length(blocks) - Keyword.get(opts, :lines, 10), 0)
+
+    follow(path, print_new(%{state | printed: skip}, blocks, results, opts), opts)
+  end
+
+  defp resolve!("latest", opts) do
+    files =
+      if opts[:project],
+        do: SessionFiles.project_files(opts[:project]),
+        else: SessionFiles.all_files()
+
+    SessionFiles.latest(files) || ExitStatus.raise!(:not_found, "No session files found")
+  end
+
+  defp resolve!(path, _opts) do
+    cond do
+      not File.regular?(path) -> ExitStatus.raise!(:not_found, "No such file: #{path}")
+      not ClaudeCode.can_parse?(path) -> ExitStatus.raise!(:usage, "Not a JSONL session: #{path}")
+      true -> Path.expand(path)
+    end
+  end
+
+  # Only the bytes past the last read are read and parsed. A file that
+  # shrank was rewritten, so it is read again from the start, without
+  # printing the blocks shown before again.
+  defp follow(path, state, opts) do
+    Process.sleep(Keyword.get(opts, :interval, 500))
+
+    case File.stat(path) do
+      {:ok, %{size: size}} when size == state.offset ->
+        follow(path, state, opts)
+
+      {:ok, %{size: size}} ->
+        state = if size < state.offset, do: rewind(state), else: state
+        {state, blocks, results} = read_appended(path, state)
+        follow(path, print_new(state, blocks, results, opts), opts)
+
+      {:error, reason} ->
+        stop!(path, reason)
+    end
+  end
+
+  defp rewind(state), do: %{state | offset: 0, partial: "", seen: 0}
+
+  # A line still being written is kept back until its newline arrives.
+  # Returns the blocks of the new lines, and their tool results, which may
+  # belong to calls read before.
+  defp read_appended(path, state) do
+    with {:ok, data} <- File.open(path, [:read, :binary], &read_from(&1, state.offset)),
+         {complete, [partial]} = (state.partial <> data) |> String.split("\n") |> Enum.split(-1),
+         objects = Enum.flat_map(complete, &ClaudeCode.decode_line/1),
+         {:ok, %{blocks: blocks}} <- ClaudeCode.parse_objects(objects) do
+      state = %{state | offset: state.offset + byte_size(data), partial: partial}
+      {state, blocks, ClaudeCode.tool_results(objects)}
+    else
+      {:error, reason} -> stop!(path, reason)
+    end
+  end
+
+  defp read_from(file, offset) do
+    {:ok, _position} = :file.position(file, offset)
+
+    case IO.binread(file, :eof) do
+      data when is_binary(data) -> data
+      :eof -> ""
+    end
+  end
+
+  defp stop!(path, reason) do
+    Mix.raise("Stopped following #{path}: #{:file.format_error(reason)}")
+  end
+
+  # `seen` counts the blocks read from the file, `printed` those shown or
+  # skipped. Tool output is printed once its result line is written, so
+  # calls printed without output wait for it in `awaiting_output`.
+  defp print_new(state, blocks, results, opts) do
+    completed = Enum.filter(state.awaiting_output, &output(results[&1]))
+    Enum.each(completed, &print_output(results[&1].text))
+
+    new = Enum.drop(blocks, max(state.printed - state.seen, 0))
+    awaiting = MapSet.difference(state.awaiting_output, MapSet.new(completed))
+    awaiting = Enum.reduce(new, awaiting, &print_new_block(&1, &2, opts))
+
+    seen = state.seen + length(blocks)
+    %{state | seen: seen, printed: max(state.printed, seen), awaiting_output: awaiting}
+  end
+
+  defp print_new_block(block, awaiting, opts) do
+    print_block(block, opts)
+    id = block.metadata && block.metadata["id"]
+
+    cond do
+      block.block_type not in [:tool_call, :file_edit, :sub_agent_spawn] or is_nil(id) ->
+        awaiting
+
+      output(block.metadata) ->
+        print_output(block.metadata["output"])
+        awaiting
+
+      true ->
+        MapSet.put(awaiting, id)
+    end
+  end
+
+  defp print_block(%{block_type: :user_prompt} = block, _opts),
+    do: print([:cyan, :bright, "▶ user"], block.content)
+
+  defp print_block(%{block_type: :assistant_response} = block, _opts),
+    do: print([:green, :bright, "◀ assistant"], block.content)
+
+  defp print_block(%{block_type: :thinking} = block, opts) do
+    if opts[:thinking], do: print([:faint, "… thinking"], block.content)
+  end
+
+  defp print_block(%{block_type: :file_edit, metadata: metadata}, _opts),
+    do: print([:yellow, "✎ #{metadata["name"]} #{metadata["path"]}"], nil)
+
+  defp print_block(%{block_type: :sub_agent_spawn, metadata: metadata}, _opts),
+    do: print([:magenta, "⇢ #{metadata["agent_type"]} agent"], metadata["description"])
+
+  defp print_block(%{block_type: :tool_call, metadata: metadata}, _opts),
+    do: print([:yellow, "⚙ #{metadata["name"]}"], tool_summary(metadata["input"]))
+
+  defp print_block(%{block_type: type} = block, _opts),
+    do: print([:faint, "· #{type}"], block.content)
+
+  defp print_output(output) do
+    [first | rest] = output |> String.trim() |> String.split("\n")
+    more = if rest == [], do: "", else: " (+#{length(rest)} lines)"
+
+    Mix.shell().info(IO.ANSI.format([:faint, "  ↳ ", String.slice(first, 0, 120), more]))
+  end
+
+  defp print(label, nil), do: Mix.shell().info(IO.ANSI.format(label))
+
+  defp print(label, content) do
+    Mix.shell().info(IO.ANSI.format(label ++ [:reset, "  ", String.trim(content)]))
+  end
+
+  defp output(%{"output" => output}) when is_binary(output), do: output
+  defp output(%{text: text}) when is_binary(text), do: text
+  defp output(_result), do: nil
+
+  defp tool_summary(%{"command" => command}), do: command
+  defp tool_summary(%{"description" => description}), do: description
+  defp tool_summary(%{"pattern" => pattern}), do: pattern
+  defp tool_summary(%{"file_path" => path}), do: path
+  defp tool_summary(%{"url" => url}), do: url
+  defp tool_summary(_input), do: nil
+end
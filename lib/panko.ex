@@ -1,9 +1,61 @@
 defmodule Panko do
   @moduledoc """
-  Panko keeps the contexts that define your domain
-  and business logic.
+  High-level entry points for using Panko from other code.
 
-  Contexts are also responsible for managing your data, regardless
-  if it comes from the database, an external API or others.
+  The `Panko.Sessions` and `Panko.Sharing` domains hold the full API; the
+  functions here cover the common flow of loading a session file, storing
+  it, sharing it and exporting it, without going through the Mix tasks.
+  None of them print anything.
+
+      {:ok, session} = Panko.import_file("~/.claude/projects/-home-me-app/abc.jsonl")
+      {:ok, share} = Panko.share(session, expires_in: "1d")
+      Panko.share_url(share)
+      #=> "http://localhost:4000/s/k3j9x2ab"
+
+  `load_file/1` and `export/3` work on files alone and need neither the
+  database nor the endpoint.
+  """
+
+  alias Panko.Sessions.Exporters
+
+  @doc """
+  Parses a session file without storing it.
+  """
+  @spec load_file(String.t()) :: {:ok, Panko.Sessions.Session.t()} | {:error, term()}
+  def load_file(path), do: path |> Path.expand() |> Exporters.load_file()
+
+  @doc """
+  Parses a session file and stores it, updating an earlier import of the
+  same session.
+  """
+  @spec import_file(String.t()) :: {:ok, Panko.Sessions.Session.t()} | {:error, term()}
+  def import_file(path), do: path |> Path.expand() |> Panko.Sessions.import_from_file()
+
+  @doc """
+  Shares a stored session.
+
+  Accepts `:slug` for a named share, `:expires_in` (`"12h"`, `"7d"`,
+  `"never"`) and `:snapshot` to freeze the shared content; all default as
+  in `Panko.Sharing.Share`.
+  """
+  @spec share(Panko.Sessions.Session.t(), keyword()) ::
+          {:ok, Panko.Sharing.Share.t()} | {:error, term()}
+  def share(session, opts \\ []) do
+    params = opts |> Keyword.take([:slug, :expires_in, :snapshot]) |> Map.new()
+    Panko.Sharing.create_share(session.id, params)
+  end
+
+  @doc """
+  Returns the public URL of a share.
+  """
+  @spec share_url(Panko.Sharing.Share.t()) :: String.t()
+  def share_url(share), do: PankoWeb.Endpoint.url() <> "/s/#{share.slug}"
+
+  @doc """
+  Exports a session, stored or loaded, in one of the formats listed by
+  `Panko.Sessions.Exporters.Registry`.
   """
+  @spec export(Panko.Sessions.Session.t(), atom() | String.t(), keyword()) ::
+          {:ok, iodata()} | {:error, term()}
+  defdelegate export(session, format, opts \\ []), to: Exporters
 end
@@ -7,6 +7,10 @@ defmodule Panko.Application do
 
   @impl true
   def start(_type, _args) do
+    Panko.Metrics.setup()
+    PankoWeb.RequestMetrics.setup()
+    Panko.Sessions.ParseCache.setup()
+
     children =
       [
         PankoWeb.Telemetry,
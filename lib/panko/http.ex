@@ -0,0 +1,35 @@
+defmodule Panko.HTTP do
+  @moduledoc """
+  Options for outgoing `:httpc` requests.
+
+  Since OTP 26, `:httpc` verifies https peers by default but has no CA
+  certificates to verify them against, so every https request fails
+  unless they are passed in. `options/1` adds them from the operating
+  system's trust store, with hostname checks that accept wildcard
+  certificates.
+  """
+
+  @doc """
+  Returns `:httpc` HTTP options, `opts` plus the `:ssl` options.
+  """
+  @spec options(keyword()) :: keyword()
+  def options(opts \\ []) do
+    Keyword.put(opts, :ssl, ssl_options())
+  end
+
+  @doc """
+  Returns the `:ssl` options verifying peers against the system CA
+  certificates.
+  """
+  @spec ssl_options() :: keyword()
+  def ssl_options do
+    [
+      verify: :verify_peer,
+      cacerts: :public_key.cacerts_get(),
+      depth: 3,
+      customize_hostname_check: [
+        match_fun: :public_key.pkix_verify_hostname_match_fun(:https)
+      ]
+    ]
+  end
+end
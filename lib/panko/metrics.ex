@@ -0,0 +1,92 @@
+defmodule Panko.Metrics do
+  @moduledoc """
+  Operational metrics in the Prometheus text format, served at `/metrics`.
+
+  Gauges such as the number of sessions and active shares are read from the
+  database on each scrape. Event counters (imports and import failures of
+  the session watcher) live in memory and reset when the app restarts.
+  """
+
+  alias Panko.Sessions.Session
+  alias Panko.Sharing.Share
+
+  require Ash.Query
+
+  @counters [:sessions_imported, :import_errors]
+
+  @doc """
+  Creates the in-memory counters. Called once on application start.
+  """
+  def setup do
+    :persistent_term.put(__MODULE__, :counters.new(length(@counters), [:write_concurrency]))
+  end
+
+  @doc """
+  Increments one of the event counters.
+  """
+  def increment(name) when name in @counters do
+    case :persistent_term.get(__MODULE__, nil) do
+      nil -> :ok
+      ref -> :counters.add(ref, index(name), 1)
+    end
+  end
+
+  @doc """
+  Returns the current metrics as `{name, type, help, value}` tuples.
+  """
+  def snapshot do
+    active_shares = Share |> Ash.Query.filter(is_shared == true) |> Ash.count!()
+
+    [
+      {"panko_sessions", :gauge, "Imported sessions", Ash.count!(Session)},
+      {"panko_shares_active", :gauge, "Published shares", active_shares},
+      {"panko_share_views_total", :counter, "Views of all shares",
+       Ash.sum!(Share, :view_count) || 0},
+      {"panko_share_bytes_served_total", :counter, "Bytes of share pages served",
+       Ash.sum!(Share, :bytes_served) || 0},
+      {"panko_sessions_imported_total", :counter, "Sessions imported by the watcher",
+       counter(:sessions_imported)},
+      {"panko_import_errors_total", :counter, "Session files that failed to import",
+       counter(:import_errors)},
+      {"panko_vm_memory_bytes", :gauge, "Memory used by the BEAM", :erlang.memory(:total)}
+    ]
+  end
+
+  @doc """
+  Renders metrics in the Prometheus text exposition format.
+
+  A value may also be a list of `{labels, value}` samples, one line each.
+  """
+  def to_prometheus(metrics \\ snapshot()) do
+    Enum.map(metrics, fn {name, type, help, value} ->
+      ["# HELP ", name, " ", help, "\n# TYPE ", name, " ", to_string(type), "\n"] ++
+        samples(name, value)
+    end)
+  end
+
+  defp samples(name, samples) when is_list(samples) do
+    Enum.map(samples, fn {labels, value} ->
+      labels = Enum.map_join(labels, ",", fn {key, label} -> ~s(#{key}="#{escape(label)}") end)
+      [name, "{", labels, "} ", to_string(value), "\n"]
+    end)
+  end
+
+  defp samples(name, value), do: [name, " ", to_string(value), "\n"]
+
+  defp escape(label) do
+    label
+    |> to_string()
+    |> String.replace("\\", "\\\\")
+    |> String.replace("\"", "\\\"")
+    |> String.replace("\n", "\\n")
+  end
+
+  defp counter(name) do
+    case :persistent_term.get(__MODULE__, nil) do
+      nil -> 0
+      ref -> :counters.get(ref, index(name))
+    end
+  end
+
+  defp index(name), do: Enum.find_index(@counters, &(&1 == name)) + 1
+end
@@ -0,0 +1,96 @@
+defmodule Panko.Notifications do
+  @moduledoc """
+  Desktop notifications for events worth knowing about while Panko runs
+  in a background terminal.
+
+  Events are off unless listed in `PANKO_NOTIFY`, comma-separated, or
+  `all` for every one:
+
+    * `share_published` - a share was created, with its URL
+    * `share_expired` - the reaper unpublished an expired share
+    * `export_completed` - `mix panko.export` wrote its files
+    * `import_failed` - the session watcher could not import a file
+
+  Notifications are delivered by the `:notifier` module, any module
+  implementing this behaviour, `Panko.Notifications.Desktop` by default.
+  """
+
+  require Logger
+
+  @events [:share_published, :share_expired, :export_completed, :import_failed]
+
+  @type event ::
+          :share_published | :share_expired | :export_completed | :import_failed
+
+  @doc """
+  Shows a notification with `title` and `body`.
+  """
+  @callback deliver(title :: String.t(), body :: String.t()) :: :ok | {:error, term()}
+
+  @doc """
+  Returns the known events.
+  """
+  @spec events() :: [event()]
+  def events, do: @events
+
+  @doc """
+  Parses a comma-separated event list, ignoring unknown names.
+
+  ## Examples
+
+      iex> parse_events("share_published, import_failed, nope")
+      [:share_published, :import_failed]
+
+      iex> parse_events(nil)
+      []
+  """
+  @spec parse_events(String.t() | nil) :: [event()]
+  def parse_events(value) when value in [nil, ""], do: []
+  def parse_events("all"), do: @events
+
+  def parse_events(value) do
+    value
+    |> String.split(",", trim: true)
+    |> Enum.flat_map(fn name ->
+      name = String.trim(name)
+      Enum.filter(@events, &(Atom.to_string(&1) == name))
+    end)
+  end
+
+  @doc """
+  Notifies of `event` when it is enabled.
+
+  ## Options
+
+    * `:events` - the enabled events, instead of `PANKO_NOTIFY`
+    * `:notifier` - a module implementing this behaviour, or a function of
+      title and body, instead of the configured one
+  """
+  @spec notify(event(), String.t(), String.t(), keyword()) :: :ok | :disabled | {:error, term()}
+  def notify(event, title, body, opts \\ []) when event in @events do
+    enabled = Keyword.get_lazy(opts, :events, &enabled_events/0)
+
+    if event in enabled do
+      notifier =
+        Keyword.get_lazy(opts, :notifier, fn ->
+          Application.get_env(:panko, :notifier, Panko.Notifications.Desktop)
+        end)
+
+      case deliver(notifier, title, body) do
+        :ok ->
+          :ok
+
+        {:error, reason} = error ->
+          Logger.warning("Could not show the #{event} notification: #{inspect(reason)}")
+          error
+      end
+    else
+      :disabled
+    end
+  end
+
+  defp enabled_events, do: parse_events(Application.get_env(:panko, :notify_events))
+
+  defp deliver(notifier, title, body) when is_function(notifier, 2), do: notifier.(title, body)
+  defp deliver(notifier, title, body), do: notifier.deliver(title, body)
+end
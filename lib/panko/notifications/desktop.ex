@@ -0,0 +1,79 @@
+defmodule Panko.Notifications.Desktop do
+  @moduledoc """
+  Shows notifications with the operating system's own tool: `notify-send`
+  on Linux and other Unix desktops, `osascript` on macOS.
+
+  The command is waited for, up to a short timeout, so a Mix task that
+  halts right after notifying does not take the notification down with
+  it, while a hung notification daemon cannot hold up the action that
+  triggered it for long.
+  """
+
+  @behaviour Panko.Notifications
+
+  @timeout 2_000
+
+  @impl true
+  def deliver(title, body), do: deliver(title, body, [])
+
+  @doc """
+  Shows a notification like `deliver/2`.
+
+  ## Options
+
+    * `:os_type` - the platform, as returned by `:os.type/0`
+    * `:runner` - a function of command name and arguments that runs the
+      command, returning `:ok` or `{:error, reason}`
+    * `:timeout` - milliseconds to wait for the command (default: #{@timeout})
+  """
+  @spec deliver(String.t(), String.t(), keyword()) :: :ok | {:error, term()}
+  def deliver(title, body, opts) do
+    runner = Keyword.get(opts, :runner, &run/2)
+    os_type = Keyword.get_lazy(opts, :os_type, &:os.type/0)
+    timeout = Keyword.get(opts, :timeout, @timeout)
+
+    case command(os_type, title, body) do
+      {command, args} ->
+        task = Task.async(fn -> runner.(command, args) end)
+
+        case Task.yield(task, timeout) || Task.shutdown(task, :brutal_kill) do
+          {:ok, result} -> result
+          nil -> {:error, :timeout}
+        end
+
+      nil ->
+        {:error, :no_notifier}
+    end
+  end
+
+  @doc """
+  Returns the command and arguments showing a notification on `os_type`,
+  as returned by `:os.type/0`, or `nil` when there is none.
+  """
+  @spec command({atom(), atom()}, String.t(), String.t()) :: {String.t(), [String.t()]} | nil
+  def command({:unix, :darwin}, title, body) do
+    script = "display notification #{applescript(body)} with title #{applescript(title)}"
+    {"osascript", ["-e", script]}
+  end
+
+  def command({:unix, _name}, title, body), do: {"notify-send", ["--app-name=Panko", title, body]}
+  def command(_os_type, _title, _body), do: nil
+
+  defp run(command, args) do
+    case System.find_executable(command) do
+      nil ->
+        {:error, :no_notifier}
+
+      path ->
+        case System.cmd(path, args, stderr_to_stdout: true) do
+          {_output, 0} -> :ok
+          {output, status} -> {:error, {:exit_status, status, String.trim(output)}}
+        end
+    end
+  end
+
+  defp applescript(text) do
+    escaped = text |> String.replace("\\", "\\\\") |> String.replace("\"", "\\\"")
+    "\"" <> escaped <> "\""
+  end
+end
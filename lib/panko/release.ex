@@ -11,6 +11,9 @@ defmodule Panko.Release do
 
       # Rollback to a specific version
       bin/panko eval "Panko.Release.rollback(Panko.Repo, 20240101000000)"
+
+      # Show schema version and pending migrations
+      bin/panko eval "Panko.Release.print_db_info()"
   """
 
   @app :panko
@@ -28,6 +31,49 @@ defmodule Panko.Release do
     {:ok, _, _} = Ecto.Migrator.with_repo(repo, &Ecto.Migrator.run(&1, :down, to: version))
   end
 
+  @doc """
+  Returns the database, schema version and migration status of each repo.
+
+  `:version` is the latest applied migration (`nil` on an empty database)
+  and `:pending` lists migrations that have not been applied yet.
+  """
+  def db_info do
+    load_app()
+
+    for repo <- repos() do
+      {:ok, migrations, _} = Ecto.Migrator.with_repo(repo, &Ecto.Migrator.migrations/1)
+      config = repo.config()
+
+      applied = for {:up, version, _name} <- migrations, do: version
+      pending = for {:down, version, name} <- migrations, do: "#{version}_#{name}"
+
+      %{
+        repo: repo,
+        database: config[:database],
+        hostname: config[:hostname] || config[:socket_dir],
+        migrations_path: Ecto.Migrator.migrations_path(repo),
+        version: Enum.max(applied, fn -> nil end),
+        applied: length(applied),
+        pending: pending
+      }
+    end
+  end
+
+  @doc """
+  Prints `db_info/0` in a human readable form.
+  """
+  def print_db_info do
+    for info <- db_info() do
+      IO.puts(inspect(info.repo))
+      IO.puts("  database:   #{info.database} on #{info.hostname || "localhost"}")
+      IO.puts("  migrations: #{info.migrations_path}")
+      IO.puts("  version:    #{info.version || "none"} (#{info.applied} applied)")
+      Enum.each(info.pending, &IO.puts("  pending:    #{&1}"))
+    end
+
+    :ok
+  end
+
   @doc """
   Creates an admin user with the given email and password.
 
@@ -4,12 +4,35 @@ defmodule Panko.Sessions do
   resources do
     resource Panko.Sessions.Session do
       define :import_from_file, action: :import_from_file, args: [:file_path]
+      define :upsert_session, action: :upsert_from_import
       define :get_session, action: :read, get_by: [:id]
+      define :get_session_by_external_id, action: :read, get_by: [:external_id]
       define :list_sessions, action: :list_recent
       define :list_all_sessions, action: :list_projects
+      define :list_project_sessions, action: :by_project, args: [:project]
+      define :list_filtered_sessions, action: :filtered
+      define :search_sessions, action: :search, args: [:pattern]
+      define :prune_session, action: :prune
+      define :cache_session_usage, action: :cache_usage
+      define :list_sessions_missing_usage, action: :missing_usage
+    end
+
+    resource Panko.Sessions.Block do
+      define :search_blocks, action: :search, args: [:pattern, :session_ids]
     end
 
-    resource Panko.Sessions.Block
     resource Panko.Sessions.SubAgent
+
+    resource Panko.Sessions.Comment do
+      define :add_comment, action: :create, args: [:session_id, :position, :body]
+      define :list_session_comments, action: :for_session, args: [:session_id]
+      define :delete_comment, action: :destroy
+    end
+
+    resource Panko.Sessions.Bookmark do
+      define :add_bookmark, action: :create, args: [:session_id, :position]
+      define :list_session_bookmarks, action: :for_session, args: [:session_id]
+      define :delete_bookmark, action: :destroy
+    end
   end
 end
@@ -58,6 +58,13 @@ defmodule Panko.Sessions.Block do
       accept [:position, :block_type, :content, :metadata, :timestamp, :session_id]
     end
 
+    read :search do
+      argument :pattern, :string, allow_nil?: false
+      argument :session_ids, {:array, :uuid}, allow_nil?: false
+      filter expr(session_id in ^arg(:session_ids) and ilike(content, ^arg(:pattern)))
+      prepare build(sort: [session_id: :asc, position: :asc])
+    end
+
     update :update do
       primary? true
       accept [:position, :block_type, :content, :metadata, :timestamp]
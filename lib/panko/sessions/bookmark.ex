@@ -0,0 +1,73 @@
+defmodule Panko.Sessions.Bookmark do
+  @moduledoc """
+  A bookmark on one block of a session, for jumping back to important
+  exchanges in long sessions.
+
+  Like comments, bookmarks point at the block's position so they survive
+  re-imports, and a block has at most one. Creating or deleting one is
+  broadcast on `bookmarks:session:<id>`.
+  """
+  use Ash.Resource,
+    domain: Panko.Sessions,
+    data_layer: AshPostgres.DataLayer,
+    notifiers: [Ash.Notifier.PubSub]
+
+  postgres do
+    table "bookmarks"
+    repo Panko.Repo
+
+    references do
+      reference :session, on_delete: :delete
+    end
+  end
+
+  pub_sub do
+    module PankoWeb.Endpoint
+    prefix "bookmarks"
+    publish_all :create, ["session", :session_id]
+    publish_all :destroy, ["session", :session_id]
+  end
+
+  attributes do
+    uuid_primary_key :id
+
+    attribute :position, :integer do
+      allow_nil? false
+      public? true
+    end
+
+    attribute :label, :string do
+      allow_nil? true
+      public? true
+      constraints max_length: 120
+    end
+
+    create_timestamp :inserted_at
+  end
+
+  relationships do
+    belongs_to :session, Panko.Sessions.Session do
+      allow_nil? false
+      public? true
+    end
+  end
+
+  identities do
+    identity :session_position, [:session_id, :position]
+  end
+
+  actions do
+    defaults [:read, :destroy]
+
+    create :create do
+      primary? true
+      accept [:session_id, :position, :label]
+    end
+
+    read :for_session do
+      argument :session_id, :uuid, allow_nil?: false
+      filter expr(session_id == ^arg(:session_id))
+      prepare build(sort: [position: :asc])
+    end
+  end
+end
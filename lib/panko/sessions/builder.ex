@@ -0,0 +1,356 @@
+defmodule Panko.Sessions.Builder do
+  @moduledoc """
+  Builds sessions in code and writes them as Claude Code JSONL, the
+  format `Panko.Sessions.Parsers.ClaudeCode` reads.
+
+  Meant for tools that synthesize sessions, such as converting CI agent
+  logs, so the result can be imported, viewed and shared like any other
+  session:
+
+      Builder.new("ci-run-42", cwd: "/app")
+      |> Builder.user("Fix the failing build", at: ~U[2026-10-17 09:00:00Z])
+      |> Builder.assistant("Running the tests first.")
+      |> Builder.tool_call("Bash", %{"command" => "mix test"}, output: "1 failure")
+      |> Builder.summary("Fix the failing build")
+      |> Builder.write("ci-run-42.jsonl")
+
+  Entries without `:at` take the timestamp of the one before, or the
+  session's `:started_at`. Before writing, the session is validated:
+
+    * the session id must be a non-empty string
+    * timestamps must not go backwards
+    * tool call ids, generated when not given, must be unique
+  """
+
+  alias __MODULE__, as: Builder
+
+  defstruct [:session_id, :cwd, :git_branch, :started_at, :summary, entries: []]
+
+  @type t :: %Builder{
+          session_id: String.t(),
+          cwd: String.t() | nil,
+          git_branch: String.t() | nil,
+          started_at: DateTime.t() | nil,
+          summary: String.t() | nil,
+          entries: [map()]
+        }
+
+  # `entries` holds the entries newest first.
+
+  @type error ::
+          :missing_session_id
+          | {:timestamp_out_of_order, non_neg_integer()}
+          | {:duplicate_tool_id, String.t()}
+
+  @doc """
+  Starts a session.
+
+  ## Options
+
+    * `:cwd` - the project directory
+    * `:git_branch` - the branch the session ran on
+    * `:started_at` - timestamp of entries before the first with `:at`
+  """
+  @spec new(String.t(), keyword()) :: t()
+  def new(session_id, opts \\ []) do
+    %Builder{
+      session_id: session_id,
+      cwd: opts[:cwd],
+      git_branch: opts[:git_branch],
+      started_at: opts[:started_at]
+    }
+  end
+
+  @doc """
+  Adds a user prompt. Takes the `:at` option.
+  """
+  @spec user(t(), String.t(), keyword()) :: t()
+  def user(builder, text, opts \\ []), do: add(builder, :user, %{text: text}, opts)
+
+  @doc """
+  Adds an assistant response. Takes the `:at`, `:model` and `:usage`
+  options, the latter a map of token counts as the API reports them.
+  """
+  @spec assistant(t(), String.t(), keyword()) :: t()
+  def assistant(builder, text, opts \\ []), do: add(builder, :assistant, %{text: text}, opts)
+
+  @doc """
+  Adds the assistant's thinking. Takes the same options as `assistant/3`.
+  """
+  @spec thinking(t(), String.t(), keyword()) :: t()
+  def thinking(builder, text, opts \\ []), do: add(builder, :thinking, %{text: text}, opts)
+
+  @doc """
+  Adds a tool call by the assistant, and its result when `:output` is
+  given. Besides the options of `assistant/3`, takes `:id`, `:output`
+  and `:is_error`.
+  """
+  @spec tool_call(t(), String.t(), map(), keyword()) :: t()
+  def tool_call(builder, name, input, opts \\ []) do
+    fields = %{
+      name: name,
+      input: input,
+      id: opts[:id],
+      output: opts[:output],
+      is_error: Keyword.get(opts, :is_error, false)
+    }
+
+    add(builder, :tool_call, fields, opts)
+  end
+
+  @doc """
+  Sets the summary Claude Code uses as the session's title.
+  """
+  @spec summary(t(), String.t()) :: t()
+  def summary(builder, text), do: %{builder | summary: text}
+
+  @doc """
+  Starts a builder from a session with its blocks loaded, from the
+  database or `Panko.Sessions.Exporters.load_file/1`, to convert it to
+  Claude Code JSONL.
+
+  Blocks stay in their order. One timestamped before the block ahead of
+  it, as happens in resumed sessions, takes that block's timestamp.
+  """
+  @spec from_session(map()) :: t()
+  def from_session(session) do
+    builder =
+      new(session.external_id || "unknown",
+        cwd: session.project,
+        started_at: session.started_at
+      )
+
+    {builder, _last} =
+      Enum.reduce(session.blocks, {builder, session.started_at}, fn block, {builder, last} ->
+        block = clamp_timestamp(block, last)
+        {add_block(builder, block), block.timestamp || last}
+      end)
+
+    builder
+  end
+
+  @doc """
+  Checks the session, see the module documentation.
+  """
+  @spec validate(t()) :: :ok | {:error, error()}
+  def validate(%Builder{session_id: id}) when not is_binary(id) or id == "",
+    do: {:error, :missing_session_id}
+
+  def validate(builder) do
+    entries = timed_entries(builder)
+
+    with :ok <- validate_order(entries) do
+      validate_tool_ids(entries)
+    end
+  end
+
+  @doc """
+  Returns the session as the decoded JSONL lines, one map per line.
+  """
+  @spec to_lines(t()) :: {:ok, [map()]} | {:error, error()}
+  def to_lines(builder) do
+    with :ok <- validate(builder) do
+      {lines, last_uuid} =
+        builder
+        |> timed_entries()
+        |> Enum.with_index()
+        |> Enum.flat_map_reduce(nil, fn {entry, index}, parent ->
+          entry_lines(builder, entry, index, parent)
+        end)
+
+      summary =
+        if builder.summary && last_uuid,
+          do: [%{"type" => "summary", "summary" => builder.summary, "leafUuid" => last_uuid}],
+          else: []
+
+      {:ok, lines ++ summary}
+    end
+  end
+
+  @doc """
+  Returns the session as JSONL.
+  """
+  @spec to_jsonl(t()) :: {:ok, iodata()} | {:error, error()}
+  def to_jsonl(builder) do
+    with {:ok, lines} <- to_lines(builder) do
+      {:ok, Enum.map(lines, &[Jason.encode_to_iodata!(&1), "\n"])}
+    end
+  end
+
+  @doc """
+  Writes the session as JSONL to `path`.
+  """
+  @spec write(t(), Path.t()) :: :ok | {:error, term()}
+  def write(builder, path) do
+    with {:ok, jsonl} <- to_jsonl(builder) do
+      File.write(path, jsonl)
+    end
+  end
+
+  defp add(builder, kind, fields, opts) do
+    entry =
+      Map.merge(fields, %{kind: kind, at: opts[:at], model: opts[:model], usage: opts[:usage]})
+
+    %{builder | entries: [entry | builder.entries]}
+  end
+
+  defp clamp_timestamp(%{timestamp: %DateTime{} = at} = block, %DateTime{} = last) do
+    if DateTime.compare(at, last) == :lt, do: %{block | timestamp: last}, else: block
+  end
+
+  defp clamp_timestamp(block, _last), do: block
+
+  defp add_block(builder, %{block_type: :user_prompt} = block),
+    do: user(builder, block.content || "", at: block.timestamp)
+
+  defp add_block(builder, %{block_type: :assistant_response} = block),
+    do: assistant(builder, block.content || "", block_opts(block))
+
+  defp add_block(builder, %{block_type: :thinking} = block),
+    do: thinking(builder, block.content || "", block_opts(block))
+
+  defp add_block(builder, %{block_type: type} = block)
+       when type in [:tool_call, :file_edit, :sub_agent_spawn] do
+    metadata = block.metadata || %{}
+
+    opts =
+      block_opts(block) ++
+        [id: metadata["id"], output: metadata["output"], is_error: metadata["is_error"] == true]
+
+    tool_call(builder, metadata["name"] || "Tool", metadata["input"] || %{}, opts)
+  end
+
+  defp add_block(builder, _block), do: builder
+
+  defp block_opts(block) do
+    metadata = block.metadata || %{}
+    [at: block.timestamp, model: metadata["model"], usage: metadata["usage"]]
+  end
+
+  # Fills in missing timestamps and tool ids.
+  defp timed_entries(builder) do
+    {entries, _last} =
+      builder.entries
+      |> Enum.reverse()
+      |> Enum.with_index(1)
+      |> Enum.map_reduce(builder.started_at, fn {entry, n}, last ->
+        at = entry.at || last
+        entry = %{entry | at: at}
+
+        entry =
+          if entry.kind == :tool_call, do: %{entry | id: entry.id || "toolu_#{n}"}, else: entry
+
+        {entry, at}
+      end)
+
+    entries
+  end
+
+  defp validate_order(entries) do
+    entries
+    |> Enum.with_index()
+    |> Enum.reduce_while({:ok, nil}, fn
+      {%{at: nil}, _index}, acc ->
+        {:cont, acc}
+
+      {%{at: at}, index}, {:ok, last} ->
+        if last && DateTime.compare(at, last) == :lt,
+          do: {:halt, {:error, {:timestamp_out_of_order, index}}},
+          else: {:cont, {:ok, at}}
+    end)
+    |> case do
+      {:ok, _last} -> :ok
+      error -> error
+    end
+  end
+
+  defp validate_tool_ids(entries) do
+    entries
+    |> Enum.filter(&(&1.kind == :tool_call))
+    |> Enum.map(& &1.id)
+    |> Enum.reduce_while(MapSet.new(), fn id, seen ->
+      if MapSet.member?(seen, id),
+        do: {:halt, {:error, {:duplicate_tool_id, id}}},
+        else: {:cont, MapSet.put(seen, id)}
+    end)
+    |> case do
+      {:error, _} = error -> error
+      _seen -> :ok
+    end
+  end
+
+  defp entry_lines(builder, %{kind: :user} = entry, index, parent) do
+    uuid = uuid(builder, index)
+    message = %{"role" => "user", "content" => entry.text}
+    {[line(builder, entry, "user", uuid, parent, message)], uuid}
+  end
+
+  defp entry_lines(builder, %{kind: :tool_call} = entry, index, parent) do
+    uuid = uuid(builder, index)
+    part = %{"type" => "tool_use", "id" => entry.id, "name" => entry.name, "input" => entry.input}
+    call = line(builder, entry, "assistant", uuid, parent, assistant_message(entry, index, part))
+
+    if is_nil(entry.output) do
+      {[call], uuid}
+    else
+      result_uuid = uuid(builder, "#{index}-result")
+
+      result = %{
+        "type" => "tool_result",
+        "tool_use_id" => entry.id,
+        "content" => entry.output,
+        "is_error" => entry.is_error
+      }
+
+      message = %{"role" => "user", "content" => [result]}
+      {[call, line(builder, entry, "user", result_uuid, uuid, message)], result_uuid}
+    end
+  end
+
+  defp entry_lines(builder, %{kind: kind} = entry, index, parent) do
+    part =
+      if kind == :thinking,
+        do: %{"type" => "thinking", "thinking" => entry.text},
+        else: %{"type" => "text", "text" => entry.text}
+
+    uuid = uuid(builder, index)
+    message = assistant_message(entry, index, part)
+    {[line(builder, entry, "assistant", uuid, parent, message)], uuid}
+  end
+
+  defp assistant_message(entry, index, part) do
+    %{
+      "id" => "msg_#{index}",
+      "type" => "message",
+      "role" => "assistant",
+      "content" => [part]
+    }
+    |> put_present("model", entry.model)
+    |> put_present("usage", entry.usage)
+  end
+
+  defp line(builder, entry, type, uuid, parent, message) do
+    %{
+      "type" => type,
+      "sessionId" => builder.session_id,
+      "uuid" => uuid,
+      "parentUuid" => parent,
+      "message" => message
+    }
+    |> put_present("timestamp", entry.at && DateTime.to_iso8601(entry.at))
+    |> put_present("cwd", builder.cwd)
+    |> put_present("gitBranch", builder.git_branch)
+  end
+
+  defp put_present(map, _key, nil), do: map
+  defp put_present(map, key, value), do: Map.put(map, key, value)
+
+  # Stable per session and entry, so writing the same session twice gives
+  # the same file.
+  defp uuid(builder, index) do
+    <<a::binary-4, b::binary-2, c::binary-2, d::binary-2, e::binary-6, _::binary>> =
+      :crypto.hash(:sha256, "#{builder.session_id}:#{index}")
+
+    [a, b, c, d, e] |> Enum.map_join("-", &Base.encode16(&1, case: :lower))
+  end
+end
@@ -0,0 +1,33 @@
+defmodule Panko.Sessions.Changes.CacheUsage do
+  @moduledoc """
+  Stores a session's token totals and estimated cost on the session row,
+  so listings and stats can show them without loading every block.
+
+  On import the totals come from the `blocks` argument; otherwise the
+  stored blocks are loaded.
+  """
+  use Ash.Resource.Change
+
+  alias Panko.Sessions.Usage
+
+  @impl true
+  def change(changeset, _opts, _context) do
+    blocks =
+      case Ash.Changeset.get_argument(changeset, :blocks) do
+        nil -> changeset.data |> Ash.load!(:blocks) |> Map.fetch!(:blocks)
+        blocks -> Enum.map(blocks, &usage_fields/1)
+      end
+
+    usage = Map.put(Usage.totals(blocks), :estimated_cost_usd, Usage.cost(blocks))
+    Ash.Changeset.force_change_attributes(changeset, usage)
+  end
+
+  # Imported blocks are plain maps, with atom keys from the parsers and
+  # string keys from API clients.
+  defp usage_fields(block) do
+    %{
+      metadata: Map.get(block, :metadata, block["metadata"]),
+      position: Map.get(block, :position, block["position"])
+    }
+  end
+end
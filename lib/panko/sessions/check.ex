@@ -0,0 +1,121 @@
+defmodule Panko.Sessions.Check do
+  @moduledoc """
+  Validates session files without importing them.
+
+  Parsers skip what they cannot read, so a session can import "fine" while
+  missing half its transcript. A check parses the file and reports such
+  problems as diagnostics: errors make the file unusable, warnings point at
+  content that was dropped or is incomplete.
+  """
+
+  alias Panko.Sessions.Parsers
+
+  @type diagnostic :: %{level: :error | :warning, message: String.t(), line: pos_integer() | nil}
+
+  @type result :: %{
+          path: String.t(),
+          ok: boolean(),
+          source_type: atom() | nil,
+          blocks: non_neg_integer(),
+          diagnostics: [diagnostic()]
+        }
+
+  @doc """
+  Expands directories (recursively) and shell-style globs into the session
+  files they contain. Plain file paths are kept as given.
+  """
+  @spec expand_paths([String.t()]) :: [String.t()]
+  def expand_paths(paths) do
+    paths
+    |> Enum.flat_map(fn path ->
+      cond do
+        File.dir?(path) -> Path.wildcard(Path.join([path, "**", "*.{jsonl,json}"]))
+        String.contains?(path, ["*", "?", "[", "{"]) -> Path.wildcard(path)
+        true -> [path]
+      end
+    end)
+    |> Enum.map(&Path.expand/1)
+    |> Enum.uniq()
+  end
+
+  @doc """
+  Checks many session files in parallel, in the order given.
+  """
+  @spec check_files([String.t()]) :: [result()]
+  def check_files(paths) do
+    paths
+    |> Task.async_stream(&check_file/1, ordered: true, timeout: :infinity)
+    |> Enum.map(fn {:ok, result} -> result end)
+  end
+
+  @doc """
+  Checks one session file.
+  """
+  @spec check_file(String.t()) :: result()
+  def check_file(path) do
+    {attrs, diagnostics} =
+      case Parsers.Registry.find_parser(path) do
+        {:ok, parser} -> parse(parser, path)
+        {:error, :no_parser_found} -> {nil, [error("no parser for this file type")]}
+      end
+
+    %{
+      path: path,
+      ok: not Enum.any?(diagnostics, &(&1.level == :error)),
+      source_type: attrs && attrs.source_type,
+      blocks: if(attrs, do: length(attrs.blocks), else: 0),
+      diagnostics: diagnostics
+    }
+  end
+
+  defp parse(parser, path) do
+    case Parsers.Registry.parse(parser, path) do
+      {:ok, attrs} ->
+        {attrs, line_diagnostics(path) ++ session_diagnostics(attrs)}
+
+      {:error, {:file_read_error, reason}} ->
+        {nil, [error("cannot read file: #{:file.format_error(reason)}")]}
+
+      {:error, reason} ->
+        {nil, [error("cannot parse file: #{inspect(reason)}")]}
+    end
+  end
+
+  # Decoded like the parser does, so lines it recovers are not reported.
+  defp line_diagnostics(path) do
+    if String.ends_with?(path, ".jsonl") do
+      path
+      |> File.stream!()
+      |> Stream.with_index(1)
+      |> Stream.reject(fn {line, _number} -> String.trim(line) == "" end)
+      |> Enum.flat_map(fn {line, number} ->
+        case Parsers.ClaudeCode.decode_line(line) do
+          [_object] -> []
+          [] -> [warning("invalid JSON, line skipped", number)]
+        end
+      end)
+    else
+      []
+    end
+  end
+
+  defp session_diagnostics(attrs) do
+    unanswered =
+      Enum.count(attrs.blocks, fn block ->
+        block.block_type in [:tool_call, :file_edit, :sub_agent_spawn] and
+          not Map.has_key?(block.metadata || %{}, "output")
+      end)
+
+    Enum.reject(
+      [
+        attrs.blocks == [] && error("no blocks found"),
+        attrs.external_id in [nil, "unknown"] && warning("no session id"),
+        unanswered > 0 && warning("#{unanswered} tool call(s) without a result")
+      ],
+      &(&1 == false)
+    )
+  end
+
+  defp error(message, line \\ nil), do: %{level: :error, message: message, line: line}
+  defp warning(message, line \\ nil), do: %{level: :warning, message: message, line: line}
+end
@@ -0,0 +1,75 @@
+defmodule Panko.Sessions.Comment do
+  @moduledoc """
+  A comment on one block of a session.
+
+  Comments point at the block's position rather than its row, because
+  re-importing a session replaces its blocks while keeping their positions.
+  Creating or deleting a comment is broadcast on `comments:session:<id>` so
+  open viewers update.
+  """
+  use Ash.Resource,
+    domain: Panko.Sessions,
+    data_layer: AshPostgres.DataLayer,
+    notifiers: [Ash.Notifier.PubSub]
+
+  postgres do
+    table "comments"
+    repo Panko.Repo
+
+    references do
+      reference :session, on_delete: :delete
+    end
+  end
+
+  pub_sub do
+    module PankoWeb.Endpoint
+    prefix "comments"
+    publish_all :create, ["session", :session_id]
+    publish_all :destroy, ["session", :session_id]
+  end
+
+  attributes do
+    uuid_primary_key :id
+
+    attribute :position, :integer do
+      allow_nil? false
+      public? true
+    end
+
+    attribute :body, :string do
+      allow_nil? false
+      public? true
+      constraints max_length: 2000
+    end
+
+    attribute :author, :string do
+      allow_nil? true
+      public? true
+      constraints max_length: 80
+    end
+
+    create_timestamp :inserted_at
+  end
+
+  relationships do
+    belongs_to :session, Panko.Sessions.Session do
+      allow_nil? false
+      public? true
+    end
+  end
+
+  actions do
+    defaults [:read, :destroy]
+
+    create :create do
+      primary? true
+      accept [:session_id, :position, :body, :author]
+    end
+
+    read :for_session do
+      argument :session_id, :uuid, allow_nil?: false
+      filter expr(session_id == ^arg(:session_id))
+      prepare build(sort: [position: :asc, inserted_at: :asc])
+    end
+  end
+end
@@ -0,0 +1,68 @@
+defmodule Panko.Sessions.Diff do
+  @moduledoc """
+  Compares two sessions, e.g. two attempts at the same task.
+
+  Prompts are aligned by their order in each session; the first pair that
+  differs is where the sessions diverge. Tools invoked and files changed
+  are compared as sets, with per-tool call counts.
+  """
+
+  @type t :: %{
+          prompts: [%{index: pos_integer(), a: String.t() | nil, b: String.t() | nil}],
+          diverges_at: pos_integer() | nil,
+          tools: %{a: %{String.t() => pos_integer()}, b: %{String.t() => pos_integer()}},
+          files: %{only_a: [String.t()], only_b: [String.t()], both: [String.t()]}
+        }
+
+  @doc """
+  Compares sessions `a` and `b`, both with their blocks loaded.
+  """
+  @spec compare(Panko.Sessions.Session.t(), Panko.Sessions.Session.t()) :: t()
+  def compare(a, b) do
+    prompts_a = prompts(a.blocks)
+    prompts_b = prompts(b.blocks)
+    count = max(length(prompts_a), length(prompts_b))
+
+    prompts =
+      for index <- 1..count//1 do
+        %{index: index, a: Enum.at(prompts_a, index - 1), b: Enum.at(prompts_b, index - 1)}
+      end
+
+    files_a = files(a.blocks)
+    files_b = files(b.blocks)
+
+    %{
+      prompts: prompts,
+      diverges_at: Enum.find_value(prompts, &(normalize(&1.a) != normalize(&1.b) && &1.index)),
+      tools: %{a: tools(a.blocks), b: tools(b.blocks)},
+      files: %{
+        only_a: sorted(MapSet.difference(files_a, files_b)),
+        only_b: sorted(MapSet.difference(files_b, files_a)),
+        both: sorted(MapSet.intersection(files_a, files_b))
+      }
+    }
+  end
+
+  defp prompts(blocks) do
+    for %{block_type: :user_prompt, content: content} <- blocks, do: content
+  end
+
+  defp tools(blocks) do
+    blocks
+    |> Enum.filter(&(&1.block_type in [:tool_call, :file_edit, :sub_agent_spawn]))
+    |> Enum.map(&(&1.metadata && &1.metadata["name"]))
+    |> Enum.reject(&is_nil/1)
+    |> Enum.frequencies()
+  end
+
+  defp files(blocks) do
+    for %{block_type: :file_edit, metadata: %{"path" => path}} when is_binary(path) <- blocks,
+        into: MapSet.new(),
+        do: path
+  end
+
+  defp normalize(nil), do: nil
+  defp normalize(prompt), do: prompt |> String.split() |> Enum.join(" ")
+
+  defp sorted(set), do: set |> MapSet.to_list() |> Enum.sort()
+end
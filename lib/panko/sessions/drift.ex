@@ -0,0 +1,129 @@
+defmodule Panko.Sessions.Drift do
+  @moduledoc """
+  Checks whether the file edits recorded in a session are present in a
+  working tree or commit, to audit what an agent really changed.
+
+  Each `Write` and `Edit` block is checked against the file as it is now:
+
+    * `:present` - the written content, or the edit's new text, is there
+    * `:reverted` - an edit's old text is back in place of the new one
+    * `:changed` - the file differs in some other way
+    * `:missing` - the file does not exist
+    * `:superseded` - a later edit of the session rewrote this one, so it
+      cannot be checked on its own
+  """
+
+  @type status :: :present | :reverted | :changed | :missing | :superseded
+
+  @type entry :: %{
+          position: non_neg_integer(),
+          tool: String.t(),
+          path: String.t(),
+          status: status()
+        }
+
+  @type reader :: (String.t() -> {:ok, binary()} | {:error, term()})
+
+  @doc """
+  Checks the file edits of a session with its blocks loaded, reading files
+  with `read`, which is given each edit's path.
+  """
+  @spec check(Panko.Sessions.Session.t(), reader()) :: [entry()]
+  def check(session, read) do
+    edits =
+      for %{block_type: :file_edit, metadata: %{"path" => path} = meta} = block <- session.blocks,
+          is_binary(path),
+          do: {block.position, meta["name"], path, meta["input"] || %{}}
+
+    edits
+    |> Enum.with_index()
+    |> Enum.map(fn {{position, tool, path, input}, index} ->
+      later = Enum.drop(edits, index + 1)
+
+      status =
+        if superseded?(tool, path, input, later),
+          do: :superseded,
+          else: status(tool, input, read.(path))
+
+      %{position: position, tool: tool, path: path, status: status}
+    end)
+  end
+
+  @doc """
+  Counts entries per status.
+  """
+  @spec summary([entry()]) :: %{status() => non_neg_integer()}
+  def summary(entries) do
+    counts = Map.new([:present, :reverted, :changed, :missing, :superseded], &{&1, 0})
+    Enum.reduce(entries, counts, &Map.update!(&2, &1.status, fn count -> count + 1 end))
+  end
+
+  @doc """
+  Whether any edit is not present, ignoring superseded ones.
+  """
+  @spec drifted?([entry()]) :: boolean()
+  def drifted?(entries), do: Enum.any?(entries, &(&1.status in [:reverted, :changed, :missing]))
+
+  @doc """
+  Reads files from the working tree under `repo`.
+  """
+  @spec worktree_reader(Path.t()) :: reader()
+  def worktree_reader(repo), do: &File.read(Path.expand(&1, repo))
+
+  @doc """
+  Reads files as of `ref` in the git repository at `repo`.
+  """
+  @spec git_reader(Path.t(), String.t()) :: reader()
+  def git_reader(repo, ref) do
+    fn path ->
+      relative = Path.relative_to(Path.expand(path, repo), Path.expand(repo))
+
+      # `ref:./path` is relative to the directory git runs in rather than to
+      # the repository root, so projects in a subdirectory resolve. Listing
+      # the path first keeps git from printing errors for missing files.
+      with {listing, 0} when listing != "" <-
+             git(repo, ["ls-tree", "--name-only", ref, "--", relative]),
+           {content, 0} <- git(repo, ["show", "#{ref}:./#{relative}"]) do
+        {:ok, content}
+      else
+        _ -> {:error, :not_found}
+      end
+    end
+  end
+
+  defp git(dir, args), do: System.cmd("git", args, cd: dir)
+
+  # A whole-file write is rewritten by any later edit of the file; an edit
+  # by a later one replacing text that contains its new text.
+  defp superseded?("Write", path, _input, later), do: Enum.any?(later, &(elem(&1, 2) == path))
+
+  defp superseded?(_tool, path, %{"new_string" => new}, later)
+       when is_binary(new) and new != "" do
+    Enum.any?(later, fn
+      {_, "Write", ^path, _} -> true
+      {_, _, ^path, %{"old_string" => old}} when is_binary(old) -> String.contains?(old, new)
+      _ -> false
+    end)
+  end
+
+  defp superseded?(_tool, _path, _input, _later), do: false
+
+  defp status(_tool, _input, {:error, _reason}), do: :missing
+
+  defp status("Write", %{"content" => content}, {:ok, current}) do
+    if current == content, do: :present, else: :changed
+  end
+
+  defp status(_tool, %{"new_string" => new} = input, {:ok, current}) when is_binary(new) do
+    old = input["old_string"]
+
+    cond do
+      new != "" and String.contains?(current, new) -> :present
+      is_binary(old) and old != "" and String.contains?(current, old) -> :reverted
+      new == "" -> :present
+      true -> :changed
+    end
+  end
+
+  defp status(_tool, _input, {:ok, _current}), do: :changed
+end
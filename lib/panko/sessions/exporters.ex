@@ -0,0 +1,83 @@
+defmodule Panko.Sessions.Exporters do
+  @moduledoc """
+  Entry point for exporting sessions to standalone documents.
+
+  Sessions can be exported straight from the database, or from a session
+  file on disk without importing it first via `load_file/1`.
+  """
+
+  alias Panko.Sessions.{Block, Redactor, Session, SubAgent}
+  alias Panko.Sessions.Exporters.{Filter, Registry}
+  alias Panko.Sessions.Parsers
+
+  @doc """
+  Exports a session with its blocks loaded using the exporter for `format`.
+
+  Blocks are narrowed first with any `Panko.Sessions.Exporters.Filter`
+  options present in `opts`, then passed through `Panko.Sessions.Redactor`
+  unless `redact: false` is given (`:emails` tunes the redaction rules).
+  Remaining options go to the exporter.
+  """
+  @spec export(Session.t(), atom() | String.t(), keyword()) :: {:ok, iodata()} | {:error, term()}
+  def export(session, format, opts \\ []) do
+    with {:ok, document, _report} <- export_with_report(session, format, opts) do
+      {:ok, document}
+    end
+  end
+
+  @doc """
+  Like `export/3`, but also returns a report of the redactions made.
+  """
+  @spec export_with_report(Session.t(), atom() | String.t(), keyword()) ::
+          {:ok, iodata(), %{redactions: non_neg_integer(), redactions_by_rule: map()}}
+          | {:error, term()}
+  def export_with_report(session, format, opts \\ []) do
+    {filter_opts, opts} = Keyword.split(opts, Filter.keys())
+    {redact_opts, exporter_opts} = Keyword.split(opts, [:redact, :emails])
+
+    with {:ok, exporter} <- Registry.find_exporter(format) do
+      {session, counts} =
+        session
+        |> Filter.filter(filter_opts)
+        |> redact(redact_opts)
+
+      with {:ok, document} <- exporter.export(session, exporter_opts) do
+        {:ok, document, %{redactions: Redactor.total(counts), redactions_by_rule: counts}}
+      end
+    end
+  end
+
+  defp redact(session, opts) do
+    if Keyword.get(opts, :redact, true) do
+      Redactor.redact_session(session, opts)
+    else
+      {session, %{}}
+    end
+  end
+
+  @doc """
+  Parses a session file into an in-memory `Session` with blocks and
+  sub-agents populated, without touching the database.
+  """
+  @spec load_file(String.t()) :: {:ok, Session.t()} | {:error, term()}
+  def load_file(path) do
+    with {:ok, attrs} <- Panko.Sessions.ParseCache.parse(path) do
+      {:ok, from_attrs(attrs)}
+    end
+  end
+
+  @doc """
+  Builds an in-memory `Session` from parser output.
+  """
+  @spec from_attrs(Parsers.Parser.session_attrs()) :: Session.t()
+  def from_attrs(attrs) do
+    blocks = Enum.map(attrs.blocks, &with_id(Block, &1))
+    sub_agents = Enum.map(attrs.sub_agents, &with_id(SubAgent, &1))
+
+    Session
+    |> with_id(Map.drop(attrs, [:blocks, :sub_agents]))
+    |> Map.merge(%{blocks: blocks, sub_agents: sub_agents})
+  end
+
+  defp with_id(resource, attrs), do: struct(resource, Map.put(attrs, :id, Ash.UUID.generate()))
+end
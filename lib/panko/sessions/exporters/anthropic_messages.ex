@@ -0,0 +1,96 @@
+defmodule Panko.Sessions.Exporters.AnthropicMessages do
+  @moduledoc """
+  Exports a session as an Anthropic Messages API `messages` array.
+
+  Tool invocations become `tool_use` content blocks on the assistant
+  message, answered by `tool_result` blocks in the following user
+  message. Thinking blocks are omitted because they cannot be replayed
+  without their signatures.
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  alias Panko.Sessions.Exporters.Turns
+
+  @impl true
+  def format, do: :anthropic_messages
+
+  @impl true
+  def extension, do: ".json"
+
+  @impl true
+  def export(session, opts) do
+    session.blocks
+    |> Turns.group()
+    |> Enum.flat_map(&messages/1)
+    |> merge_consecutive_users()
+    |> Jason.encode(pretty: Keyword.get(opts, :pretty, true))
+  end
+
+  defp messages({:user, block}) do
+    [%{"role" => "user", "content" => [text_part(block.content)]}]
+  end
+
+  defp messages({:assistant, texts, tools}) do
+    assistant = %{
+      "role" => "assistant",
+      "content" => Enum.map(texts, &text_part(&1.content)) ++ Enum.map(tools, &tool_use/1)
+    }
+
+    case tools do
+      [] -> [assistant]
+      tools -> [assistant, %{"role" => "user", "content" => Enum.map(tools, &tool_result/1)}]
+    end
+  end
+
+  # Roles must alternate, so tool results followed by a new prompt are
+  # folded into a single user message.
+  defp merge_consecutive_users(messages) do
+    messages
+    |> Enum.chunk_while(
+      nil,
+      fn
+        %{"role" => "user"} = msg, %{"role" => "user"} = acc ->
+          {:cont, %{acc | "content" => acc["content"] ++ msg["content"]}}
+
+        msg, nil ->
+          {:cont, msg}
+
+        msg, acc ->
+          {:cont, acc, msg}
+      end,
+      fn
+        nil -> {:cont, nil}
+        acc -> {:cont, acc, nil}
+      end
+    )
+  end
+
+  defp text_part(text), do: %{"type" => "text", "text" => text || ""}
+
+  defp tool_use(block) do
+    metadata = block.metadata || %{}
+
+    %{
+      "type" => "tool_use",
+      "id" => Turns.tool_id(block, "toolu"),
+      "name" => metadata["name"] || "tool",
+      "input" => metadata["input"] || %{}
+    }
+  end
+
+  defp tool_result(block) do
+    metadata = block.metadata || %{}
+
+    %{
+      "type" => "tool_result",
+      "tool_use_id" => Turns.tool_id(block, "toolu"),
+      "content" => output_text(metadata["output"]),
+      "is_error" => metadata["is_error"] == true
+    }
+  end
+
+  defp output_text(nil), do: ""
+  defp output_text(output) when is_binary(output), do: output
+  defp output_text(output), do: Jason.encode!(output)
+end
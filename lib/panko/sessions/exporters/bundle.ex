@@ -0,0 +1,131 @@
+defmodule Panko.Sessions.Exporters.Bundle do
+  @moduledoc """
+  Combines several sessions, typically every session in a project, into a
+  single export.
+
+  Two bundle kinds are supported:
+
+    * `:markdown` - one document with an index followed by each session's
+      transcript
+    * `:zip` - an archive with one file per session, exported in `:format`
+      (default: `:markdown`), plus an `index.md`
+
+  Every session goes through `Panko.Sessions.Exporters.export_with_report/3`,
+  so filter and redaction options apply to each of them.
+  """
+
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Exporters.Download
+
+  @kinds [:markdown, :zip]
+
+  @doc """
+  Returns the supported bundle kinds.
+  """
+  @spec kinds() :: [atom()]
+  def kinds, do: @kinds
+
+  @doc """
+  Builds a bundle of `sessions`, returning it with the combined redaction report.
+  """
+  @spec build([Panko.Sessions.Session.t()], atom() | String.t(), keyword()) ::
+          {:ok, iodata(), map()} | {:error, term()}
+  def build(sessions, kind, opts \\ [])
+
+  def build(sessions, kind, opts) when is_binary(kind) do
+    case Enum.find(@kinds, &(Atom.to_string(&1) == kind)) do
+      nil -> {:error, :unknown_bundle}
+      kind -> build(sessions, kind, opts)
+    end
+  end
+
+  def build(sessions, :markdown, opts) do
+    opts = opts |> Keyword.delete(:format) |> Keyword.put(:front_matter, false)
+
+    with {:ok, documents, report} <- export_all(sessions, :markdown, opts) do
+      entries = Enum.map(documents, fn {session, _document} -> {session, nil} end)
+      transcripts = Enum.map(documents, fn {_session, document} -> document end)
+
+      {:ok, Enum.intersperse([index(entries) | transcripts], "\n---\n\n"), report}
+    end
+  end
+
+  def build(sessions, :zip, opts) do
+    {format, opts} = Keyword.pop(opts, :format, :markdown)
+
+    with {:ok, exporter} <- Exporters.Registry.find_exporter(format),
+         {:ok, documents, report} <- export_all(sessions, format, opts) do
+      files =
+        documents
+        |> Enum.with_index(1)
+        |> Enum.map(fn {{session, document}, i} ->
+          {session, file_name(session, i, exporter.extension()), document}
+        end)
+
+      index = files |> Enum.map(fn {session, name, _document} -> {session, name} end) |> index()
+
+      entries =
+        [{"index.md", index} | Enum.map(files, fn {_session, name, doc} -> {name, doc} end)]
+        |> Enum.map(fn {name, doc} -> {String.to_charlist(name), IO.iodata_to_binary(doc)} end)
+
+      {:ok, {_name, zip}} = :zip.create(~c"sessions.zip", entries, [:memory])
+      {:ok, zip, report}
+    end
+  end
+
+  def build(_sessions, _kind, _opts), do: {:error, :unknown_bundle}
+
+  defp export_all(sessions, format, opts) do
+    empty = %{redactions: 0, redactions_by_rule: %{}}
+
+    sessions
+    |> Enum.sort_by(& &1.started_at, DateTime)
+    |> Enum.reduce_while({:ok, [], empty}, fn session, {:ok, documents, report} ->
+      case Exporters.export_with_report(session, format, opts) do
+        {:ok, document, session_report} ->
+          {:cont, {:ok, [{session, document} | documents], merge(report, session_report)}}
+
+        {:error, reason} ->
+          {:halt, {:error, reason}}
+      end
+    end)
+    |> case do
+      {:ok, documents, report} -> {:ok, Enum.reverse(documents), report}
+      error -> error
+    end
+  end
+
+  defp merge(a, b) do
+    %{
+      redactions: a.redactions + b.redactions,
+      redactions_by_rule:
+        Map.merge(a.redactions_by_rule, b.redactions_by_rule, fn _rule, x, y -> x + y end)
+    }
+  end
+
+  defp index(entries) do
+    title =
+      case entries do
+        [{%{project: project}, _} | _] when is_binary(project) -> project
+        _ -> "Sessions"
+      end
+
+    items =
+      entries
+      |> Enum.with_index(1)
+      |> Enum.map(fn {{session, file}, i} ->
+        name = String.replace(session.title || "Untitled session", ~r/\s+/, " ")
+        link = if file, do: "[#{name}](#{file})", else: name
+        "#{i}. #{link} · #{format_date(session.started_at)}\n"
+      end)
+
+    ["# #{title}\n\n", "#{length(entries)} sessions\n\n" | items]
+  end
+
+  defp file_name(session, i, extension) do
+    "#{String.pad_leading(Integer.to_string(i), 3, "0")}-#{Download.slug(session)}#{extension}"
+  end
+
+  defp format_date(nil), do: "unknown date"
+  defp format_date(dt), do: Calendar.strftime(dt, "%Y-%m-%d %H:%M")
+end
@@ -0,0 +1,29 @@
+defmodule Panko.Sessions.Exporters.ClaudeCode do
+  @moduledoc """
+  Exports a session as Claude Code JSONL, written by
+  `Panko.Sessions.Builder`.
+
+  Turns sessions from other sources, or Panko JSON exports, back into
+  files Claude Code tooling reads. Only what Panko keeps is written: one
+  line per block, tool results as their text, and token usage on the
+  blocks that carried it.
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  alias Panko.Sessions.Builder
+
+  @impl true
+  def format, do: :claude_code
+
+  @impl true
+  def extension, do: ".jsonl"
+
+  @impl true
+  def export(session, _opts) do
+    session
+    |> Builder.from_session()
+    |> then(&if(session.title, do: Builder.summary(&1, session.title), else: &1))
+    |> Builder.to_jsonl()
+  end
+end
@@ -0,0 +1,75 @@
+defmodule Panko.Sessions.Exporters.Commands do
+  @moduledoc """
+  Exports the shell commands run in a session as an annotated script.
+
+  Each `Bash` invocation is preceded by a comment with its number, time,
+  description, exit code and how long it ran, which makes the output a
+  starting point for runbooks. Exit codes come from the recorded tool
+  result: `0` for successful results, the reported code for failures, and
+  `?` when no result was recorded or a failure did not report one.
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  alias Panko.Sessions.ToolTiming
+
+  @impl true
+  def format, do: :commands
+
+  @impl true
+  def extension, do: ".sh"
+
+  @impl true
+  def export(session, _opts) do
+    commands =
+      for %{block_type: :tool_call, metadata: %{"name" => "Bash", "input" => input}} = block <-
+            session.blocks,
+          is_binary(input["command"]) do
+        block
+      end
+
+    script =
+      commands
+      |> Enum.with_index(1)
+      |> Enum.map(fn {block, i} ->
+        [comment(i, block), block.metadata["input"]["command"], "\n"]
+      end)
+
+    {:ok, ["#!/usr/bin/env bash\n\n" | Enum.intersperse(script, "\n")]}
+  end
+
+  @doc """
+  Returns the exit code recorded for a `Bash` tool block, or `nil` if unknown.
+  """
+  @spec exit_code(map()) :: non_neg_integer() | nil
+  def exit_code(%{metadata: %{"is_error" => true} = metadata}) do
+    case Regex.run(~r/exit code:?\s*(\d+)/i, metadata["output"] || "") do
+      [_, code] -> String.to_integer(code)
+      nil -> nil
+    end
+  end
+
+  def exit_code(%{metadata: %{"output" => _}}), do: 0
+  def exit_code(_block), do: nil
+
+  defp comment(i, block) do
+    description = block.metadata["input"]["description"]
+
+    details =
+      [
+        "##{i}",
+        block.timestamp && Calendar.strftime(block.timestamp, "%H:%M:%S"),
+        description,
+        "exit #{exit_code(block) || "?"}",
+        duration(block)
+      ]
+      |> Enum.reject(&is_nil/1)
+      |> Enum.join(" · ")
+
+    "# #{String.replace(details, "\n", " ")}\n"
+  end
+
+  defp duration(block) do
+    if ms = ToolTiming.duration_ms(block), do: ToolTiming.format(ms)
+  end
+end
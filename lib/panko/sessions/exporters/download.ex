@@ -0,0 +1,80 @@
+defmodule Panko.Sessions.Exporters.Download do
+  @moduledoc """
+  Writes exported sessions as files into a directory, one per session.
+
+  The directory defaults to `PANKO_DOWNLOAD_DIR` (the `:download_dir`
+  setting), else the current directory. Files are named after the
+  session's title, and an existing file is never overwritten: the name
+  gets the session's start time appended instead, and a counter should
+  that be taken too.
+  """
+
+  alias Panko.Sessions.Exporters
+
+  @doc """
+  Returns the directory to write to: `dir` when given, else the
+  configured download directory, else the current directory.
+  """
+  @spec dir(Path.t() | nil) :: Path.t()
+  def dir(nil), do: Path.expand(Application.get_env(:panko, :download_dir) || ".")
+  def dir(dir), do: Path.expand(dir)
+
+  @doc """
+  Exports `session` in `format` and writes it into `dir`, which is
+  created if needed. Takes the options of `Panko.Sessions.Exporters.export/3`.
+  """
+  @spec write(Panko.Sessions.Session.t(), Path.t(), atom() | String.t(), keyword()) ::
+          {:ok, Path.t(), map()} | {:error, term()}
+  def write(session, dir, format, opts \\ []) do
+    with {:ok, exporter} <- Exporters.Registry.find_exporter(format),
+         {:ok, document, report} <- Exporters.export_with_report(session, format, opts),
+         :ok <- File.mkdir_p(dir) do
+      dir
+      |> candidate_paths(slug(session) <> exporter.extension(), session.started_at)
+      |> write_new(document, report)
+    end
+  end
+
+  # The paths to try for a file named `name`: the name itself, then with
+  # the timestamp `at` (or the current time) appended, then with a counter.
+  defp candidate_paths(dir, name, at) do
+    extension = Path.extname(name)
+    stamp = Calendar.strftime(at || DateTime.utc_now(), "%Y%m%d-%H%M%S")
+    stamped = "#{Path.rootname(name)}-#{stamp}"
+
+    Stream.iterate(0, &(&1 + 1))
+    |> Stream.map(fn
+      0 -> Path.join(dir, name)
+      1 -> Path.join(dir, stamped <> extension)
+      n -> Path.join(dir, "#{stamped}-#{n}#{extension}")
+    end)
+  end
+
+  # Files are created exclusively, so a file written by someone else
+  # between the check and the write cannot be overwritten.
+  defp write_new(paths, document, report) do
+    Enum.reduce_while(paths, nil, fn path, nil ->
+      case File.write(path, document, [:exclusive]) do
+        :ok -> {:halt, {:ok, path, report}}
+        {:error, :eexist} -> {:cont, nil}
+        {:error, reason} -> {:halt, {:error, reason}}
+      end
+    end)
+  end
+
+  @doc """
+  A file name for the session from its title, or its id without one.
+  """
+  @spec slug(map()) :: String.t()
+  def slug(session) do
+    slug =
+      (session.title || session.external_id || "session")
+      |> String.downcase()
+      |> String.replace(~r/[^a-z0-9]+/, "-")
+      |> String.trim("-")
+      |> String.slice(0, 50)
+      |> String.trim_trailing("-")
+
+    if slug == "", do: "session", else: slug
+  end
+end
@@ -0,0 +1,14 @@
+defmodule Panko.Sessions.Exporters.Exporter do
+  @moduledoc """
+  Behaviour for session exporters.
+
+  Exporters are pure functions: session in, document out. The session
+  must have its `:blocks` loaded, either from the database or built
+  in memory by `Panko.Sessions.Exporters.load_file/1`.
+  """
+
+  @callback format() :: atom()
+  @callback extension() :: String.t()
+  @callback export(session :: Panko.Sessions.Session.t(), opts :: keyword()) ::
+              {:ok, iodata()} | {:error, term()}
+end
@@ -0,0 +1,84 @@
+defmodule Panko.Sessions.Exporters.Filter do
+  @moduledoc """
+  Narrows a session's blocks before export.
+
+  ## Options
+
+    * `:thinking` - keep thinking blocks (default: `true`)
+    * `:tool_output` - keep tool outputs in block metadata (default: `true`)
+    * `:system` - keep `<system-reminder>` and command caveat text inside
+      block content (default: `true`)
+    * `:only` - list of block types to keep
+    * `:exclude` - list of block types to drop
+    * `:range` - a `Range` of block positions to keep
+    * `:since` / `:until` - keep blocks whose timestamp falls within the
+      bounds (inclusive); blocks without a timestamp are kept
+  """
+
+  @system_pattern ~r/<(system-reminder|local-command-caveat)>.*?<\/\1>/s
+
+  @filter_keys [:thinking, :tool_output, :system, :only, :exclude, :range, :since, :until]
+
+  @doc """
+  Returns the option keys understood by `filter/2`.
+  """
+  @spec keys() :: [atom()]
+  def keys, do: @filter_keys
+
+  @doc """
+  Applies the filter options to the session's blocks.
+  """
+  @spec filter(Panko.Sessions.Session.t(), keyword()) :: Panko.Sessions.Session.t()
+  def filter(session, opts) do
+    blocks =
+      session.blocks
+      |> Enum.filter(&keep?(&1, opts))
+      |> Enum.map(&strip(&1, opts))
+      |> Enum.reject(&empty_text?/1)
+
+    %{session | blocks: blocks}
+  end
+
+  defp keep?(block, opts) do
+    type_allowed?(block.block_type, opts) and
+      (Keyword.get(opts, :thinking, true) or block.block_type != :thinking) and
+      in_range?(block.position, opts[:range]) and
+      within?(block.timestamp, opts[:since], opts[:until])
+  end
+
+  defp type_allowed?(type, opts) do
+    (is_nil(opts[:only]) or type in opts[:only]) and type not in (opts[:exclude] || [])
+  end
+
+  defp in_range?(_position, nil), do: true
+  defp in_range?(position, %Range{} = range), do: position in range
+
+  defp within?(nil, _since, _until), do: true
+
+  defp within?(timestamp, since, until) do
+    (is_nil(since) or DateTime.compare(timestamp, since) != :lt) and
+      (is_nil(until) or DateTime.compare(timestamp, until) != :gt)
+  end
+
+  defp strip(block, opts) do
+    block
+    |> strip_tool_output(Keyword.get(opts, :tool_output, true))
+    |> strip_system(Keyword.get(opts, :system, true))
+  end
+
+  defp strip_tool_output(%{metadata: %{"output" => _} = metadata} = block, false),
+    do: %{block | metadata: Map.delete(metadata, "output")}
+
+  defp strip_tool_output(block, _keep), do: block
+
+  defp strip_system(%{content: content} = block, false) when is_binary(content),
+    do: %{block | content: content |> String.replace(@system_pattern, "") |> String.trim()}
+
+  defp strip_system(block, _keep), do: block
+
+  defp empty_text?(%{block_type: type, content: ""})
+       when type in [:user_prompt, :assistant_response],
+       do: true
+
+  defp empty_text?(_block), do: false
+end
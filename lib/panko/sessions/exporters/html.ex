@@ -0,0 +1,355 @@
+defmodule Panko.Sessions.Exporters.Html do
+  @moduledoc """
+  Exports a session as a single self-contained HTML document.
+
+  Blocks are laid out like the web viewer, and the compiled application
+  stylesheet and the export script (`assets/js/export.js`) are inlined, so
+  the file can be archived or emailed and opened without a running server.
+  Tool calls and thinking are plain `<details>` sections. A table of
+  contents links to the user prompts, and a list of the session's
+  bookmarks, when loaded, to the bookmarked blocks. Loaded comments follow
+  the block they are on.
+
+  Returns `{:error, :assets_missing}` when the assets have not been built,
+  see `mix assets.build`.
+
+  ## Options
+
+    * `:stylesheet_href` - link this stylesheet instead of inlining it, for
+      pages that share one stylesheet such as `Panko.Sessions.Exporters.Site`
+    * `:back_href` - adds a link back to an index page
+    * `:assets_dir` - where the compiled assets are read from, defaults to
+      the `:export_assets_dir` setting or `priv/static/assets`
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  use Phoenix.Component
+
+  alias Panko.Sessions.{Markdown, TableOfContents, ToolTiming}
+
+  @important_tools ~w(Write Edit Bash Read NotebookEdit)
+
+  @impl true
+  def format, do: :html
+
+  @impl true
+  def extension, do: ".html"
+
+  @impl true
+  def export(session, opts) do
+    stylesheet_href = opts[:stylesheet_href]
+
+    with {:ok, stylesheet} <- inline_stylesheet(stylesheet_href, opts),
+         {:ok, script} <- read_asset("js/export.js", opts) do
+      assigns = %{
+        session: session,
+        title: session.title || "Untitled session",
+        stylesheet_href: stylesheet_href,
+        stylesheet: stylesheet && Phoenix.HTML.raw(stylesheet),
+        script: Phoenix.HTML.raw(script),
+        back_href: opts[:back_href],
+        toc: TableOfContents.entries(session.blocks),
+        bookmarks: TableOfContents.bookmarks(session.blocks, loaded(session, :bookmarks)),
+        comments: session |> loaded(:comments) |> Enum.group_by(& &1.position)
+      }
+
+      {:ok, ["<!DOCTYPE html>\n", Phoenix.HTML.Safe.to_iodata(document(assigns))]}
+    end
+  end
+
+  @doc """
+  Returns the compiled application stylesheet.
+  """
+  @spec stylesheet(keyword()) :: {:ok, String.t()} | {:error, :assets_missing}
+  def stylesheet(opts \\ []), do: read_asset("css/app.css", opts)
+
+  defp inline_stylesheet(nil, opts), do: stylesheet(opts)
+  defp inline_stylesheet(_href, _opts), do: {:ok, nil}
+
+  defp read_asset(path, opts) do
+    case File.read(Path.join(Keyword.get_lazy(opts, :assets_dir, &assets_dir/0), path)) do
+      {:ok, contents} -> {:ok, contents}
+      {:error, _} -> {:error, :assets_missing}
+    end
+  end
+
+  defp assets_dir do
+    Application.get_env(:panko, :export_assets_dir) ||
+      Path.join(:code.priv_dir(:panko), "static/assets")
+  end
+
+  defp document(assigns) do
+    ~H"""
+    <html lang="en">
+      <head>
+        <meta charset="utf-8" />
+        <meta name="viewport" content="width=device-width, initial-scale=1" />
+        <meta name="generator" content="Panko" />
+        <title>{@title} · Panko</title>
+        <link :if={@stylesheet_href} rel="stylesheet" href={@stylesheet_href} />
+        <style :if={@stylesheet}><%= @stylesheet %></style>
+        <script><%= @script %></script>
+      </head>
+      <body class="min-h-screen bg-base-100 font-[Inter,sans-serif]">
+        <main class="container mx-auto px-4 py-8 max-w-4xl">
+          <a :if={@back_href} href={@back_href} class="link link-hover text-sm">
+            &larr; All sessions
+          </a>
+          <div class="flex items-start justify-between gap-4">
+            <h1 class="text-2xl font-bold mb-1">{@title}</h1>
+            <button type="button" data-toggle-all aria-pressed="false" class="btn btn-ghost btn-sm">
+              Expand all
+            </button>
+          </div>
+          <p :if={@session.project} class="text-sm text-base-content/60 mb-6 font-mono">
+            {@session.project}
+          </p>
+
+          <.toc entries={@toc} />
+          <.bookmarks entries={@bookmarks} />
+
+          <div role="feed" class="space-y-2">
+            <div :for={blk <- @session.blocks} id={TableOfContents.anchor(blk)}>
+              <.block block={blk} />
+              <.comments comments={Map.get(@comments, blk.position, [])} />
+            </div>
+          </div>
+
+          <footer class="text-center text-xs text-base-content/40 mt-12 py-4 border-t border-base-300">
+            Exported with <a href="https://github.com/jordangarrison/panko" class="link">Panko</a>
+          </footer>
+        </main>
+      </body>
+    </html>
+    """
+  end
+
+  defp toc(assigns) do
+    ~H"""
+    <details :if={@entries != []} class="mb-4 text-sm border border-base-300 rounded-lg">
+      <summary class="px-4 py-2 cursor-pointer bg-base-200/30 hover:bg-base-200/60">
+        Contents <span class="text-base-content/50">({length(@entries)})</span>
+      </summary>
+      <nav aria-label="Contents" class="px-4 py-2">
+        <ol class="list-decimal list-inside space-y-1">
+          <li :for={entry <- @entries}>
+            <a href={"##{entry.anchor}"} class="link link-hover">{entry.title}</a>
+          </li>
+        </ol>
+      </nav>
+    </details>
+    """
+  end
+
+  defp bookmarks(assigns) do
+    ~H"""
+    <nav
+      :if={@entries != []}
+      id="bookmarks"
+      aria-label="Bookmarks"
+      class="mb-4 text-sm border border-base-300 rounded-lg px-4 py-2"
+    >
+      <h2 class="font-medium mb-1">
+        Bookmarks <span class="text-base-content/50">({length(@entries)})</span>
+      </h2>
+      <ul class="space-y-1">
+        <li :for={entry <- @entries} class="flex gap-2">
+          <span class="text-base-content/40">★</span>
+          <a href={"##{entry.anchor}"} class="link link-hover">{entry.title}</a>
+        </li>
+      </ul>
+    </nav>
+    """
+  end
+
+  defp block(%{block: %{block_type: :user_prompt}} = assigns) do
+    ~H"""
+    <.card block={@block} label="User" color="info">
+      <div class="px-4 py-3 whitespace-pre-wrap">{@block.content}</div>
+    </.card>
+    """
+  end
+
+  defp block(%{block: %{block_type: :assistant_response}} = assigns) do
+    assigns = assign(assigns, :html, markdown(assigns.block.content))
+
+    ~H"""
+    <.card block={@block} label="Assistant" color="success">
+      <div class="px-4 py-3 prose prose-sm max-w-none dark:prose-invert">{@html}</div>
+    </.card>
+    """
+  end
+
+  defp block(%{block: %{block_type: :thinking}} = assigns) do
+    ~H"""
+    <article class="block border border-base-300 rounded-lg mb-4 border-l-4 border-l-secondary opacity-85 overflow-hidden">
+      <details>
+        <summary class="flex items-center justify-between px-4 py-2 cursor-pointer bg-base-200/30 hover:bg-base-200/60">
+          <span class="text-sm font-semibold text-secondary italic">Thinking</span>
+          <.time block={@block} />
+        </summary>
+        <div class="px-4 py-3 whitespace-pre-wrap text-sm italic text-base-content/70">
+          {@block.content}
+        </div>
+      </details>
+    </article>
+    """
+  end
+
+  defp block(%{block: %{block_type: type}} = assigns) when type in [:tool_call, :file_edit] do
+    metadata = assigns.block.metadata || %{}
+    name = metadata["name"] || if(type == :file_edit, do: "File Edit", else: "Tool")
+    input = if type == :file_edit, do: metadata["input"] || metadata, else: metadata["input"]
+    output = encode(metadata["output"])
+
+    assigns =
+      assigns
+      |> assign(:label, tool_label(type, name, metadata))
+      |> assign(:color, if(type == :file_edit, do: "error", else: "warning"))
+      |> assign(:input, encode(input))
+      |> assign(:output, output)
+      |> assign(:open, type == :file_edit or name in @important_tools)
+      |> assign(:output_lines, if(output != "", do: length(String.split(output, "\n")), else: 0))
+      |> assign(:duration_ms, ToolTiming.duration_ms(assigns.block))
+
+    ~H"""
+    <.card block={@block} label={@label} color={@color}>
+      <details open={@open}>
+        <summary class="px-4 py-2 cursor-pointer bg-base-200/30 border-b border-base-300 text-sm text-base-content/70 hover:bg-base-200/60">
+          Input
+        </summary>
+        <pre class="text-xs overflow-x-auto p-4 bg-base-300/30"><code>{@input}</code></pre>
+      </details>
+      <details :if={@output != ""} open={@open && @output_lines <= 100}>
+        <summary class="px-4 py-2 cursor-pointer bg-base-200/30 border-b border-base-300 text-sm text-base-content/70 hover:bg-base-200/60">
+          Output
+          <span :if={@output_lines > 100} class="text-xs text-base-content/40 ml-1">
+            ({@output_lines} lines)
+          </span>
+          <span :if={@duration_ms} class="badge badge-ghost badge-xs ml-1">
+            {ToolTiming.format(@duration_ms)}
+          </span>
+        </summary>
+        <pre class="text-xs overflow-x-auto p-4 bg-base-300/30"><code>{@output}</code></pre>
+      </details>
+    </.card>
+    """
+  end
+
+  defp block(%{block: %{block_type: :sub_agent_spawn}} = assigns) do
+    metadata = assigns.block.metadata || %{}
+
+    assigns =
+      assigns
+      |> assign(:label, "Sub-Agent: #{metadata["agent_type"] || "unknown"}")
+      |> assign(:description, metadata["description"] || "")
+
+    ~H"""
+    <div class="ml-8">
+      <.card block={@block} label={@label} color="accent">
+        <div class="px-4 py-3 text-sm">{@description}</div>
+      </.card>
+    </div>
+    """
+  end
+
+  defp block(assigns), do: ~H""
+
+  attr :block, :map, required: true
+  attr :label, :string, required: true
+  attr :color, :string, required: true
+  slot :inner_block, required: true
+
+  # Class names are written out in full so Tailwind finds them.
+  defp card(assigns) do
+    ~H"""
+    <article class={[
+      "block border border-base-300 rounded-lg mb-4 border-l-4 overflow-hidden",
+      border_class(@color)
+    ]}>
+      <div class="flex items-center justify-between px-4 py-2 bg-base-200/50 border-b border-base-300">
+        <span class={["text-sm font-semibold", text_class(@color)]}>{@label}</span>
+        <.time block={@block} />
+      </div>
+      {render_slot(@inner_block)}
+    </article>
+    """
+  end
+
+  defp time(assigns) do
+    ~H"""
+    <time :if={@block.timestamp} class="text-xs text-base-content/50">
+      {Calendar.strftime(@block.timestamp, "%H:%M:%S")}
+    </time>
+    """
+  end
+
+  defp comments(assigns) do
+    ~H"""
+    <div :if={@comments != []} class="-mt-1 mb-4 ml-4 space-y-2">
+      <div :for={comment <- @comments} class="text-sm border-l-2 border-info pl-3">
+        <span class="font-semibold">{comment.author || "Anonymous"}</span>
+        <time class="text-xs text-base-content/50 ml-1">
+          {Calendar.strftime(comment.inserted_at, "%Y-%m-%d %H:%M")}
+        </time>
+        <p class="whitespace-pre-wrap">{comment.body}</p>
+      </div>
+    </div>
+    """
+  end
+
+  defp border_class("info"), do: "border-l-info"
+  defp border_class("success"), do: "border-l-success"
+  defp border_class("warning"), do: "border-l-warning"
+  defp border_class("error"), do: "border-l-error"
+  defp border_class("accent"), do: "border-l-accent"
+
+  defp text_class("info"), do: "text-info"
+  defp text_class("success"), do: "text-success"
+  defp text_class("warning"), do: "text-warning"
+  defp text_class("error"), do: "text-error"
+  defp text_class("accent"), do: "text-accent"
+
+  defp tool_label(:file_edit, name, metadata) do
+    path = metadata["path"] || get_in(metadata, ["input", "file_path"])
+    if is_binary(path), do: "#{name} #{path}", else: name
+  end
+
+  defp tool_label(:tool_call, name, _metadata), do: "Tool: #{name}"
+
+  # System reminders are noise in an archived transcript; the viewer drops
+  # them too.
+  defp markdown(content) when is_binary(content) do
+    content
+    |> String.replace(~r/<system-reminder>.*?<\/system-reminder>/s, "")
+    |> Markdown.to_html()
+    |> add_copy_buttons()
+    |> Phoenix.HTML.raw()
+  end
+
+  defp markdown(_content), do: ""
+
+  defp add_copy_buttons(html) do
+    button = """
+    <button type="button" data-copy-code class="btn btn-ghost btn-xs absolute top-1 right-1" \
+    aria-label="Copy code">Copy</button>\
+    """
+
+    Regex.replace(~r{<pre>.*?</pre>}s, html, fn pre ->
+      ~s(<div class="relative">) <> button <> pre <> "</div>"
+    end)
+  end
+
+  defp encode(nil), do: ""
+  defp encode(data) when is_binary(data), do: data
+  defp encode(data) when is_map(data), do: Jason.encode!(data, pretty: true)
+  defp encode(data), do: inspect(data)
+
+  defp loaded(session, key) do
+    case Map.get(session, key) do
+      records when is_list(records) -> records
+      _not_loaded -> []
+    end
+  end
+end
@@ -0,0 +1,139 @@
+defmodule Panko.Sessions.Exporters.Json do
+  @moduledoc """
+  Exports a session as normalized JSON with a versioned schema.
+
+  The document is agent-agnostic so downstream tooling does not need to
+  understand each agent's native session format:
+
+      {
+        "schema": "panko.session",
+        "schema_version": 1,
+        "session": {
+          "external_id": "...", "source_type": "claude_code",
+          "project": "...", "title": "...", "started_at": "ISO 8601"
+        },
+        "usage": {
+          "input_tokens": 0, "output_tokens": 0,
+          "cache_creation_input_tokens": 0, "cache_read_input_tokens": 0
+        },
+        "blocks": [
+          {
+            "position": 0, "type": "user_prompt", "role": "user",
+            "timestamp": "ISO 8601" | null, "content": "..." | null,
+            "tool": {"name": "...", "input": {}, "output": ... | null} | null
+          }
+        ],
+        "sub_agents": [
+          {
+            "external_id": "...", "agent_type": "...", "description": "...",
+            "prompt": "...", "status": "running", "result": "..." | null,
+            "spawned_at": "ISO 8601", "completed_at": "ISO 8601" | null
+          }
+        ],
+        "comments": [
+          {
+            "position": 0, "author": "..." | null, "body": "...",
+            "created_at": "ISO 8601"
+          }
+        ]
+      }
+
+  Sub-agents and comments are empty lists unless loaded on the session.
+
+  `schema_version` is bumped whenever a field is removed or changes meaning;
+  new fields may be added without a bump.
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  alias Panko.Sessions.Usage
+
+  @schema "panko.session"
+  @schema_version 1
+
+  @impl true
+  def format, do: :json
+
+  @impl true
+  def extension, do: ".json"
+
+  @doc """
+  The schema identifier written to every export.
+  """
+  def schema, do: @schema
+
+  @doc """
+  The current schema version written to every export.
+  """
+  def schema_version, do: @schema_version
+
+  @impl true
+  def export(session, opts) do
+    # Schema keys come first so readers can identify the file from its start.
+    document =
+      Jason.OrderedObject.new([
+        {"schema", @schema},
+        {"schema_version", @schema_version},
+        {"session",
+         %{
+           "external_id" => session.external_id,
+           "source_type" => session.source_type,
+           "project" => session.project,
+           "title" => session.title,
+           "started_at" => session.started_at
+         }},
+        {"usage", Usage.totals(session.blocks)},
+        {"blocks", Enum.map(session.blocks, &encode_block/1)},
+        {"sub_agents", Enum.map(loaded(session.sub_agents), &encode_sub_agent/1)},
+        {"comments", Enum.map(loaded(session.comments), &encode_comment/1)}
+      ])
+
+    Jason.encode(document, pretty: Keyword.get(opts, :pretty, true))
+  end
+
+  defp encode_block(block) do
+    %{
+      "position" => block.position,
+      "type" => block.block_type,
+      "role" => role(block.block_type),
+      "timestamp" => block.timestamp,
+      "content" => block.content,
+      "tool" => encode_tool(block)
+    }
+  end
+
+  defp encode_tool(%{block_type: type, metadata: %{"name" => name} = metadata})
+       when type in [:tool_call, :file_edit, :sub_agent_spawn] do
+    %{"name" => name, "input" => metadata["input"], "output" => metadata["output"]}
+  end
+
+  defp encode_tool(_block), do: nil
+
+  defp encode_sub_agent(agent) do
+    %{
+      "external_id" => agent.external_id,
+      "agent_type" => agent.agent_type,
+      "description" => agent.description,
+      "prompt" => agent.prompt,
+      "status" => agent.status,
+      "result" => agent.result,
+      "spawned_at" => agent.spawned_at,
+      "completed_at" => agent.completed_at
+    }
+  end
+
+  defp encode_comment(comment) do
+    %{
+      "position" => comment.position,
+      "author" => comment.author,
+      "body" => comment.body,
+      "created_at" => comment.inserted_at
+    }
+  end
+
+  defp role(:user_prompt), do: "user"
+  defp role(_block_type), do: "assistant"
+
+  defp loaded(%Ash.NotLoaded{}), do: []
+  defp loaded(records), do: records
+end
@@ -0,0 +1,195 @@
+defmodule Panko.Sessions.Exporters.Markdown do
+  @moduledoc """
+  Exports a complete session transcript as Markdown.
+
+  The document starts with YAML front matter holding session metadata,
+  followed by one heading per block. Tool inputs and outputs are written
+  as fenced code blocks whose fence is always longer than any backtick
+  run in the content, so embedded fences cannot break out. When the
+  session's comments are loaded, they follow the block they are on.
+
+  Sessions with several prompts get a table of contents after the title,
+  linking to anchors placed before each prompt. When the session's
+  bookmarks are loaded, a list of them follows, linking to anchors before
+  the bookmarked blocks.
+
+  ## Options
+
+    * `:front_matter` - include the YAML front matter (default: `true`)
+    * `:toc` - include the table of contents (default: `true`)
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  alias Panko.Sessions.TableOfContents
+
+  @impl true
+  def format, do: :markdown
+
+  @impl true
+  def extension, do: ".md"
+
+  @impl true
+  def export(session, opts) do
+    title = session.title || "Untitled session"
+
+    front_matter =
+      if Keyword.get(opts, :front_matter, true), do: [front_matter(session)], else: []
+
+    comments = comments_by_position(session)
+    toc = if Keyword.get(opts, :toc, true), do: TableOfContents.entries(session.blocks), else: []
+    bookmarks = TableOfContents.bookmarks(session.blocks, loaded(session, :bookmarks))
+    anchored = MapSet.new(toc ++ bookmarks, & &1.position)
+
+    blocks =
+      session.blocks
+      |> Enum.map(fn block ->
+        anchor =
+          if block.position in anchored,
+            do: [~s(<a id="#{TableOfContents.anchor(block)}"></a>\n)],
+            else: []
+
+        [anchor, render_block(block) | render_comments(Map.get(comments, block.position, []))]
+      end)
+      |> Enum.reject(&(&1 == [[], []]))
+
+    sections =
+      front_matter ++
+        ["# #{heading_text(title)}\n" | render_toc(toc) ++ render_bookmarks(bookmarks) ++ blocks]
+
+    {:ok, Enum.intersperse(sections, "\n")}
+  end
+
+  @doc """
+  Wraps `text` in a code fence longer than any backtick run it contains.
+  """
+  @spec fence(String.t(), String.t()) :: String.t()
+  def fence(text, lang \\ "") do
+    longest =
+      ~r/`+/
+      |> Regex.scan(text)
+      |> Enum.map(fn [run] -> String.length(run) end)
+      |> Enum.max(fn -> 0 end)
+
+    ticks = String.duplicate("`", max(3, longest + 1))
+    "#{ticks}#{lang}\n#{String.trim_trailing(text, "\n")}\n#{ticks}\n"
+  end
+
+  defp front_matter(session) do
+    fields = [
+      title: session.title,
+      project: session.project,
+      source_type: session.source_type,
+      external_id: session.external_id,
+      started_at: session.started_at,
+      blocks: length(session.blocks)
+    ]
+
+    lines =
+      for {key, value} <- fields, not is_nil(value) do
+        "#{key}: #{yaml_value(value)}\n"
+      end
+
+    ["---\n", lines, "---\n"]
+  end
+
+  defp render_toc([]), do: []
+  defp render_toc(entries), do: [["## Contents\n\n", Enum.map(entries, &link("1.", &1))]]
+
+  defp render_bookmarks([]), do: []
+  defp render_bookmarks(entries), do: [["## Bookmarks\n\n", Enum.map(entries, &link("-", &1))]]
+
+  defp link(marker, entry) do
+    title = String.replace(entry.title, ~r/[\[\]\\]/, "\\\\\\0")
+    "#{marker} [#{title}](##{entry.anchor})\n"
+  end
+
+  defp yaml_value(value) when is_integer(value), do: Integer.to_string(value)
+  defp yaml_value(%DateTime{} = dt), do: DateTime.to_iso8601(dt)
+  defp yaml_value(value), do: Jason.encode!(to_string(value))
+
+  @doc """
+  Renders one block as it appears in a full export, without comments.
+  """
+  @spec render_block(map()) :: iodata()
+  def render_block(%{block_type: :user_prompt} = block) do
+    [heading("User", block), "\n", block.content || "", "\n"]
+  end
+
+  def render_block(%{block_type: :assistant_response} = block) do
+    [heading("Assistant", block), "\n", block.content || "", "\n"]
+  end
+
+  def render_block(%{block_type: :thinking} = block) do
+    quoted =
+      (block.content || "")
+      |> String.split("\n")
+      |> Enum.map_join("\n", &String.trim_trailing("> " <> &1))
+
+    [heading("Thinking", block), "\n", quoted, "\n"]
+  end
+
+  def render_block(%{block_type: :file_edit} = block) do
+    metadata = block.metadata || %{}
+    label = "#{metadata["name"] || "File Edit"}: `#{metadata["path"] || "unknown"}`"
+    [heading(label, block), "\n", tool_sections(metadata)]
+  end
+
+  def render_block(%{block_type: :sub_agent_spawn} = block) do
+    metadata = block.metadata || %{}
+    label = "Sub-Agent: #{metadata["agent_type"] || "unknown"}"
+    [heading(label, block), "\n", metadata["description"] || "", "\n"]
+  end
+
+  def render_block(%{block_type: :tool_call} = block) do
+    metadata = block.metadata || %{}
+    [heading("Tool: #{metadata["name"] || "Tool"}", block), "\n", tool_sections(metadata)]
+  end
+
+  def render_block(_block), do: []
+
+  defp comments_by_position(session),
+    do: session |> loaded(:comments) |> Enum.group_by(& &1.position)
+
+  defp loaded(session, key) do
+    case Map.get(session, key) do
+      list when is_list(list) -> list
+      _not_loaded -> []
+    end
+  end
+
+  defp render_comments(comments) do
+    Enum.map(comments, fn comment ->
+      quoted =
+        comment.body
+        |> String.split("\n")
+        |> Enum.map_join("\n", &String.trim_trailing("> " <> &1))
+
+      ["\n> **#{comment.author || "Anonymous"}** commented:\n>\n", quoted, "\n"]
+    end)
+  end
+
+  defp tool_sections(metadata) do
+    [
+      section("Input", metadata["input"]),
+      section("Output", metadata["output"])
+    ]
+  end
+
+  defp section(_label, nil), do: []
+
+  defp section(label, data) do
+    {text, lang} = encode(data)
+    ["**#{label}**\n\n", fence(text, lang)]
+  end
+
+  defp encode(data) when is_binary(data), do: {data, ""}
+  defp encode(data), do: {Jason.encode!(data, pretty: true), "json"}
+
+  defp heading(label, %{timestamp: %DateTime{} = ts}),
+    do: "## #{label} · #{Calendar.strftime(ts, "%H:%M:%S")}\n"
+
+  defp heading(label, _block), do: "## #{label}\n"
+
+  defp heading_text(title), do: title |> String.split("\n") |> hd()
+end
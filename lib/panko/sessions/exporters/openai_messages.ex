@@ -0,0 +1,72 @@
+defmodule Panko.Sessions.Exporters.OpenaiMessages do
+  @moduledoc """
+  Exports a session as an OpenAI Chat Completions `messages` array.
+
+  Tool invocations become `tool_calls` on the assistant message, each
+  followed by a `tool` message carrying its output. Thinking blocks are
+  omitted since the format has no equivalent.
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  alias Panko.Sessions.Exporters.Turns
+
+  @impl true
+  def format, do: :openai_messages
+
+  @impl true
+  def extension, do: ".json"
+
+  @impl true
+  def export(session, opts) do
+    session.blocks
+    |> Turns.group()
+    |> Enum.flat_map(&messages/1)
+    |> Jason.encode(pretty: Keyword.get(opts, :pretty, true))
+  end
+
+  defp messages({:user, block}), do: [%{"role" => "user", "content" => block.content || ""}]
+
+  defp messages({:assistant, texts, tools}) do
+    content =
+      case Enum.map_join(texts, "\n\n", &(&1.content || "")) do
+        "" -> nil
+        text -> text
+      end
+
+    assistant = put_tool_calls(%{"role" => "assistant", "content" => content}, tools)
+
+    [assistant | Enum.map(tools, &tool_message/1)]
+  end
+
+  defp put_tool_calls(message, []), do: message
+
+  defp put_tool_calls(message, tools) do
+    Map.put(message, "tool_calls", Enum.map(tools, &tool_call/1))
+  end
+
+  defp tool_call(block) do
+    metadata = block.metadata || %{}
+
+    %{
+      "id" => Turns.tool_id(block, "call"),
+      "type" => "function",
+      "function" => %{
+        "name" => metadata["name"] || "tool",
+        "arguments" => Jason.encode!(metadata["input"] || %{})
+      }
+    }
+  end
+
+  defp tool_message(block) do
+    %{
+      "role" => "tool",
+      "tool_call_id" => Turns.tool_id(block, "call"),
+      "content" => output_text(get_in(block.metadata, ["output"]))
+    }
+  end
+
+  defp output_text(nil), do: ""
+  defp output_text(output) when is_binary(output), do: output
+  defp output_text(output), do: Jason.encode!(output)
+end
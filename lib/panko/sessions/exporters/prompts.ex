@@ -0,0 +1,34 @@
+defmodule Panko.Sessions.Exporters.Prompts do
+  @moduledoc """
+  Exports only the user prompts of a session, numbered in order.
+
+  Useful for reusing a session's instructions without the assistant's
+  side of the conversation.
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  @impl true
+  def format, do: :prompts
+
+  @impl true
+  def extension, do: ".md"
+
+  @impl true
+  def export(session, _opts) do
+    prompts =
+      session.blocks
+      |> Enum.filter(&(&1.block_type == :user_prompt and is_binary(&1.content)))
+      |> Enum.with_index(1)
+      |> Enum.map(fn {block, i} ->
+        [heading(i, block), "\n", String.trim(block.content), "\n"]
+      end)
+
+    {:ok, Enum.intersperse(prompts, "\n")}
+  end
+
+  defp heading(i, %{timestamp: %DateTime{} = ts}),
+    do: "## #{i} · #{Calendar.strftime(ts, "%H:%M:%S")}\n"
+
+  defp heading(i, _block), do: "## #{i}\n"
+end
@@ -0,0 +1,39 @@
+defmodule Panko.Sessions.Exporters.Registry do
+  @moduledoc """
+  Finds the appropriate exporter for a given format name.
+
+  Format names are matched with dashes and underscores treated alike, so
+  `"openai-messages"` finds the `:openai_messages` exporter.
+  """
+
+  @exporters [
+    Panko.Sessions.Exporters.Html,
+    Panko.Sessions.Exporters.Json,
+    Panko.Sessions.Exporters.Markdown,
+    Panko.Sessions.Exporters.OpenaiMessages,
+    Panko.Sessions.Exporters.AnthropicMessages,
+    Panko.Sessions.Exporters.ClaudeCode,
+    Panko.Sessions.Exporters.Summary,
+    Panko.Sessions.Exporters.Prompts,
+    Panko.Sessions.Exporters.Commands,
+    Panko.Sessions.Exporters.Template
+  ]
+
+  @spec find_exporter(atom() | String.t()) :: {:ok, module()} | {:error, :unknown_format}
+  def find_exporter(format) do
+    format = format |> to_string() |> String.replace("-", "_")
+
+    case Enum.find(@exporters, &(Atom.to_string(&1.format()) == format)) do
+      nil -> {:error, :unknown_format}
+      exporter -> {:ok, exporter}
+    end
+  end
+
+  @spec exporters() :: [module()]
+  def exporters, do: @exporters
+
+  @spec formats() :: [String.t()]
+  def formats do
+    Enum.map(@exporters, &(&1.format() |> Atom.to_string() |> String.replace("_", "-")))
+  end
+end
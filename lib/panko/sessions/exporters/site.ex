@@ -0,0 +1,130 @@
+defmodule Panko.Sessions.Exporters.Site do
+  @moduledoc """
+  Renders a session archive as a static website.
+
+  Every session becomes a page under `sessions/` rendered by the HTML
+  exporter, so it matches the viewer, and `index.html` lists the sessions
+  grouped by project. All pages share `assets/app.css`, and links are
+  relative, so the directory can be served from GitHub Pages, S3 or
+  opened straight from disk.
+
+  Pages go through `Panko.Sessions.Exporters.export_with_report/3`, so
+  filter and redaction options apply to each of them.
+  """
+
+  use Phoenix.Component
+
+  alias Panko.Sessions.Exporters
+
+  @doc """
+  Writes the site for `sessions` into `dir`, returning the number of pages
+  written with the combined redaction count.
+  """
+  @spec build([Panko.Sessions.Session.t()], Path.t(), keyword()) ::
+          {:ok, %{pages: non_neg_integer(), redactions: non_neg_integer()}} | {:error, term()}
+  def build(sessions, dir, opts \\ []) do
+    opts = Keyword.merge(opts, stylesheet_href: "../assets/app.css", back_href: "../index.html")
+    pages = page_names(sessions)
+
+    with :ok <- File.mkdir_p(Path.join(dir, "sessions")),
+         :ok <- File.mkdir_p(Path.join(dir, "assets")),
+         {:ok, stylesheet} <- Exporters.Html.stylesheet(opts),
+         :ok <- File.write(Path.join([dir, "assets", "app.css"]), stylesheet),
+         {:ok, redactions} <- write_pages(pages, dir, opts),
+         :ok <- File.write(Path.join(dir, "index.html"), index(pages)) do
+      {:ok, %{pages: length(pages), redactions: redactions}}
+    end
+  end
+
+  defp write_pages(pages, dir, opts) do
+    Enum.reduce_while(pages, {:ok, 0}, fn {session, name}, {:ok, redactions} ->
+      with {:ok, document, report} <- Exporters.export_with_report(session, :html, opts),
+           :ok <- File.write(Path.join([dir, "sessions", name]), document) do
+        {:cont, {:ok, redactions + report.redactions}}
+      else
+        error -> {:halt, error}
+      end
+    end)
+  end
+
+  # Names that clash, such as the same session loaded twice or ids that
+  # only differ in replaced characters, get a numeric suffix so no page
+  # overwrites another.
+  defp page_names(sessions) do
+    {pages, _taken} =
+      Enum.map_reduce(sessions, MapSet.new(), fn session, taken ->
+        base = String.replace(session.external_id || session.id, ~r/[^A-Za-z0-9_-]/, "-")
+
+        name =
+          Stream.iterate(1, &(&1 + 1))
+          |> Stream.map(fn
+            1 -> "#{base}.html"
+            n -> "#{base}-#{n}.html"
+          end)
+          |> Enum.find(&(not MapSet.member?(taken, &1)))
+
+        {{session, name}, MapSet.put(taken, name)}
+      end)
+
+    pages
+  end
+
+  defp index(pages) do
+    projects =
+      pages
+      |> Enum.group_by(fn {session, _name} -> session.project || "Unknown Project" end)
+      |> Enum.map(fn {project, pages} ->
+        {project, Enum.sort_by(pages, &elem(&1, 0).started_at, {:desc, DateTime})}
+      end)
+      |> Enum.sort_by(fn {project, _pages} -> project end)
+
+    assigns = %{projects: projects, count: length(pages)}
+    ["<!DOCTYPE html>\n", Phoenix.HTML.Safe.to_iodata(index_document(assigns))]
+  end
+
+  defp index_document(assigns) do
+    ~H"""
+    <html lang="en">
+      <head>
+        <meta charset="utf-8" />
+        <meta name="viewport" content="width=device-width, initial-scale=1" />
+        <meta name="generator" content="Panko" />
+        <title>Sessions · Panko</title>
+        <link rel="stylesheet" href="assets/app.css" />
+      </head>
+      <body class="min-h-screen bg-base-100 font-[Inter,sans-serif]">
+        <main class="container mx-auto px-4 py-8 max-w-4xl">
+          <h1 class="text-3xl font-bold mb-1">Sessions</h1>
+          <p class="text-sm text-base-content/50 mb-6">
+            {@count} sessions across {length(@projects)} projects
+          </p>
+
+          <section :for={{project, pages} <- @projects} class="mb-8">
+            <h2 class="font-semibold text-sm font-mono mb-2">{project}</h2>
+            <ul class="border border-base-300 rounded-lg overflow-hidden">
+              <li :for={{session, name} <- pages} class="border-b border-base-300 last:border-b-0">
+                <a
+                  href={"sessions/#{name}"}
+                  class="flex items-center justify-between px-4 py-3 hover:bg-base-200/30"
+                >
+                  <span class="text-sm truncate">{session.title || "Untitled session"}</span>
+                  <span class="text-xs text-base-content/50 shrink-0 ml-4">
+                    {length(session.blocks)} blocks · {format_date(session.started_at)}
+                  </span>
+                </a>
+              </li>
+            </ul>
+          </section>
+
+          <footer class="text-center text-xs text-base-content/40 mt-12 py-4 border-t border-base-300">
+            Exported with <a href="https://github.com/jordangarrison/panko" class="link">Panko</a>
+          </footer>
+        </main>
+      </body>
+    </html>
+    """
+  end
+
+  defp format_date(nil), do: ""
+  defp format_date(dt), do: Calendar.strftime(dt, "%Y-%m-%d %H:%M")
+end
@@ -0,0 +1,112 @@
+defmodule Panko.Sessions.Exporters.Summary do
+  @moduledoc """
+  Exports a structured digest of a session computed from its blocks.
+
+  The digest covers the goal (first user prompt), files changed, shell
+  commands run, sub-agents spawned, the final assistant response and
+  token usage. It is deterministic; pass `llm_prompt: true` to wrap it
+  in instructions for an LLM to write a prose summary from.
+
+  ## Options
+
+    * `:llm_prompt` - wrap the digest in a summarization prompt (default: `false`)
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  alias Panko.Sessions.Exporters.Markdown
+  alias Panko.Sessions.Usage
+
+  @llm_instructions """
+  Summarize the following AI coding session for a teammate who was not
+  there. Explain what the goal was, what was changed and why, and whether
+  the goal was achieved. Keep it under 200 words.
+  """
+
+  @impl true
+  def format, do: :summary
+
+  @impl true
+  def extension, do: ".md"
+
+  @doc """
+  Computes the digest fields for a session.
+  """
+  @spec digest(Panko.Sessions.Session.t()) :: map()
+  def digest(session) do
+    blocks = session.blocks
+
+    %{
+      title: session.title,
+      project: session.project,
+      goal: blocks |> Enum.find(&(&1.block_type == :user_prompt)) |> content(),
+      prompts: Enum.count(blocks, &(&1.block_type == :user_prompt)),
+      files_changed: files_changed(blocks),
+      commands: commands(blocks),
+      sub_agents: sub_agents(blocks),
+      outcome: blocks |> Enum.reverse() |> Enum.find(&assistant_response?/1) |> content(),
+      usage: Usage.totals(blocks)
+    }
+  end
+
+  @impl true
+  def export(session, opts) do
+    summary = render(digest(session))
+
+    if Keyword.get(opts, :llm_prompt, false) do
+      {:ok, [@llm_instructions, "\n", Markdown.fence(IO.iodata_to_binary(summary), "markdown")]}
+    else
+      {:ok, summary}
+    end
+  end
+
+  defp render(digest) do
+    [
+      "# Summary: #{digest.title || "Untitled session"}\n\n",
+      if(digest.project, do: "**Project:** `#{digest.project}`\n\n", else: []),
+      "## Goal\n\n",
+      digest.goal || "_No user prompt._",
+      "\n\n",
+      list_section("Files changed", Enum.map(digest.files_changed, &"`#{&1}`")),
+      list_section("Commands run", Enum.map(digest.commands, &"`#{&1}`")),
+      list_section("Sub-agents", digest.sub_agents),
+      "## Outcome\n\n",
+      digest.outcome || "_No assistant response._",
+      "\n\n",
+      "## Usage\n\n",
+      "- Prompts: #{digest.prompts}\n",
+      "- Input tokens: #{digest.usage.input_tokens}\n",
+      "- Output tokens: #{digest.usage.output_tokens}\n",
+      "- Cache read tokens: #{digest.usage.cache_read_input_tokens}\n",
+      "- Cache write tokens: #{digest.usage.cache_creation_input_tokens}\n"
+    ]
+  end
+
+  defp list_section(_heading, []), do: []
+
+  defp list_section(heading, items) do
+    ["## #{heading}\n\n", Enum.map(items, &"- #{&1}\n"), "\n"]
+  end
+
+  defp files_changed(blocks) do
+    for %{block_type: :file_edit, metadata: %{"path" => path}} when is_binary(path) <- blocks,
+        uniq: true,
+        do: path
+  end
+
+  defp commands(blocks) do
+    for %{block_type: :tool_call, metadata: %{"name" => "Bash", "input" => input}} <- blocks,
+        is_binary(input["command"]),
+        do: input["command"]
+  end
+
+  defp sub_agents(blocks) do
+    for %{block_type: :sub_agent_spawn, metadata: metadata} <- blocks,
+        do: metadata["description"] || metadata["agent_type"] || "unknown"
+  end
+
+  defp assistant_response?(block), do: block.block_type == :assistant_response
+
+  defp content(nil), do: nil
+  defp content(block), do: block.content
+end
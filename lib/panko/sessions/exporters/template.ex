@@ -0,0 +1,67 @@
+defmodule Panko.Sessions.Exporters.Template do
+  @moduledoc """
+  Exports a session through a user-provided EEx template.
+
+  Lets teams match their own layouts, such as incident reports or pull
+  request descriptions. Templates are regular EEx files with these
+  assigns:
+
+    * `@session` - the session, with `title`, `project`, `started_at`, ...
+    * `@blocks` - the (filtered) blocks, each with `position`,
+      `block_type`, `content`, `metadata` and `timestamp`
+    * `@usage` - token totals, see `Panko.Sessions.Usage`
+    * `@summary` - the digest computed by `Panko.Sessions.Exporters.Summary`
+
+  For example:
+
+      # <%= @session.title %>
+
+      <%= for block <- @blocks, block.block_type == :user_prompt do %>
+      - <%= block.content %>
+      <% end %>
+
+  Templates are evaluated as Elixir code, so only use templates you trust.
+
+  ## Options
+
+    * `:template` - path to the template file (required)
+  """
+
+  @behaviour Panko.Sessions.Exporters.Exporter
+
+  alias Panko.Sessions.Exporters.Summary
+  alias Panko.Sessions.Usage
+
+  @impl true
+  def format, do: :template
+
+  @impl true
+  def extension, do: ".txt"
+
+  @impl true
+  def export(session, opts) do
+    case opts[:template] do
+      nil -> {:error, :template_required}
+      path -> render(path, session)
+    end
+  end
+
+  defp render(path, session) do
+    assigns = [
+      session: session,
+      blocks: session.blocks,
+      usage: Usage.totals(session.blocks),
+      summary: Summary.digest(session)
+    ]
+
+    case File.read(path) do
+      {:ok, template} ->
+        {:ok, EEx.eval_string(template, [assigns: assigns], file: path)}
+
+      {:error, reason} ->
+        {:error, {:template_error, "cannot read #{path}: #{:file.format_error(reason)}"}}
+    end
+  rescue
+    error -> {:error, {:template_error, Exception.message(error)}}
+  end
+end
@@ -0,0 +1,62 @@
+defmodule Panko.Sessions.Exporters.Turns do
+  @moduledoc """
+  Groups blocks into conversational turns for chat-API style exports.
+
+  A turn is either a user prompt or an assistant turn made of text blocks
+  followed by the tool invocations they triggered. A new assistant turn
+  starts whenever text follows a tool invocation, mirroring the
+  request/response cycle of the original API calls.
+  """
+
+  @tool_types [:tool_call, :file_edit, :sub_agent_spawn]
+
+  @type turn ::
+          {:user, map()}
+          | {:assistant, texts :: [map()], tools :: [map()]}
+
+  @doc """
+  Splits blocks into user and assistant turns. Thinking blocks are dropped.
+  """
+  @spec group([map()]) :: [turn()]
+  def group(blocks) do
+    blocks
+    |> Enum.reject(&(&1.block_type == :thinking))
+    |> Enum.reduce([], &add_block/2)
+    |> Enum.reverse()
+    |> Enum.map(fn
+      {:assistant, texts, tools} -> {:assistant, Enum.reverse(texts), Enum.reverse(tools)}
+      turn -> turn
+    end)
+  end
+
+  @doc """
+  Returns the tool call id for a tool block, falling back to one derived
+  from its position for sessions imported before ids were recorded.
+  """
+  @spec tool_id(map(), String.t()) :: String.t()
+  def tool_id(block, prefix) do
+    get_in(block.metadata, ["id"]) || "#{prefix}_#{block.position}"
+  end
+
+  @doc """
+  Returns whether the block is a tool invocation.
+  """
+  @spec tool?(map()) :: boolean()
+  def tool?(block), do: block.block_type in @tool_types
+
+  defp add_block(%{block_type: :user_prompt} = block, turns), do: [{:user, block} | turns]
+
+  defp add_block(block, [{:assistant, texts, tools} | rest] = turns) do
+    cond do
+      tool?(block) -> [{:assistant, texts, [block | tools]} | rest]
+      tools == [] -> [{:assistant, [block | texts], tools} | rest]
+      true -> [{:assistant, [block], []} | turns]
+    end
+  end
+
+  defp add_block(block, turns) do
+    if tool?(block),
+      do: [{:assistant, [], [block]} | turns],
+      else: [{:assistant, [block], []} | turns]
+  end
+end
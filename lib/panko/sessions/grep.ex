@@ -0,0 +1,59 @@
+defmodule Panko.Sessions.Grep do
+  @moduledoc """
+  Regex search over the text of a session's blocks, with context lines.
+
+  Block content and tool output are searched line by line. Each hit
+  references its block position and the line within that block, so it can
+  be found again in the viewer.
+  """
+
+  @type hit :: %{
+          position: non_neg_integer(),
+          block_type: atom(),
+          line: pos_integer(),
+          text: String.t(),
+          before: [String.t()],
+          after: [String.t()]
+        }
+
+  @doc """
+  Returns the lines of `session`'s blocks matching `regex`.
+
+  ## Options
+
+    * `:before` - number of context lines before each match (default: 0)
+    * `:after` - number of context lines after each match (default: 0)
+  """
+  @spec grep(Panko.Sessions.Session.t(), Regex.t(), keyword()) :: [hit()]
+  def grep(session, regex, opts \\ []) do
+    before = Keyword.get(opts, :before, 0)
+    after_count = Keyword.get(opts, :after, 0)
+
+    Enum.flat_map(session.blocks, fn block ->
+      lines = block |> text() |> String.split("\n")
+
+      lines
+      |> Enum.with_index()
+      |> Enum.filter(fn {line, _index} -> Regex.match?(regex, line) end)
+      |> Enum.map(fn {line, index} ->
+        from = max(index - before, 0)
+
+        %{
+          position: block.position,
+          block_type: block.block_type,
+          line: index + 1,
+          text: line,
+          before: Enum.slice(lines, from, index - from),
+          after: Enum.slice(lines, index + 1, after_count)
+        }
+      end)
+    end)
+  end
+
+  # Tool calls carry their text in the tool output rather than the content.
+  defp text(block) do
+    [block.content, block.metadata && block.metadata["output"]]
+    |> Enum.filter(&is_binary/1)
+    |> Enum.join("\n")
+  end
+end
@@ -0,0 +1,106 @@
+defmodule Panko.Sessions.Markdown do
+  @moduledoc """
+  Renders message markdown to sanitized HTML, for the web viewer and the
+  HTML export.
+  """
+
+  @doc """
+  Renders `text` to HTML with Earmark.
+
+  Code fences are balanced first, see `balance_fences/1`, so a fence quoted
+  inside another, or one left open, cannot turn the rest of the message
+  inside out. Whatever the markdown contains, the HTML is sanitized. Text
+  Earmark cannot parse is escaped into a single paragraph.
+  """
+  @spec to_html(String.t()) :: String.t()
+  def to_html(text) when is_binary(text) do
+    case text |> balance_fences() |> Earmark.as_html(compact_output: true) do
+      {:ok, html, _warnings} -> HtmlSanitizeEx.markdown_html(html)
+      {:error, _html, _errors} -> "<p>#{escape(text)}</p>"
+    end
+  end
+
+  @doc ~S"""
+  Rewrites code fences the way a reader would pair them.
+
+  Earmark closes a fence at the next one with the same delimiter, so a
+  message quoting markdown (a fence inside a fence) ends its code block
+  half way. A fence with an info string inside an open fence is taken as
+  nested instead, and the outer fence is lengthened past every fence it
+  contains. A fence still open at the end of the text is closed there.
+
+  ## Examples
+
+      iex> balance_fences("```md\n```sh\nls\n```\n```")
+      "````md\n```sh\nls\n```\n````"
+
+      iex> balance_fences("```sh\nls")
+      "```sh\nls\n```"
+  """
+  @spec balance_fences(String.t()) :: String.t()
+  def balance_fences(text) do
+    {lines, open} =
+      text
+      |> String.split("\n")
+      |> Enum.reduce({[], nil}, &fence_line/2)
+
+    lines = if open, do: close_fence(open, lines), else: lines
+    lines |> Enum.reverse() |> Enum.join("\n")
+  end
+
+  # Lines are collected in reverse; `open` is the fence being read, if any.
+  defp fence_line(line, {lines, nil}) do
+    case fence(line) do
+      {indent, delimiter, info} ->
+        open = %{indent: indent, delimiter: delimiter, info: info, body: [], depth: 0, inner: 0}
+        {lines, open}
+
+      nil ->
+        {[line | lines], nil}
+    end
+  end
+
+  defp fence_line(line, {lines, open}) do
+    char = String.first(open.delimiter)
+
+    case fence(line) do
+      {_indent, delimiter, info} when binary_part(delimiter, 0, 1) == char ->
+        length = String.length(delimiter)
+
+        # Only nested fences count toward the outer fence's length, not the
+        # fence that closes it.
+        cond do
+          info != "" -> {lines, nest(open, line, length, 1)}
+          open.depth > 0 -> {lines, nest(open, line, length, -1)}
+          length >= String.length(open.delimiter) -> {close_fence(open, lines), nil}
+          true -> {lines, %{open | body: [line | open.body]}}
+        end
+
+      _ ->
+        {lines, %{open | body: [line | open.body]}}
+    end
+  end
+
+  defp nest(open, line, length, step) do
+    %{open | body: [line | open.body], depth: open.depth + step, inner: max(open.inner, length)}
+  end
+
+  defp close_fence(open, lines) do
+    char = String.first(open.delimiter)
+    length = max(String.length(open.delimiter), open.inner + 1)
+    delimiter = open.indent <> String.duplicate(char, length)
+
+    [delimiter | open.body] ++ [delimiter <> open.info | lines]
+  end
+
+  # Same rule as Earmark's: three or more backticks or tildes, optionally
+  # followed by a single-word info string.
+  defp fence(line) do
+    case Regex.run(~r/\A(\s*)(`{3,}|~{3,})\s*([^`\s]*)\s*\z/u, line) do
+      [_line, indent, delimiter, info] -> {indent, delimiter, info}
+      nil -> nil
+    end
+  end
+
+  defp escape(text), do: text |> Phoenix.HTML.html_escape() |> Phoenix.HTML.safe_to_string()
+end
@@ -0,0 +1,81 @@
+defmodule Panko.Sessions.ParseCache do
+  @moduledoc """
+  Keeps recently parsed session files in memory.
+
+  Exporting, sharing and re-importing the same file each parsed it from
+  scratch, which is slow for long sessions. Entries are keyed by path and
+  checked against the file's modification time and size, so a file that
+  changed is parsed again. The least recently used entry is dropped once
+  the cache holds `:parse_cache_size` files (default 16).
+
+  The table is created on application start. When the application is not
+  running, as in Mix tasks that only load the config, files are parsed
+  directly.
+  """
+
+  alias Panko.Sessions.Parsers
+
+  @table __MODULE__
+
+  @doc """
+  Creates the cache table. Called once on application start.
+  """
+  def setup do
+    :ets.new(@table, [:set, :public, :named_table, read_concurrency: true])
+    :ok
+  end
+
+  @doc """
+  Parses `path` with the parser registered for it, reusing the previous
+  result while the file is unchanged.
+  """
+  @spec parse(String.t()) :: {:ok, Parsers.Parser.session_attrs()} | {:error, term()}
+  def parse(path) do
+    with {:ok, parser} <- Parsers.Registry.find_parser(path) do
+      case {:ets.whereis(@table), stamp(path)} do
+        {:undefined, _} -> Parsers.Registry.parse(parser, path)
+        {_, nil} -> Parsers.Registry.parse(parser, path)
+        {_, stamp} -> cached(path, stamp, parser)
+      end
+    end
+  end
+
+  defp cached(path, stamp, parser) do
+    case :ets.lookup(@table, path) do
+      [{^path, ^stamp, attrs, _used}] ->
+        :ets.update_element(@table, path, {4, now()})
+        {:ok, attrs}
+
+      _ ->
+        with {:ok, attrs} <- Parsers.Registry.parse(parser, path) do
+          :ets.insert(@table, {path, stamp, attrs, now()})
+          evict()
+          {:ok, attrs}
+        end
+    end
+  end
+
+  defp evict do
+    if :ets.info(@table, :size) > Application.get_env(:panko, :parse_cache_size, 16) do
+      {path, _used} =
+        :ets.foldl(
+          fn {path, _stamp, _attrs, used}, oldest ->
+            if oldest == nil or used < elem(oldest, 1), do: {path, used}, else: oldest
+          end,
+          nil,
+          @table
+        )
+
+      :ets.delete(@table, path)
+    end
+  end
+
+  defp stamp(path) do
+    case File.stat(path, time: :posix) do
+      {:ok, %{mtime: mtime, size: size}} -> {mtime, size}
+      {:error, _} -> nil
+    end
+  end
+
+  defp now, do: System.unique_integer([:monotonic])
+end
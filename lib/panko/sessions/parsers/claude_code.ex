@@ -8,81 +8,171 @@ defmodule Panko.Sessions.Parsers.ClaudeCode do
 
   @behaviour Panko.Sessions.Parsers.Parser
 
+  alias Panko.Sessions.Parsers.FastJson
+  alias Panko.Sessions.Preview
+
   @impl true
   def source_type, do: :claude_code
 
   @impl true
   def can_parse?(path), do: String.ends_with?(path, ".jsonl")
 
+  # Every conversation line carries the session id; a leading summary line
+  # carries the uuid of the message it summarizes instead.
+  @impl true
+  def detect?(head) do
+    head
+    |> String.split("\n", trim: true)
+    |> Enum.any?(fn line ->
+      case FastJson.decode(line) do
+        {:ok, %{"sessionId" => id, "type" => type}} when is_binary(id) and is_binary(type) -> true
+        {:ok, %{"type" => "summary", "leafUuid" => uuid}} when is_binary(uuid) -> true
+        _ -> false
+      end
+    end)
+  end
+
+  # Streamed so a large file is never held in memory alongside its lines.
   @impl true
   def parse(path) do
-    case File.read(path) do
-      {:ok, content} ->
-        lines =
-          content
-          |> String.split("\n", trim: true)
-          |> Enum.flat_map(fn line ->
-            case Jason.decode(line) do
-              {:ok, parsed} -> [parsed]
-              {:error, _} -> []
-            end
-          end)
-
-        session_id = extract_session_id(lines)
-        project = extract_project(lines)
-        started_at = extract_started_at(lines)
-        title = extract_title(lines)
-
-        {blocks, sub_agents} = extract_blocks_and_agents(lines)
-
-        {:ok,
-         %{
-           external_id: session_id,
-           source_type: :claude_code,
-           source_path: path,
-           project: project,
-           title: title,
-           started_at: started_at,
-           blocks: blocks,
-           sub_agents: sub_agents
-         }}
-
-      {:error, reason} ->
-        {:error, {:file_read_error, reason}}
+    {:ok, attrs} = path |> File.stream!(read_ahead: 1_048_576) |> parse_lines()
+    {:ok, %{attrs | source_path: path}}
+  rescue
+    error in File.Error -> {:error, {:file_read_error, error.reason}}
+  end
+
+  @doc """
+  Parses the lines of a Claude Code session, given as any enumerable of
+  strings with or without their line endings.
+
+  Never fails on bad input: lines that are not JSON objects are skipped,
+  and fields of an unexpected shape are ignored. A line with junk before
+  its JSON, as left by an interrupted write, is read from its first `{`.
+  The returned attributes have no `source_path`.
+  """
+  @spec parse_lines(Enumerable.t()) :: {:ok, map()}
+  def parse_lines(raw_lines) do
+    raw_lines
+    |> Enum.flat_map(&decode_line/1)
+    |> parse_objects()
+  end
+
+  @doc """
+  Like `parse_lines/1`, for lines already decoded with `decode_line/1`,
+  so a caller following a file only decodes the lines appended to it.
+  """
+  @spec parse_objects([map()]) :: {:ok, map()}
+  def parse_objects(lines) do
+    {blocks, sub_agents} = extract_blocks_and_agents(lines)
+
+    {:ok,
+     %{
+       external_id: extract_session_id(lines),
+       source_type: :claude_code,
+       source_path: nil,
+       project: extract_project(lines),
+       title: extract_title(lines),
+       started_at: extract_started_at(lines),
+       blocks: blocks,
+       sub_agents: sub_agents
+     }}
+  end
+
+  @doc """
+  Decodes one line the way `parse_lines/1` does, returning the JSON object
+  in a list, or an empty list when the line is skipped.
+  """
+  @spec decode_line(String.t()) :: [map()]
+  def decode_line(line) do
+    case decode_object(line) do
+      {:ok, parsed} -> [parsed]
+      :error -> recover_line(line)
+    end
+  end
+
+  @doc """
+  Returns the tool results in decoded lines by the id of their tool call,
+  so a caller parsing a file piecewise can match results to calls parsed
+  earlier.
+  """
+  @spec tool_results([map()]) :: %{String.t() => map()}
+  def tool_results(lines) do
+    for %{"type" => "user", "message" => %{"content" => parts}} = line <- lines,
+        is_list(parts),
+        %{"type" => "tool_result", "tool_use_id" => id} = part <- parts,
+        into: %{} do
+      {id,
+       %{
+         text: tool_result_text(part["content"]),
+         is_error: part["is_error"] == true,
+         at: precise_timestamp(line["timestamp"])
+       }}
+    end
+  end
+
+  defp recover_line(line) do
+    with {start, _} when start > 0 <- :binary.match(line, "{"),
+         {:ok, parsed} <- decode_object(binary_part(line, start, byte_size(line) - start)) do
+      [parsed]
+    else
+      _ -> []
+    end
+  end
+
+  defp decode_object(line) do
+    case FastJson.decode(line) do
+      {:ok, parsed} when is_map(parsed) -> {:ok, parsed}
+      _ -> :error
     end
   end
 
   defp extract_session_id(lines) do
     lines
-    |> Enum.find_value(fn line -> line["sessionId"] end)
+    |> Enum.find_value(fn line -> string(line["sessionId"]) end)
     |> Kernel.||("unknown")
   end
 
   defp extract_project(lines) do
-    Enum.find_value(lines, fn line -> line["cwd"] end)
+    Enum.find_value(lines, fn line -> string(line["cwd"]) end)
   end
 
   defp extract_started_at(lines) do
     lines
-    |> Enum.find_value(fn line -> line["timestamp"] end)
-    |> parse_timestamp()
+    |> Enum.find_value(fn line -> parse_timestamp(line["timestamp"]) end)
     |> Kernel.||(DateTime.utc_now())
   end
 
   defp extract_title(lines) do
-    case Enum.find(lines, fn line ->
-           line["type"] == "user" && is_binary(get_in(line, ["message", "content"]))
-         end) do
-      nil ->
-        nil
-
-      line ->
-        line
-        |> get_in(["message", "content"])
-        |> String.slice(0, 200)
+    extract_summary(lines) || extract_first_prompt(lines)
+  end
+
+  # Claude Code appends summary records naming a conversation. A file may
+  # also carry summaries of earlier sessions it continues, so only those
+  # pointing at a message of this file count; the latest one wins.
+  defp extract_summary(lines) do
+    uuids = for %{"uuid" => uuid} <- lines, into: MapSet.new(), do: uuid
+
+    lines
+    |> Enum.filter(fn line ->
+      line["type"] == "summary" and is_binary(line["summary"]) and
+        MapSet.member?(uuids, line["leafUuid"])
+    end)
+    |> List.last()
+    |> case do
+      nil -> nil
+      line -> line["summary"] |> String.trim() |> String.slice(0, 200)
     end
   end
 
+  # Meta messages are written by Claude Code itself, not typed.
+  defp extract_first_prompt(lines) do
+    Enum.find_value(lines, fn
+      %{"type" => "user", "isMeta" => true} -> nil
+      %{"type" => "user", "message" => %{"content" => content}} -> Preview.from_prompt(content)
+      _ -> nil
+    end)
+  end
+
   defp extract_blocks_and_agents(lines) do
     {blocks_rev, agents_rev, _pos} =
       lines
@@ -92,10 +182,62 @@ defmodule Panko.Sessions.Parsers.ClaudeCode do
         {Enum.reverse(new_blocks) ++ blocks, Enum.reverse(new_agents) ++ agents, new_pos}
       end)
 
-    {Enum.reverse(blocks_rev), Enum.reverse(agents_rev)}
+    # Tool results arrive in later user messages; they are folded into the
+    # metadata of the originating block.
+    results = tool_results(lines)
+    started = extract_tool_use_times(lines)
+    blocks = blocks_rev |> Enum.reverse() |> Enum.map(&attach_tool_result(&1, results, started))
+
+    {blocks, Enum.reverse(agents_rev)}
+  end
+
+  # Block timestamps are truncated to seconds, so tool durations are
+  # measured between the untruncated timestamps of the lines.
+  defp extract_tool_use_times(lines) do
+    for %{"type" => "assistant", "message" => %{"content" => parts}} = line <- lines,
+        is_list(parts),
+        %{"type" => "tool_use", "id" => id} <- parts,
+        into: %{} do
+      {id, precise_timestamp(line["timestamp"])}
+    end
+  end
+
+  defp tool_result_text(content) when is_binary(content), do: content
+
+  defp tool_result_text(parts) when is_list(parts) do
+    parts
+    |> Enum.filter(&(is_map(&1) and &1["type"] == "text" and is_binary(&1["text"])))
+    |> Enum.map_join("\n", & &1["text"])
+  end
+
+  defp tool_result_text(_content), do: nil
+
+  defp attach_tool_result(%{metadata: %{"id" => id} = metadata} = block, results, started)
+       when is_map_key(results, id) do
+    %{text: text, is_error: is_error, at: finished} = Map.fetch!(results, id)
+
+    metadata =
+      metadata
+      |> Map.put("output", text)
+      |> then(&if(is_error, do: Map.put(&1, "is_error", true), else: &1))
+      |> put_duration(started[id], finished)
+
+    %{block | metadata: metadata}
+  end
+
+  defp attach_tool_result(block, _results, _started), do: block
+
+  defp put_duration(metadata, %DateTime{} = started, %DateTime{} = finished) do
+    case DateTime.diff(finished, started, :millisecond) do
+      ms when ms >= 0 -> Map.put(metadata, "duration_ms", ms)
+      _negative -> metadata
+    end
   end
 
-  defp process_line(%{"type" => "user", "message" => message} = line, pos) do
+  defp put_duration(metadata, _started, _finished), do: metadata
+
+  defp process_line(%{"type" => "user", "message" => message} = line, pos)
+       when is_map(message) do
     content = message["content"]
     timestamp = parse_timestamp(line["timestamp"])
 
@@ -120,18 +262,19 @@ defmodule Panko.Sessions.Parsers.ClaudeCode do
     end
   end
 
-  defp process_line(%{"type" => "assistant", "message" => message} = line, pos) do
-    content_parts = message["content"] || []
+  defp process_line(%{"type" => "assistant", "message" => message} = line, pos)
+       when is_map(message) do
+    content_parts = if is_list(message["content"]), do: message["content"], else: []
     timestamp = parse_timestamp(line["timestamp"])
 
     {blocks_rev, agents_rev, next_pos} =
       Enum.reduce(content_parts, {[], [], pos}, fn part, {blks, agts, p} ->
-        case part["type"] do
+        case is_map(part) && part["type"] do
           "text" ->
             block = %{
               position: p,
               block_type: :assistant_response,
-              content: part["text"],
+              content: string(part["text"]),
               metadata: nil,
               timestamp: timestamp
             }
@@ -146,7 +289,7 @@ defmodule Panko.Sessions.Parsers.ClaudeCode do
             block = %{
               position: p,
               block_type: :thinking,
-              content: part["thinking"],
+              content: string(part["thinking"]),
               metadata: nil,
               timestamp: timestamp
             }
@@ -158,14 +301,32 @@ defmodule Panko.Sessions.Parsers.ClaudeCode do
         end
       end)
 
-    {Enum.reverse(blocks_rev), Enum.reverse(agents_rev), next_pos}
+    blocks = blocks_rev |> Enum.reverse() |> attach_usage(message)
+
+    {blocks, Enum.reverse(agents_rev), next_pos}
   end
 
   defp process_line(_line, pos), do: {[], [], pos}
 
+  # Usage is reported per API message, and Claude Code may repeat the same
+  # message across several lines, so it is attached once to the first block
+  # together with the message id for de-duplication and the model for pricing.
+  defp attach_usage([first | rest], %{"usage" => usage} = message) when is_map(usage) do
+    metadata =
+      Map.merge(first.metadata || %{}, %{
+        "usage" => usage,
+        "message_id" => message["id"],
+        "model" => message["model"]
+      })
+
+    [%{first | metadata: metadata} | rest]
+  end
+
+  defp attach_usage(blocks, _message), do: blocks
+
   defp process_tool_use(part, pos, timestamp) do
     tool_name = part["name"]
-    input = part["input"]
+    input = if is_map(part["input"]), do: part["input"], else: %{}
     tool_id = part["id"]
 
     {block_type, metadata} = categorize_tool(tool_name, input)
@@ -174,7 +335,7 @@ defmodule Panko.Sessions.Parsers.ClaudeCode do
       position: pos,
       block_type: block_type,
       content: nil,
-      metadata: metadata,
+      metadata: Map.put(metadata, "id", tool_id),
       timestamp: timestamp
     }
 
@@ -182,10 +343,10 @@ defmodule Panko.Sessions.Parsers.ClaudeCode do
       if block_type == :sub_agent_spawn do
         [
           %{
-            external_id: tool_id || "unknown",
-            agent_type: input["subagent_type"] || input["type"] || "unknown",
-            description: input["description"] || "",
-            prompt: input["prompt"] || "",
+            external_id: string(tool_id) || "unknown",
+            agent_type: string(input["subagent_type"] || input["type"]) || "unknown",
+            description: string(input["description"]) || "",
+            prompt: string(input["prompt"]) || "",
             status: :running,
             spawned_at: timestamp
           }
@@ -227,4 +388,18 @@ defmodule Panko.Sessions.Parsers.ClaudeCode do
       _ -> nil
     end
   end
+
+  defp parse_timestamp(_ts), do: nil
+
+  defp precise_timestamp(ts) when is_binary(ts) do
+    case DateTime.from_iso8601(ts) do
+      {:ok, dt, _offset} -> dt
+      _ -> nil
+    end
+  end
+
+  defp precise_timestamp(_ts), do: nil
+
+  defp string(value) when is_binary(value), do: value
+  defp string(_value), do: nil
 end
@@ -0,0 +1,39 @@
+defmodule Panko.Sessions.Parsers.FastJson do
+  @moduledoc """
+  JSON decoding for the line-by-line hot path of session parsing.
+
+  On OTP 27 and later this uses the built-in `:json` decoder, which is
+  several times faster than Jason on session lines. Setting
+  `PANKO_FAST_JSON=false`, or running on an older OTP, falls back to
+  Jason. Both decode to the same terms.
+  """
+
+  if Code.ensure_loaded?(:json) do
+    @doc """
+    Whether the fast decoder is available and enabled.
+    """
+    @spec enabled?() :: boolean()
+    def enabled?, do: Application.get_env(:panko, :fast_json, true)
+
+    @doc """
+    Decodes one JSON document, like `Jason.decode/1`.
+    """
+    @spec decode(binary()) :: {:ok, term()} | {:error, term()}
+    def decode(data) do
+      if enabled?(), do: decode_fast(data), else: Jason.decode(data)
+    end
+
+    # :json raises on invalid input and decodes null to the atom null unless
+    # told otherwise; trailing non-whitespace is an error, as in Jason.
+    defp decode_fast(data) do
+      {value, :ok, rest} = :json.decode(data, :ok, %{null: nil})
+
+      if String.trim(rest) == "", do: {:ok, value}, else: {:error, :trailing_data}
+    rescue
+      error in [ErlangError, ArgumentError] -> {:error, error}
+    end
+  else
+    def enabled?, do: false
+    def decode(data), do: Jason.decode(data)
+  end
+end
@@ -0,0 +1,175 @@
+defmodule Panko.Sessions.Parsers.PankoJson do
+  @moduledoc """
+  Parser for Panko's own JSON exports.
+
+  Reads documents written by `Panko.Sessions.Exporters.Json` back into
+  session attributes, so archived exports can be viewed and shared again
+  after the original agent files are gone. The session keeps the source
+  type recorded in the export.
+
+  The export only carries normalized tool data and session-wide token
+  totals, so agent-specific block metadata is rebuilt from the tool input
+  and the totals are attached to the first assistant block.
+  """
+
+  @behaviour Panko.Sessions.Parsers.Parser
+
+  alias Panko.Sessions.Exporters
+
+  @impl true
+  def source_type, do: :claude_code
+
+  @impl true
+  def can_parse?(path), do: String.ends_with?(path, ".json")
+
+  # The head may end mid-document, so look for the schema key rather than
+  # decoding it.
+  @impl true
+  def detect?(head) do
+    Regex.match?(~r/"schema"\s*:\s*"#{Regex.escape(Exporters.Json.schema())}"/, head)
+  end
+
+  @impl true
+  def parse(path) do
+    with {:ok, content} <- read(path),
+         {:ok, document} <- decode(content) do
+      from_document(document, path)
+    end
+  end
+
+  defp read(path) do
+    case File.read(path) do
+      {:ok, content} -> {:ok, content}
+      {:error, reason} -> {:error, {:file_read_error, reason}}
+    end
+  end
+
+  defp decode(content) do
+    case Jason.decode(content) do
+      {:ok, document} -> {:ok, document}
+      {:error, _} -> {:error, :invalid_json}
+    end
+  end
+
+  defp from_document(%{"schema" => schema, "schema_version" => version} = document, path) do
+    cond do
+      schema != Exporters.Json.schema() ->
+        {:error, :not_a_panko_export}
+
+      version > Exporters.Json.schema_version() ->
+        {:error, {:unsupported_schema_version, version}}
+
+      true ->
+        session = document["session"] || %{}
+        started_at = parse_timestamp(session["started_at"]) || DateTime.utc_now()
+
+        {:ok,
+         %{
+           external_id: session["external_id"] || "unknown",
+           source_type: source_type(session["source_type"]),
+           source_path: path,
+           project: session["project"],
+           title: session["title"],
+           started_at: started_at,
+           blocks:
+             (document["blocks"] || [])
+             |> Enum.flat_map(&parse_block/1)
+             |> attach_usage(document["usage"]),
+           sub_agents: Enum.map(document["sub_agents"] || [], &parse_sub_agent(&1, started_at))
+         }}
+    end
+  end
+
+  defp from_document(_document, _path), do: {:error, :not_a_panko_export}
+
+  defp parse_block(%{"type" => type} = block) do
+    case enum_value(Panko.Sessions.Block.Type.values(), type) do
+      nil ->
+        []
+
+      block_type ->
+        [
+          %{
+            position: block["position"],
+            block_type: block_type,
+            content: block["content"],
+            metadata: metadata(block_type, block["tool"]),
+            timestamp: parse_timestamp(block["timestamp"])
+          }
+        ]
+    end
+  end
+
+  defp parse_block(_block), do: []
+
+  defp metadata(_block_type, nil), do: nil
+
+  defp metadata(block_type, tool) do
+    input = tool["input"]
+
+    %{"name" => tool["name"], "input" => input}
+    |> put_present("output", tool["output"])
+    |> Map.merge(tool_details(block_type, input))
+  end
+
+  defp tool_details(:file_edit, %{"file_path" => path}), do: %{"path" => path}
+
+  defp tool_details(:sub_agent_spawn, input) when is_map(input) do
+    %{
+      "agent_type" => input["subagent_type"] || input["type"],
+      "description" => input["description"]
+    }
+  end
+
+  defp tool_details(_block_type, _input), do: %{}
+
+  defp attach_usage(blocks, usage) when is_map(usage) and map_size(usage) > 0 do
+    case Enum.find_index(blocks, &(&1.block_type == :assistant_response)) do
+      nil ->
+        blocks
+
+      index ->
+        List.update_at(blocks, index, fn block ->
+          metadata = Map.merge(block.metadata || %{}, %{"usage" => usage})
+          %{block | metadata: metadata}
+        end)
+    end
+  end
+
+  defp attach_usage(blocks, _usage), do: blocks
+
+  defp parse_sub_agent(agent, started_at) do
+    %{
+      external_id: agent["external_id"] || "unknown",
+      agent_type: agent["agent_type"] || "unknown",
+      description: agent["description"] || "",
+      prompt: agent["prompt"] || "",
+      status: enum_value(Panko.Sessions.SubAgentStatus.values(), agent["status"]) || :running,
+      result: agent["result"],
+      spawned_at: parse_timestamp(agent["spawned_at"]) || started_at,
+      completed_at: parse_timestamp(agent["completed_at"])
+    }
+  end
+
+  defp source_type(value) do
+    enum_value(Panko.Sessions.SourceType.values(), value) || source_type()
+  end
+
+  defp enum_value(values, value) do
+    Enum.find(values, &(Atom.to_string(&1) == value))
+  end
+
+  defp put_present(map, _key, nil), do: map
+  defp put_present(map, key, value), do: Map.put(map, key, value)
+
+  defp parse_timestamp(nil), do: nil
+
+  defp parse_timestamp(ts) when is_binary(ts) do
+    case DateTime.from_iso8601(ts) do
+      {:ok, dt, _offset} -> DateTime.truncate(dt, :second)
+      _ -> nil
+    end
+  end
+
+  defp parse_timestamp(_), do: nil
+end
@@ -20,5 +20,12 @@ defmodule Panko.Sessions.Parsers.Parser do
 
   @callback source_type() :: atom()
   @callback can_parse?(path :: String.t()) :: boolean()
+
+  @doc """
+  Returns whether a file starting with `head` is in this parser's format,
+  judged by keys only that format writes. Used before `can_parse?/1`, so
+  files renamed or copied out of their agent's directory are recognized.
+  """
+  @callback detect?(head :: binary()) :: boolean()
   @callback parse(path :: String.t()) :: {:ok, session_attrs()} | {:error, term()}
 end
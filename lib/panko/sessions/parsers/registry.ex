@@ -1,20 +1,54 @@
 defmodule Panko.Sessions.Parsers.Registry do
   @moduledoc """
   Finds the appropriate parser for a given file path.
+
+  The start of the file is checked against each parser's `detect?/1`
+  first, so a session is recognized whatever it is named; the file
+  extension decides when no format is detected.
   """
 
   @parsers [
-    Panko.Sessions.Parsers.ClaudeCode
+    Panko.Sessions.Parsers.ClaudeCode,
+    Panko.Sessions.Parsers.PankoJson
   ]
 
+  @head_bytes 65_536
+
   @spec find_parser(String.t()) :: {:ok, module()} | {:error, :no_parser_found}
   def find_parser(path) do
-    case Enum.find(@parsers, & &1.can_parse?(path)) do
+    detected =
+      case read_head(path) do
+        nil -> nil
+        head -> Enum.find(@parsers, & &1.detect?(head))
+      end
+
+    case detected || Enum.find(@parsers, & &1.can_parse?(path)) do
       nil -> {:error, :no_parser_found}
       parser -> {:ok, parser}
     end
   end
 
+  @doc """
+  Parses `path` with `parser` inside a `[:panko, :sessions, :parse]`
+  telemetry span, whose metadata carries the path and parser.
+  """
+  @spec parse(module(), String.t()) ::
+          {:ok, Panko.Sessions.Parsers.Parser.session_attrs()} | {:error, term()}
+  def parse(parser, path) do
+    metadata = %{path: path, parser: parser}
+
+    :telemetry.span([:panko, :sessions, :parse], metadata, fn ->
+      {parser.parse(path), metadata}
+    end)
+  end
+
+  defp read_head(path) do
+    case File.open(path, [:read, :binary], &IO.binread(&1, @head_bytes)) do
+      {:ok, head} when is_binary(head) -> head
+      _ -> nil
+    end
+  end
+
   @spec parsers() :: [module()]
   def parsers, do: @parsers
 end
@@ -0,0 +1,267 @@
+defmodule Panko.Sessions.Parsers.Transcript do
+  @moduledoc """
+  Parser for transcripts exported from other tools.
+
+  Understands two shapes of JSON:
+
+    * ChatGPT data exports (`conversations.json`), a list of conversations
+      whose messages form a tree; the branch ending at the conversation's
+      current node is imported
+    * generic message arrays as used by the OpenAI and Anthropic APIs,
+      either a bare list or an object with a `messages` key
+
+  Panko's own JSON exports are handed to `Panko.Sessions.Parsers.PankoJson`.
+  The file may hold several conversations, so `parse_all/1` returns one
+  session per conversation and `parse/1` the first.
+
+  Transcripts carry no project, so sessions have none until the importer
+  assigns one. External ids are derived from the conversation id or the
+  content, so importing the same file again updates the session.
+
+  Every session from one file has that file as its `source_path`, so
+  `Panko.Sessions.Prune` only removes the file with the last of them.
+  """
+
+  @behaviour Panko.Sessions.Parsers.Parser
+
+  alias Panko.Sessions.Parsers.PankoJson
+  alias Panko.Sessions.Preview
+
+  @impl true
+  def source_type, do: :imported
+
+  @impl true
+  def can_parse?(path), do: String.ends_with?(path, ".json")
+
+  @impl true
+  def detect?(head) do
+    Regex.match?(~r/"mapping"\s*:\s*\{/, head) or
+      Regex.match?(~r/\A\s*(\[\s*\{\s*"role"|\{\s*"messages"\s*:)/, head)
+  end
+
+  @impl true
+  def parse(path) do
+    with {:ok, [attrs | _]} <- parse_all(path), do: {:ok, attrs}
+  end
+
+  @doc """
+  Parses every conversation in the file.
+  """
+  @spec parse_all(String.t()) :: {:ok, [map()]} | {:error, term()}
+  def parse_all(path) do
+    with {:ok, content} <- read(path),
+         {:ok, document} <- decode(content) do
+      case document do
+        %{"schema" => _} ->
+          with {:ok, attrs} <- PankoJson.parse(path), do: {:ok, [attrs]}
+
+        [%{"mapping" => _} | _] = conversations ->
+          {:ok, Enum.map(conversations, &from_chatgpt(&1, path))}
+
+        %{"mapping" => _} = conversation ->
+          {:ok, [from_chatgpt(conversation, path)]}
+
+        %{"messages" => messages} when is_list(messages) ->
+          {:ok, [from_messages(messages, document, content, path)]}
+
+        [%{"role" => _} | _] = messages ->
+          {:ok, [from_messages(messages, %{}, content, path)]}
+
+        _ ->
+          {:error, :unknown_transcript_format}
+      end
+    end
+  end
+
+  defp read(path) do
+    case File.read(path) do
+      {:ok, content} -> {:ok, content}
+      {:error, reason} -> {:error, {:file_read_error, reason}}
+    end
+  end
+
+  defp decode(content) do
+    case Jason.decode(content) do
+      {:ok, document} -> {:ok, document}
+      {:error, _} -> {:error, :invalid_json}
+    end
+  end
+
+  ## ChatGPT exports
+
+  defp from_chatgpt(conversation, path) do
+    messages =
+      conversation["mapping"]
+      |> branch(conversation["current_node"])
+      |> Enum.flat_map(&chatgpt_message/1)
+
+    session(
+      "chatgpt-" <> chatgpt_id(conversation),
+      conversation["title"],
+      unix_time(conversation["create_time"]),
+      messages,
+      path
+    )
+  end
+
+  defp chatgpt_id(%{"conversation_id" => id}) when is_binary(id), do: id
+  defp chatgpt_id(%{"id" => id}) when is_binary(id), do: id
+
+  # Without an id, title and creation time tell conversations apart.
+  defp chatgpt_id(conversation) do
+    digest(Jason.encode!([conversation["title"], conversation["create_time"]]))
+  end
+
+  # Walks from the current node up to the root, the branch the user last saw.
+  defp branch(mapping, node_id, acc \\ [])
+  defp branch(_mapping, nil, acc), do: acc
+
+  defp branch(mapping, node_id, acc) do
+    case mapping[node_id] do
+      nil -> acc
+      node -> branch(mapping, node["parent"], [node | acc])
+    end
+  end
+
+  defp chatgpt_message(%{"message" => %{"author" => %{"role" => role}} = message})
+       when role in ["user", "assistant"] do
+    text =
+      (get_in(message, ["content", "parts"]) || [])
+      |> Enum.filter(&is_binary/1)
+      |> Enum.join("\n")
+
+    if String.trim(text) == "" do
+      []
+    else
+      [{role, [%{"type" => "text", "text" => text}], unix_time(message["create_time"])}]
+    end
+  end
+
+  defp chatgpt_message(_node), do: []
+
+  ## Generic message arrays
+
+  defp from_messages(messages, document, content, path) do
+    messages =
+      Enum.map(messages, fn message ->
+        {message["role"], parts(message), nil}
+      end)
+
+    session("import-" <> digest(content), document["title"], nil, messages, path)
+  end
+
+  defp digest(data) do
+    :crypto.hash(:sha256, data) |> Base.encode16(case: :lower) |> binary_part(0, 16)
+  end
+
+  defp parts(%{"role" => "tool"} = message) do
+    [
+      %{
+        "type" => "tool_result",
+        "tool_use_id" => message["tool_call_id"],
+        "content" => message["content"]
+      }
+    ]
+  end
+
+  defp parts(message) do
+    content =
+      case message["content"] do
+        text when is_binary(text) -> [%{"type" => "text", "text" => text}]
+        parts when is_list(parts) -> parts
+        _ -> []
+      end
+
+    # OpenAI puts tool calls next to the content rather than in it.
+    tool_calls =
+      for %{"function" => function} = call <- message["tool_calls"] || [] do
+        %{
+          "type" => "tool_use",
+          "id" => call["id"],
+          "name" => function["name"],
+          "input" => decode_arguments(function["arguments"])
+        }
+      end
+
+    content ++ tool_calls
+  end
+
+  defp decode_arguments(arguments) when is_binary(arguments) do
+    case Jason.decode(arguments) do
+      {:ok, input} when is_map(input) -> input
+      _ -> %{"arguments" => arguments}
+    end
+  end
+
+  defp decode_arguments(arguments) when is_map(arguments), do: arguments
+  defp decode_arguments(_arguments), do: %{}
+
+  ## Normalization
+
+  defp session(external_id, title, started_at, messages, path) do
+    results =
+      for {_role, parts, _time} <- messages,
+          %{"type" => "tool_result", "tool_use_id" => id} = part <- parts,
+          into: %{},
+          do: {id, result_text(part["content"])}
+
+    blocks =
+      messages
+      |> Enum.flat_map(fn {role, parts, time} -> Enum.flat_map(parts, &block(role, &1, time)) end)
+      |> Enum.with_index(fn block, position -> Map.put(block, :position, position) end)
+      |> Enum.map(&attach_result(&1, results))
+
+    preview =
+      Enum.find_value(blocks, &(&1.block_type == :user_prompt && Preview.from_prompt(&1.content)))
+
+    %{
+      external_id: external_id,
+      source_type: source_type(),
+      source_path: path,
+      project: nil,
+      title: title || preview,
+      started_at: started_at || DateTime.utc_now() |> DateTime.truncate(:second),
+      blocks: blocks,
+      sub_agents: []
+    }
+  end
+
+  defp block("user", %{"type" => "text", "text" => text}, time),
+    do: [%{block_type: :user_prompt, content: text, metadata: nil, timestamp: time}]
+
+  defp block("assistant", %{"type" => "text", "text" => text}, time),
+    do: [%{block_type: :assistant_response, content: text, metadata: nil, timestamp: time}]
+
+  defp block("assistant", %{"type" => "thinking", "thinking" => text}, time),
+    do: [%{block_type: :thinking, content: text, metadata: nil, timestamp: time}]
+
+  defp block(_role, %{"type" => "tool_use"} = part, time) do
+    metadata = %{"name" => part["name"], "input" => part["input"] || %{}, "id" => part["id"]}
+    [%{block_type: :tool_call, content: nil, metadata: metadata, timestamp: time}]
+  end
+
+  defp block(_role, _part, _time), do: []
+
+  defp attach_result(%{metadata: %{"id" => id} = metadata} = block, results)
+       when is_map_key(results, id) do
+    %{block | metadata: Map.put(metadata, "output", results[id])}
+  end
+
+  defp attach_result(block, _results), do: block
+
+  defp result_text(text) when is_binary(text), do: text
+
+  defp result_text(parts) when is_list(parts) do
+    parts
+    |> Enum.filter(&(is_map(&1) and &1["type"] == "text"))
+    |> Enum.map_join("\n", & &1["text"])
+  end
+
+  defp result_text(_content), do: nil
+
+  defp unix_time(seconds) when is_number(seconds) do
+    seconds |> trunc() |> DateTime.from_unix!()
+  end
+
+  defp unix_time(_seconds), do: nil
+end
@@ -0,0 +1,52 @@
+defmodule Panko.Sessions.Preview do
+  @moduledoc """
+  Turns a prompt into a one-line preview, used as a session's title when
+  the agent recorded none.
+
+  Claude Code stores more than the typed text as user messages: slash
+  commands wrapped in `<command-name>` tags, their local output and
+  caveats, and system reminders. These are dropped, as is a leading slash
+  command, and other hyphenated tags are unwrapped, so a prompt that was
+  only noise gives no preview and the next one is taken instead.
+  """
+
+  @max_length 200
+
+  @doc """
+  Returns the preview of a prompt, a string or a list of content parts, or
+  `nil` when nothing meaningful is left.
+
+  ## Examples
+
+      iex> from_prompt("<command-name>/review</command-name> <command-args>PR 12</command-args>")
+      "PR 12"
+
+      iex> from_prompt("<local-command-caveat>Caveat: ...</local-command-caveat>")
+      nil
+  """
+  @spec from_prompt(String.t() | [map()] | nil) :: String.t() | nil
+  def from_prompt(parts) when is_list(parts) do
+    parts
+    |> Enum.flat_map(fn
+      %{"type" => "text", "text" => text} when is_binary(text) -> [text]
+      _part -> []
+    end)
+    |> Enum.join("\n")
+    |> from_prompt()
+  end
+
+  def from_prompt(text) when is_binary(text) do
+    text
+    |> String.replace(~r/<(system-reminder|local-command-\w+|command-message)>.*?<\/\1>/s, "")
+    |> String.replace(~r/<\/?[a-z]+(-[a-z]+)+>/, " ")
+    |> String.replace(~r/\s+/, " ")
+    |> String.trim()
+    |> String.replace(~r/\A\/[\w:-]+(\s+|\z)/, "")
+    |> case do
+      "" -> nil
+      preview -> String.slice(preview, 0, @max_length)
+    end
+  end
+
+  def from_prompt(_content), do: nil
+end
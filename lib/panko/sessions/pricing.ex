@@ -0,0 +1,61 @@
+defmodule Panko.Sessions.Pricing do
+  @moduledoc """
+  Estimates the API cost of token usage from published per-model prices.
+
+  Prices are in USD per million tokens and matched against the model name
+  by family, so dated variants such as `claude-sonnet-4-5-20250929` are
+  covered. The figures are estimates; subscription plans and batch
+  discounts are not taken into account.
+  """
+
+  @doc """
+  Returns the `{input, output, cache_write, cache_read}` prices for `model`,
+  or `nil` when the model is unknown.
+  """
+  @spec prices(String.t() | nil) :: {number(), number(), number(), number()} | nil
+  def prices(model) when is_binary(model) do
+    Enum.find_value(price_table(), fn {families, prices} ->
+      if Enum.any?(families, &String.contains?(model, &1)), do: prices
+    end)
+  end
+
+  def prices(_model), do: nil
+
+  @doc """
+  Estimates the cost in USD of a `usage` map for `model`.
+
+  Accepts the API usage map with string keys, or `Panko.Sessions.Usage`
+  totals with atom keys. Returns `nil` when the model is unknown.
+  """
+  @spec cost(String.t() | nil, map()) :: float() | nil
+  def cost(model, usage) do
+    case prices(model) do
+      nil ->
+        nil
+
+      {input, output, cache_write, cache_read} ->
+        (tokens(usage, :input_tokens) * input +
+           tokens(usage, :output_tokens) * output +
+           tokens(usage, :cache_creation_input_tokens) * cache_write +
+           tokens(usage, :cache_read_input_tokens) * cache_read) / 1_000_000
+    end
+  end
+
+  # Most specific families first.
+  defp price_table do
+    [
+      {["opus-4-5", "opus-4-6"], {5.0, 25.0, 6.25, 0.5}},
+      {["opus"], {15.0, 75.0, 18.75, 1.5}},
+      {["sonnet"], {3.0, 15.0, 3.75, 0.3}},
+      {["haiku-4-5"], {1.0, 5.0, 1.25, 0.1}},
+      {["haiku"], {0.8, 4.0, 1.0, 0.08}}
+    ]
+  end
+
+  defp tokens(usage, key) do
+    case Map.get(usage, key, Map.get(usage, Atom.to_string(key))) do
+      count when is_integer(count) -> count
+      _ -> 0
+    end
+  end
+end
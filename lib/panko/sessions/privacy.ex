@@ -0,0 +1,158 @@
+defmodule Panko.Sessions.Privacy do
+  @moduledoc """
+  Audits a session for content that should not be published by accident,
+  before it is shared.
+
+  Findings are of four kinds:
+
+    * `:secret` - a match of one of the `Panko.Sessions.Redactor` rules,
+      such as an API key or a private key
+    * `:email` - an email address
+    * `:internal_host` - a host on an internal-only domain (`.internal`,
+      `.local`, `.corp`, `.lan`, `.intranet`, `.home.arpa`) or a private
+      IPv4 address
+    * `:large_file` - a tool input or output of more than 100 KB, such as a
+      whole file read or written, or an embedded base64 data URL
+
+  Each distinct value is reported once, with the first block it appears in
+  and how often it does. Secrets are masked in the findings, so the report
+  itself can be shown safely.
+  """
+
+  alias Panko.Sessions.Redactor
+
+  @kinds [:secret, :email, :internal_host, :large_file]
+  @large_bytes 100_000
+
+  @type kind :: :secret | :email | :internal_host | :large_file
+
+  @type finding :: %{
+          kind: kind(),
+          rule: atom(),
+          match: String.t(),
+          position: non_neg_integer() | nil,
+          count: pos_integer()
+        }
+
+  @doc """
+  Scans the title, and the content and metadata of every block, of a
+  session with its blocks loaded.
+  """
+  @spec scan(map()) :: [finding()]
+  def scan(session) do
+    texts =
+      [{nil, session.title}] ++
+        Enum.flat_map(session.blocks, fn block ->
+          strings = [block.content | strings(block.metadata)]
+          Enum.map(strings, &{block.position, &1})
+        end)
+
+    text_findings =
+      for {position, text} <- texts,
+          is_binary(text),
+          {kind, rule, match} <- matches(text),
+          do: {kind, rule, match, position}
+
+    # Several secret rules can match the same value; the first one names it.
+    (text_findings ++ large_tool_io(session.blocks))
+    |> Enum.group_by(fn {kind, _rule, match, _position} -> {kind, match} end)
+    |> Enum.map(fn {{kind, match}, [{_, rule, _, position} | _] = hits} ->
+      match = if kind == :secret, do: mask(match), else: match
+      count = Enum.count(hits, &(elem(&1, 1) == rule))
+      %{kind: kind, rule: rule, match: match, position: position, count: count}
+    end)
+    |> Enum.sort_by(&{kind_order(&1.kind), &1.position || -1, &1.match})
+  end
+
+  @doc """
+  Counts distinct findings per kind.
+  """
+  @spec summary([finding()]) :: %{kind() => non_neg_integer()}
+  def summary(findings) do
+    counts = Map.new(@kinds, &{&1, 0})
+    Enum.reduce(findings, counts, &Map.update!(&2, &1.kind, fn count -> count + 1 end))
+  end
+
+  @doc """
+  Describes the findings in one line, e.g. `2 secrets, 1 email`, or
+  returns `nil` when there are none.
+  """
+  @spec describe([finding()]) :: String.t() | nil
+  def describe([]), do: nil
+
+  def describe(findings) do
+    findings
+    |> summary()
+    |> Enum.reject(fn {_kind, count} -> count == 0 end)
+    |> Enum.sort_by(fn {kind, _count} -> kind_order(kind) end)
+    |> Enum.map_join(", ", fn {kind, count} -> "#{count} #{label(kind, count)}" end)
+  end
+
+  @doc """
+  Names a kind of finding, pluralized for `count`.
+  """
+  @spec label(kind(), non_neg_integer()) :: String.t()
+  def label(:secret, 1), do: "likely secret"
+  def label(:secret, _count), do: "likely secrets"
+  def label(:email, 1), do: "email address"
+  def label(:email, _count), do: "email addresses"
+  def label(:internal_host, 1), do: "internal host"
+  def label(:internal_host, _count), do: "internal hosts"
+  def label(:large_file, 1), do: "large embedded file"
+  def label(:large_file, _count), do: "large embedded files"
+
+  defp matches(text) do
+    redactor =
+      for {rule, regex, _replacement} <- Redactor.rules(emails: true),
+          [match | _] <- Regex.scan(regex, text) do
+        if rule == :email, do: {:email, :email, match}, else: {:secret, rule, match}
+      end
+
+    hosts =
+      for {rule, regex} <- host_rules(), [match | _] <- Regex.scan(regex, text),
+          do: {:internal_host, rule, match}
+
+    data_urls =
+      for [match] <- Regex.scan(~r/data:[\w\/+.-]+;base64,[A-Za-z0-9+\/=]{1000,}/, text),
+          do: {:large_file, :data_url, "data URL, #{kilobytes(match)}"}
+
+    redactor ++ hosts ++ data_urls
+  end
+
+  # Functions, as regexes cannot be stored in module attributes.
+  defp host_rules do
+    [
+      {:internal_domain,
+       ~r/\b(?:[a-z0-9-]+\.)+(?:internal|local|corp|lan|intranet|home\.arpa)(?!\.?[\w-])/i},
+      {:private_ip,
+       ~r/\b(?:
+         10(?:\.\d{1,3}){3}
+         | 192\.168(?:\.\d{1,3}){2}
+         | 172\.(?:1[6-9]|2\d|3[01])(?:\.\d{1,3}){2}
+       )\b/x}
+    ]
+  end
+
+  defp large_tool_io(blocks) do
+    for %{metadata: %{} = metadata} = block <- blocks,
+        {label, value} <- [{"input", written(metadata)}, {"output", metadata["output"]}],
+        is_binary(value) and byte_size(value) > @large_bytes do
+      {:large_file, :tool_io, "#{metadata["name"] || "Tool"} #{label}, #{kilobytes(value)}",
+       block.position}
+    end
+  end
+
+  defp written(%{"input" => %{"content" => content}}), do: content
+  defp written(_metadata), do: nil
+
+  defp strings(value) when is_binary(value), do: [value]
+  defp strings(value) when is_map(value), do: value |> Map.values() |> Enum.flat_map(&strings/1)
+  defp strings(value) when is_list(value), do: Enum.flat_map(value, &strings/1)
+  defp strings(_value), do: []
+
+  defp mask(secret), do: String.slice(secret, 0, 6) <> "…"
+
+  defp kilobytes(text), do: "#{div(byte_size(text), 1000)} KB"
+
+  defp kind_order(kind), do: Enum.find_index(@kinds, &(&1 == kind))
+end
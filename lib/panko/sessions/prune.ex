@@ -0,0 +1,132 @@
+defmodule Panko.Sessions.Prune do
+  @moduledoc """
+  Removes old or trivial sessions from disk and from Panko.
+
+  Pruning a session moves its file to the desktop trash (or into an
+  archive directory) and deletes its database records, including any
+  shares of it, so the session watcher does not import it again. Files are
+  deleted outright when `:prune_permanently` is configured
+  (`PANKO_PRUNE_PERMANENT`) or `permanent: true` is given.
+
+  Several sessions can come from one file, such as the conversations of a
+  ChatGPT export. Such a file stays in place until the last session
+  imported from it is pruned.
+  """
+
+  alias Panko.Sessions
+
+  require Ash.Query
+
+  @doc """
+  Returns the sessions matching all given criteria, oldest first.
+
+  ## Options
+
+    * `:older_than` - last activity before this `DateTime`
+    * `:max_messages` - at most this many messages
+    * `:project` - only sessions of this project
+  """
+  @spec candidates(keyword()) :: [Sessions.Session.t()]
+  def candidates(opts) do
+    %{project: opts[:project]}
+    |> Sessions.list_filtered_sessions!(load: [:last_activity_at])
+    |> Enum.filter(&matches?(&1, opts))
+    |> Enum.sort_by(&last_activity(&1), DateTime)
+  end
+
+  @doc """
+  Prunes `sessions`, moving their files to the trash.
+
+  ## Options
+
+    * `:archive` - move files into this directory instead
+    * `:permanent` - delete files instead (default: `:prune_permanently`)
+    * `:trash_dir` - use this freedesktop.org style trash directory, see
+      `Panko.Trash.move/2`
+
+  Returns `{pruned, failed}`, where failures are `{session, reason}`.
+  """
+  @spec prune([Sessions.Session.t()], keyword()) ::
+          {[Sessions.Session.t()], [{Sessions.Session.t(), term()}]}
+  def prune(sessions, opts \\ []) do
+    results =
+      Enum.map(sessions, fn session ->
+        with :ok <- remove_source(session, opts),
+             :ok <- Sessions.prune_session(session) do
+          {:ok, session}
+        else
+          {:error, reason} -> {:error, {session, reason}}
+        end
+      end)
+
+    {for({:ok, session} <- results, do: session), for({:error, failure} <- results, do: failure)}
+  end
+
+  defp matches?(session, opts) do
+    older? =
+      is_nil(opts[:older_than]) or
+        DateTime.compare(last_activity(session), opts[:older_than]) == :lt
+
+    small? = is_nil(opts[:max_messages]) or (session.message_count || 0) <= opts[:max_messages]
+
+    older? and small?
+  end
+
+  defp last_activity(session), do: session.last_activity_at || session.started_at
+
+  defp disposal(opts) do
+    cond do
+      opts[:archive] -> {:archive, opts[:archive]}
+      Keyword.get(opts, :permanent, permanent_default()) -> :delete
+      true -> {:trash, opts[:trash_dir]}
+    end
+  end
+
+  defp permanent_default, do: Application.get_env(:panko, :prune_permanently, false)
+
+  defp remove_source(session, opts) do
+    if shared_source?(session), do: :ok, else: remove_file(session.source_path, disposal(opts))
+  end
+
+  defp shared_source?(%{source_path: nil}), do: false
+
+  defp shared_source?(%{id: id, source_path: path}) do
+    Sessions.Session
+    |> Ash.Query.filter(source_path == ^path and id != ^id)
+    |> Ash.count!() > 0
+  end
+
+  defp remove_file(nil, _disposal), do: :ok
+
+  defp remove_file(path, :delete) do
+    case File.rm(path) do
+      {:error, :enoent} -> :ok
+      result -> result
+    end
+  end
+
+  defp remove_file(path, {:trash, trash_dir}) do
+    case Panko.Trash.move(path, trash_dir) do
+      {:error, :enoent} -> :ok
+      result -> result
+    end
+  end
+
+  # Archived files keep their project directory so they can be restored.
+  defp remove_file(path, {:archive, archive}) do
+    target = Path.join([archive, path |> Path.dirname() |> Path.basename(), Path.basename(path)])
+
+    if File.exists?(path) do
+      with :ok <- File.mkdir_p(Path.dirname(target)), do: move(path, target)
+    else
+      :ok
+    end
+  end
+
+  defp move(source, target) do
+    case File.rename(source, target) do
+      {:error, :exdev} -> with {:ok, _} <- File.copy(source, target), do: File.rm(source)
+      result -> result
+    end
+  end
+end
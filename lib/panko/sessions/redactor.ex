@@ -0,0 +1,135 @@
+defmodule Panko.Sessions.Redactor do
+  @moduledoc """
+  Masks likely secrets in session content.
+
+  Rules cover private keys, common API key and token formats, bearer
+  tokens, `key=value` style credential assignments and, optionally,
+  email addresses. Every match is replaced with `[REDACTED]` and counted
+  per rule so callers can report what was removed.
+
+  ## Options
+
+    * `:emails` - also redact email addresses (default: the
+      `:redact_emails` application setting, `true` unless configured)
+  """
+
+  @placeholder "[REDACTED]"
+
+  @type counts :: %{optional(atom()) => pos_integer()}
+
+  @doc """
+  Returns the `{name, regex, replacement}` rules applied for `opts`.
+  """
+  @spec rules(keyword()) :: [{atom(), Regex.t(), String.t()}]
+  def rules(opts \\ []) do
+    rules = [
+      {:private_key,
+       ~r/-----BEGIN [A-Z ]*PRIVATE KEY-----.*?-----END [A-Z ]*PRIVATE KEY-----/s, @placeholder},
+      {:anthropic_key, ~r/\bsk-ant-[A-Za-z0-9_\-]{20,}/, @placeholder},
+      {:openai_key, ~r/\bsk-(?:proj-)?[A-Za-z0-9_\-]{20,}/, @placeholder},
+      {:aws_access_key, ~r/\b(?:AKIA|ASIA)[0-9A-Z]{16}\b/, @placeholder},
+      {:github_token, ~r/\b(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,})/,
+       @placeholder},
+      {:slack_token, ~r/\bxox[abprs]-[A-Za-z0-9-]{10,}/, @placeholder},
+      {:bearer_token, ~r/(Bearer\s+)[A-Za-z0-9._~+\/-]{20,}=*/, "\\1" <> @placeholder},
+      {:credential,
+       ~r/((?:api[_-]?key|secret|token|password|passwd)["']?\s*[:=]\s*["']?)[^\s"',;\[]{8,}/i,
+       "\\1" <> @placeholder}
+    ]
+
+    if Keyword.get(opts, :emails, Application.get_env(:panko, :redact_emails, true)) do
+      rules ++ [{:email, ~r/\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b/, @placeholder}]
+    else
+      rules
+    end
+  end
+
+  @doc """
+  Redacts a string, returning the masked text and per-rule match counts.
+  """
+  @spec redact(String.t(), keyword()) :: {String.t(), counts()}
+  def redact(text, opts \\ []) when is_binary(text) do
+    redact_with(text, rules(opts), %{})
+  end
+
+  @doc """
+  Redacts the title, every block's content and metadata (tool inputs and
+  outputs included), the prompt, description and result of every loaded
+  sub-agent, and the author and body of every loaded comment.
+  """
+  @spec redact_session(Panko.Sessions.Session.t(), keyword()) ::
+          {Panko.Sessions.Session.t(), counts()}
+  def redact_session(session, opts \\ []) do
+    rules = rules(opts)
+
+    {title, counts} = redact_term(session.title, rules, %{})
+
+    {blocks, counts} =
+      Enum.map_reduce(session.blocks, counts, fn block, counts ->
+        {content, counts} = redact_term(block.content, rules, counts)
+        {metadata, counts} = redact_term(block.metadata, rules, counts)
+        {%{block | content: content, metadata: metadata}, counts}
+      end)
+
+    {sub_agents, counts} = redact_sub_agents(session.sub_agents, rules, counts)
+
+    {comments, counts} = redact_comments(session.comments, rules, counts)
+
+    {%{session | title: title, blocks: blocks, sub_agents: sub_agents, comments: comments},
+     counts}
+  end
+
+  @doc """
+  Sums per-rule counts into a single total.
+  """
+  @spec total(counts()) :: non_neg_integer()
+  def total(counts), do: counts |> Map.values() |> Enum.sum()
+
+  defp redact_sub_agents(sub_agents, rules, counts) when is_list(sub_agents) do
+    Enum.map_reduce(sub_agents, counts, fn sub_agent, counts ->
+      {prompt, counts} = redact_term(sub_agent.prompt, rules, counts)
+      {description, counts} = redact_term(sub_agent.description, rules, counts)
+      {result, counts} = redact_term(sub_agent.result, rules, counts)
+      {%{sub_agent | prompt: prompt, description: description, result: result}, counts}
+    end)
+  end
+
+  defp redact_sub_agents(not_loaded, _rules, counts), do: {not_loaded, counts}
+
+  defp redact_comments(comments, rules, counts) when is_list(comments) do
+    Enum.map_reduce(comments, counts, fn comment, counts ->
+      {author, counts} = redact_term(comment.author, rules, counts)
+      {body, counts} = redact_term(comment.body, rules, counts)
+      {%{comment | author: author, body: body}, counts}
+    end)
+  end
+
+  defp redact_comments(not_loaded, _rules, counts), do: {not_loaded, counts}
+
+  defp redact_term(text, rules, counts) when is_binary(text), do: redact_with(text, rules, counts)
+
+  defp redact_term(map, rules, counts) when is_map(map) and not is_struct(map) do
+    {pairs, counts} =
+      Enum.map_reduce(map, counts, fn {key, value}, counts ->
+        {value, counts} = redact_term(value, rules, counts)
+        {{key, value}, counts}
+      end)
+
+    {Map.new(pairs), counts}
+  end
+
+  defp redact_term(list, rules, counts) when is_list(list) do
+    Enum.map_reduce(list, counts, &redact_term(&1, rules, &2))
+  end
+
+  defp redact_term(other, _rules, counts), do: {other, counts}
+
+  defp redact_with(text, rules, counts) do
+    Enum.reduce(rules, {text, counts}, fn {name, regex, replacement}, {text, counts} ->
+      case length(Regex.scan(regex, text)) do
+        0 -> {text, counts}
+        n -> {Regex.replace(regex, text, replacement), Map.update(counts, name, n, &(&1 + n))}
+      end
+    end)
+  end
+end
@@ -0,0 +1,84 @@
+defmodule Panko.Sessions.Search do
+  @moduledoc """
+  Case-insensitive search over the content of every imported block.
+
+  Results are grouped per session, with a snippet and line reference for
+  each matching line, so a session can be found from a half-remembered
+  phrase.
+  """
+
+  alias Panko.Sessions
+  alias Panko.Sessions.Session
+
+  @snippet_radius 40
+
+  @doc """
+  Searches for `query` and returns matching sessions, most matches first.
+
+  Each result is `%{session: session, matches: matches}` where a match is
+  `%{position: block_position, block_type: type, line: line, snippet: text}`.
+
+  ## Options
+
+    * `:project` - only search sessions of this project
+    * `:limit` - at most this many sessions, the most recent when more match
+  """
+  @spec search(String.t(), keyword()) :: [map()]
+  def search(query, opts \\ []) do
+    pattern = like_pattern(query)
+
+    sessions =
+      Session
+      |> Ash.Query.for_read(:search, %{pattern: pattern, project: opts[:project]})
+      |> Ash.Query.limit(opts[:limit])
+      |> Ash.read!()
+
+    blocks =
+      pattern
+      |> Sessions.search_blocks!(Enum.map(sessions, & &1.id))
+      |> Enum.group_by(& &1.session_id)
+
+    sessions
+    |> Enum.map(fn session ->
+      %{session: session, matches: Enum.flat_map(blocks[session.id] || [], &matches(&1, query))}
+    end)
+    |> Enum.sort_by(&length(&1.matches), :desc)
+  end
+
+  # Wildcards in the query are matched literally.
+  defp like_pattern(query) do
+    "%" <> String.replace(query, ~r/[\\%_]/, "\\\\\\0") <> "%"
+  end
+
+  defp matches(block, query) do
+    context = ".{0,#{@snippet_radius}}"
+    regex = Regex.compile!(context <> Regex.escape(query) <> context, "iu")
+
+    block.content
+    |> String.split("\n")
+    |> Enum.with_index(1)
+    |> Enum.flat_map(fn {line, number} ->
+      case Regex.run(regex, line, return: :index) do
+        [{start, length}] ->
+          [
+            %{
+              position: block.position,
+              block_type: block.block_type,
+              line: number,
+              snippet: snippet(line, start, length)
+            }
+          ]
+
+        nil ->
+          []
+      end
+    end)
+  end
+
+  defp snippet(line, start, length) do
+    prefix = if start > 0, do: "…", else: ""
+    suffix = if start + length < byte_size(line), do: "…", else: ""
+
+    prefix <> String.trim(binary_part(line, start, length)) <> suffix
+  end
+end
@@ -60,6 +60,32 @@ defmodule Panko.Sessions.Session do
       public? true
     end
 
+    # Usage cached by Panko.Sessions.Changes.CacheUsage; nil until cached.
+    attribute :input_tokens, :integer do
+      allow_nil? true
+      public? true
+    end
+
+    attribute :output_tokens, :integer do
+      allow_nil? true
+      public? true
+    end
+
+    attribute :cache_creation_input_tokens, :integer do
+      allow_nil? true
+      public? true
+    end
+
+    attribute :cache_read_input_tokens, :integer do
+      allow_nil? true
+      public? true
+    end
+
+    attribute :estimated_cost_usd, :float do
+      allow_nil? true
+      public? true
+    end
+
     timestamps()
   end
 
@@ -72,6 +98,21 @@ defmodule Panko.Sessions.Session do
     has_many :sub_agents, Panko.Sessions.SubAgent do
       public? true
     end
+
+    has_many :comments, Panko.Sessions.Comment do
+      sort position: :asc, inserted_at: :asc
+      public? true
+    end
+
+    has_many :bookmarks, Panko.Sessions.Bookmark do
+      sort position: :asc
+      public? true
+    end
+
+    has_many :shares, Panko.Sharing.Share do
+      domain Panko.Sharing
+      public? true
+    end
   end
 
   aggregates do
@@ -128,13 +169,29 @@ defmodule Panko.Sessions.Session do
 
       upsert? true
       upsert_identity :external_id_source_type
-      upsert_fields [:source_path, :project, :title, :started_at]
+      upsert_fields [
+        :source_path,
+        :project,
+        :title,
+        :started_at,
+        :input_tokens,
+        :output_tokens,
+        :cache_creation_input_tokens,
+        :cache_read_input_tokens,
+        :estimated_cost_usd
+      ]
 
       argument :blocks, {:array, :map}, allow_nil?: false
       argument :sub_agents, {:array, :map}, default: []
 
       change manage_relationship(:blocks, :blocks, type: :direct_control)
       change manage_relationship(:sub_agents, :sub_agents, type: :direct_control)
+      change Panko.Sessions.Changes.CacheUsage
+    end
+
+    update :cache_usage do
+      require_atomic? false
+      change Panko.Sessions.Changes.CacheUsage
     end
 
     action :import_from_file, :struct do
@@ -145,8 +202,7 @@ defmodule Panko.Sessions.Session do
       run fn input, _context ->
         path = input.arguments.file_path
 
-        with {:ok, parser} <- Panko.Sessions.Parsers.Registry.find_parser(path),
-             {:ok, attrs} <- parser.parse(path) do
+        with {:ok, attrs} <- Panko.Sessions.ParseCache.parse(path) do
           __MODULE__
           |> Ash.Changeset.for_create(:upsert_from_import, attrs)
           |> Ash.create()
@@ -154,10 +210,50 @@ defmodule Panko.Sessions.Session do
       end
     end
 
+    # Child rows reference the session without ON DELETE, so they go first.
+    destroy :prune do
+      require_atomic? false
+      change cascade_destroy(:shares, after_action?: false)
+      change cascade_destroy(:blocks, after_action?: false)
+      change cascade_destroy(:sub_agents, after_action?: false)
+    end
+
+    read :missing_usage do
+      filter expr(is_nil(input_tokens))
+      prepare build(sort: [started_at: :desc], limit: 100)
+    end
+
     read :list_recent do
       prepare build(sort: [started_at: :desc], limit: 50)
     end
 
+    read :by_project do
+      argument :project, :string, allow_nil?: false
+      filter expr(project == ^arg(:project))
+      prepare build(sort: [started_at: :asc])
+    end
+
+    # `pattern` is an ILIKE pattern, see `Panko.Sessions.Search`.
+    read :search do
+      argument :pattern, :string, allow_nil?: false
+      argument :project, :string, allow_nil?: true
+      filter expr(exists(blocks, ilike(content, ^arg(:pattern))))
+      filter expr(is_nil(^arg(:project)) or project == ^arg(:project))
+      prepare build(sort: [started_at: :desc])
+    end
+
+    read :filtered do
+      argument :project, :string, allow_nil?: true
+      argument :since, :utc_datetime, allow_nil?: true
+      filter expr(is_nil(^arg(:project)) or project == ^arg(:project))
+      filter expr(is_nil(^arg(:since)) or started_at >= ^arg(:since))
+
+      prepare build(
+                sort: [started_at: :desc],
+                load: [:block_count, :message_count, :tool_call_count]
+              )
+    end
+
     read :list_projects do
       prepare build(
                 sort: [started_at: :desc],
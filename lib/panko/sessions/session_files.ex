@@ -0,0 +1,71 @@
+defmodule Panko.Sessions.SessionFiles do
+  @moduledoc """
+  Finds session files on disk under the configured watch paths.
+
+  Agents store each project's sessions in a directory named after the
+  project path with every non-alphanumeric character replaced by `-`,
+  e.g. `~/.claude/projects/-home-me-code-app/<session>.jsonl`.
+  """
+
+  @doc """
+  The configured watch paths, expanded.
+  """
+  @spec watch_paths() :: [Path.t()]
+  def watch_paths do
+    :panko
+    |> Application.get_env(:session_watch_paths, "~/.claude/projects")
+    |> List.wrap()
+    |> Enum.map(&Path.expand/1)
+  end
+
+  @doc """
+  Session files of a project.
+
+  `project` is either a directory of session files or a project path,
+  which is looked up in its encoded directory under the watch paths.
+  """
+  @spec project_files(Path.t()) :: [Path.t()]
+  def project_files(project) do
+    dir = Path.expand(project)
+
+    case Path.wildcard(Path.join(dir, "*.jsonl")) do
+      [] -> Enum.flat_map(watch_paths(), &Path.wildcard(Path.join([&1, encode(dir), "*.jsonl"])))
+      files -> files
+    end
+  end
+
+  @doc """
+  Top-level session files of every project under the watch paths, found
+  inside a `[:panko, :sessions, :scan]` telemetry span.
+  """
+  @spec all_files() :: [Path.t()]
+  def all_files do
+    :telemetry.span([:panko, :sessions, :scan], %{}, fn ->
+      files = Enum.flat_map(watch_paths(), &Path.wildcard(Path.join([&1, "*", "*.jsonl"])))
+      {files, %{count: length(files)}}
+    end)
+  end
+
+  @doc """
+  The most recently modified file of `files`, or `nil` when there is none.
+  """
+  @spec latest([Path.t()]) :: Path.t() | nil
+  def latest(files) do
+    files
+    |> Enum.map(&{&1, File.stat!(&1, time: :posix).mtime})
+    |> Enum.max_by(&elem(&1, 1), fn -> nil end)
+    |> case do
+      nil -> nil
+      {file, _mtime} -> file
+    end
+  end
+
+  @doc """
+  Whether a session was imported from a file that no longer exists.
+  """
+  @spec source_missing?(%{source_path: Path.t() | nil}) :: boolean()
+  def source_missing?(%{source_path: path}) when is_binary(path), do: not File.exists?(path)
+  def source_missing?(_session), do: false
+
+  defp encode(dir), do: String.replace(dir, ~r/[^A-Za-z0-9]/, "-")
+end
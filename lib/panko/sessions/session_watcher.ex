@@ -97,17 +97,27 @@ defmodule Panko.Sessions.SessionWatcher do
     Path.wildcard(Path.join([dir, "**", "*.jsonl"]))
   end
 
+  # A deleted or rotated file leaves its imported session in place; viewers
+  # are told so they can flag the missing source.
   defp import_file(path) do
-    Task.start(fn -> do_import(path) end)
+    if File.exists?(path) do
+      Task.start(fn -> do_import(path) end)
+    else
+      Logger.debug("Session file #{path} was removed; keeping the imported session")
+      PankoWeb.Endpoint.broadcast("sessions:source_removed", "removed", %{path: path})
+    end
   end
 
   defp do_import(path) do
     case Panko.Sessions.import_from_file(path) do
       {:ok, session} ->
+        Panko.Metrics.increment(:sessions_imported)
         Logger.debug("Imported session #{session.external_id} from #{path}")
 
       {:error, reason} ->
+        Panko.Metrics.increment(:import_errors)
         Logger.warning("Failed to import #{path}: #{inspect(reason)}")
+        Panko.Notifications.notify(:import_failed, "Session import failed", path)
     end
   end
 end
@@ -1,3 +1,3 @@
 defmodule Panko.Sessions.SourceType do
-  use Ash.Type.Enum, values: [:claude_code, :codex]
+  use Ash.Type.Enum, values: [:claude_code, :codex, :imported]
 end
@@ -0,0 +1,252 @@
+defmodule Panko.Sessions.Stats do
+  @moduledoc """
+  Per-session usage statistics for spreadsheet analysis, and reports that
+  aggregate them across sessions.
+
+  Rows are computed from sessions with their blocks loaded, so they work
+  for imported sessions and for sessions parsed straight from disk alike.
+  `stored_rows/1` avoids loading blocks by reading the usage cached on
+  imported sessions.
+  """
+
+  alias Panko.Sessions.ToolTiming
+  alias Panko.Sessions.Usage
+  alias Panko.Sessions.Workers.UsageBackfill
+
+  @columns [
+    :id,
+    :external_id,
+    :project,
+    :title,
+    :source_type,
+    :started_at,
+    :ended_at,
+    :messages,
+    :blocks,
+    :input_tokens,
+    :output_tokens,
+    :cache_creation_input_tokens,
+    :cache_read_input_tokens,
+    :estimated_cost_usd,
+    :tools
+  ]
+
+  @doc """
+  Returns the column names of a stats row, in CSV order.
+  """
+  @spec columns() :: [atom()]
+  def columns, do: @columns
+
+  @doc """
+  Computes the stats row of a session with its blocks loaded.
+  """
+  @spec session_row(Panko.Sessions.Session.t()) :: map()
+  def session_row(session) do
+    blocks = session.blocks
+
+    Map.merge(Usage.totals(blocks), %{
+      id: session.id,
+      external_id: session.external_id,
+      project: session.project,
+      title: session.title,
+      source_type: session.source_type,
+      started_at: session.started_at,
+      ended_at: ended_at(session),
+      messages: Enum.count(blocks, &(&1.block_type in [:user_prompt, :assistant_response])),
+      blocks: length(blocks),
+      estimated_cost_usd: Usage.cost(blocks),
+      tools: tools(blocks)
+    })
+  end
+
+  @doc """
+  Computes the stats rows of imported sessions matching `filter`, the
+  `:project` and `:since` arguments of the `:filtered` read action.
+
+  Rows come from the usage cached on each session and its block
+  aggregates, with `tools` left `nil`. Sessions imported before usage was
+  cached are computed from their blocks, and a
+  `Panko.Sessions.Workers.UsageBackfill` job is queued to cache them.
+  """
+  @spec stored_rows(map()) :: [map()]
+  def stored_rows(filter) do
+    sessions = Panko.Sessions.list_filtered_sessions!(filter, load: [:last_activity_at])
+
+    if Enum.any?(sessions, &is_nil(&1.input_tokens)), do: Oban.insert(UsageBackfill.new(%{}))
+
+    Enum.map(sessions, fn
+      %{input_tokens: nil} = session -> session |> Ash.load!(:blocks) |> session_row()
+      session -> cached_row(session)
+    end)
+  end
+
+  defp cached_row(session) do
+    %{
+      id: session.id,
+      external_id: session.external_id,
+      project: session.project,
+      title: session.title,
+      source_type: session.source_type,
+      started_at: session.started_at,
+      ended_at: session.last_activity_at || session.started_at,
+      messages: session.message_count,
+      blocks: session.block_count,
+      input_tokens: session.input_tokens,
+      output_tokens: session.output_tokens,
+      cache_creation_input_tokens: session.cache_creation_input_tokens,
+      cache_read_input_tokens: session.cache_read_input_tokens,
+      estimated_cost_usd: session.estimated_cost_usd,
+      tools: nil
+    }
+  end
+
+  @doc """
+  Encodes rows as CSV with a header line, using the session row `columns`
+  unless others are given.
+
+  Tool names are joined with spaces, and costs are rounded to four decimals.
+  """
+  @spec to_csv([map()], [atom()]) :: iodata()
+  def to_csv(rows, columns \\ @columns) do
+    header = Enum.map_join(columns, ",", &Atom.to_string/1)
+
+    lines =
+      Enum.map(rows, fn row ->
+        Enum.map_join(columns, ",", &(row |> Map.get(&1) |> csv_field()))
+      end)
+
+    Enum.map([header | lines], &[&1, "\r\n"])
+  end
+
+  @summed [:messages, :input_tokens, :output_tokens, :estimated_cost_usd]
+
+  @doc """
+  Aggregates session rows into totals, per-project totals (most expensive
+  first) and the busiest days (most messages first).
+  """
+  @spec report([map()], keyword()) :: map()
+  def report(rows, opts \\ []) do
+    %{
+      totals: sum(rows),
+      projects:
+        rows
+        |> Enum.group_by(&(&1.project || "Unknown Project"))
+        |> Enum.map(fn {project, rows} -> Map.put(sum(rows), :project, project) end)
+        |> Enum.sort_by(& &1.estimated_cost_usd, :desc),
+      busiest_days:
+        rows
+        |> Enum.reject(&is_nil(&1.started_at))
+        |> Enum.group_by(&DateTime.to_date(&1.started_at))
+        |> Enum.map(fn {date, rows} -> Map.put(sum(rows), :date, date) end)
+        |> Enum.sort_by(& &1.messages, :desc)
+        |> Enum.take(Keyword.get(opts, :days, 5))
+    }
+  end
+
+  @doc """
+  Summarizes the `:days` (default 30) days up to `:today` for the insights
+  page: sessions and messages for every day in the range, including idle
+  ones, the busiest projects by session count, the average session length
+  in seconds and the total estimated cost.
+  """
+  @spec insights([map()], keyword()) :: map()
+  def insights(rows, opts \\ []) do
+    days = Keyword.get(opts, :days, 30)
+    today = Keyword.get_lazy(opts, :today, &Date.utc_today/0)
+    first = Date.add(today, 1 - days)
+
+    rows =
+      Enum.filter(rows, fn row ->
+        row.started_at && Date.compare(DateTime.to_date(row.started_at), first) != :lt
+      end)
+
+    by_day = Enum.group_by(rows, &DateTime.to_date(&1.started_at))
+
+    durations =
+      for %{started_at: %DateTime{} = started, ended_at: %DateTime{} = ended} <- rows,
+          do: DateTime.diff(ended, started)
+
+    %{
+      per_day:
+        Enum.map(Date.range(first, today), fn date ->
+          date_rows = Map.get(by_day, date, [])
+          %{date: date, sessions: length(date_rows), messages: sum(date_rows).messages}
+        end),
+      projects:
+        rows
+        |> Enum.group_by(&(&1.project || "Unknown Project"))
+        |> Enum.map(fn {project, rows} -> Map.put(sum(rows), :project, project) end)
+        |> Enum.sort_by(& &1.sessions, :desc)
+        |> Enum.take(Keyword.get(opts, :projects, 5)),
+      average_duration: average(durations),
+      totals: sum(rows)
+    }
+  end
+
+  # Grouped in the database, as sessions can hold many thousands of tool
+  # calls between them. Ash reads have no GROUP BY, hence the SQL.
+  @tool_timings_sql """
+  SELECT coalesce(metadata->>'name', 'Tool'), count(*), sum(duration)::bigint, max(duration)
+  FROM (
+    SELECT metadata, (metadata->>'duration_ms')::bigint AS duration
+    FROM blocks
+    WHERE metadata ? 'duration_ms' AND ($1::timestamp IS NULL OR "timestamp" >= $1)
+  ) AS timed
+  GROUP BY 1
+  ORDER BY 3 DESC
+  """
+
+  @doc """
+  Aggregates the recorded tool durations of imported sessions since
+  `since` (or of all sessions when `nil`) per tool, slowest in total first,
+  like `Panko.Sessions.ToolTiming.summary/1`.
+  """
+  @spec tool_timings(DateTime.t() | nil) :: [ToolTiming.timing()]
+  def tool_timings(since) do
+    %{rows: rows} = Panko.Repo.query!(@tool_timings_sql, [since && DateTime.to_naive(since)])
+
+    Enum.map(rows, fn [tool, calls, total, max] ->
+      %{tool: tool, calls: calls, total_ms: total, average_ms: div(total, calls), max_ms: max}
+    end)
+  end
+
+  defp average([]), do: 0
+  defp average(values), do: div(Enum.sum(values), length(values))
+
+  defp sum(rows) do
+    @summed
+    |> Map.new(fn key -> {key, Enum.sum(Enum.map(rows, &(Map.get(&1, key) || 0)))} end)
+    |> Map.put(:sessions, length(rows))
+  end
+
+  defp ended_at(session) do
+    session.blocks
+    |> Enum.map(& &1.timestamp)
+    |> Enum.reject(&is_nil/1)
+    |> Enum.max(DateTime, fn -> session.started_at end)
+  end
+
+  defp tools(blocks) do
+    blocks
+    |> Enum.filter(&(&1.block_type in [:tool_call, :file_edit, :sub_agent_spawn]))
+    |> Enum.map(&(&1.metadata && &1.metadata["name"]))
+    |> Enum.reject(&is_nil/1)
+    |> Enum.uniq()
+    |> Enum.sort()
+  end
+
+  defp csv_field(nil), do: ""
+  defp csv_field(list) when is_list(list), do: list |> Enum.join(" ") |> csv_field()
+  defp csv_field(%DateTime{} = dt), do: DateTime.to_iso8601(dt)
+  defp csv_field(float) when is_float(float), do: :erlang.float_to_binary(float, decimals: 4)
+
+  defp csv_field(value) do
+    value = to_string(value)
+
+    if String.contains?(value, [",", "\"", "\n", "\r"]) do
+      ~s("#{String.replace(value, "\"", "\"\"")}")
+    else
+      value
+    end
+  end
+end
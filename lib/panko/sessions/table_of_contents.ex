@@ -0,0 +1,76 @@
+defmodule Panko.Sessions.TableOfContents do
+  @moduledoc """
+  Builds a table of contents with one entry per user prompt, used by the
+  web viewer and the Markdown and HTML exports.
+
+  Entries link to `#block-<position>`, the id each block is rendered
+  under. Sessions with fewer than two prompts get no table of contents.
+  Bookmarked blocks get a list of their own, see `bookmarks/2`.
+  """
+
+  @min_entries 2
+  @max_length 80
+
+  @type entry :: %{position: integer(), anchor: String.t(), title: String.t()}
+
+  @doc """
+  Returns the entries for `blocks`, or `[]` when there are too few
+  prompts for a table of contents to help.
+  """
+  @spec entries([map()]) :: [entry()]
+  def entries(blocks) do
+    entries =
+      blocks
+      |> Enum.filter(&(&1.block_type == :user_prompt))
+      |> Enum.map(&%{position: &1.position, anchor: anchor(&1), title: title(&1.content)})
+      |> Enum.reject(&(&1.title == ""))
+
+    if length(entries) >= @min_entries, do: entries, else: []
+  end
+
+  @doc """
+  Returns one entry per bookmark whose block is in `blocks`, titled with
+  the bookmark's label or else the block's first line.
+  """
+  @spec bookmarks([map()], [map()]) :: [entry()]
+  def bookmarks(blocks, bookmarks) do
+    by_position = Map.new(blocks, &{&1.position, &1})
+
+    for bookmark <- Enum.sort_by(bookmarks, & &1.position),
+        block = by_position[bookmark.position] do
+      title =
+        case {bookmark.label, title(block.content)} do
+          {label, _} when is_binary(label) and label != "" -> label
+          {_, ""} -> block_label(block)
+          {_, title} -> title
+        end
+
+      %{position: block.position, anchor: anchor(block), title: title}
+    end
+  end
+
+  @doc """
+  Returns the anchor a block is rendered under.
+  """
+  @spec anchor(map()) :: String.t()
+  def anchor(block), do: "block-#{block.position}"
+
+  defp block_label(%{metadata: %{"name" => name}}) when is_binary(name), do: name
+  defp block_label(block), do: "Block #{block.position}"
+
+  defp title(nil), do: ""
+
+  defp title(content) do
+    line =
+      content
+      |> String.split("\n")
+      |> Enum.map(&String.trim/1)
+      |> Enum.find("", &(&1 != ""))
+
+    if String.length(line) > @max_length do
+      String.slice(line, 0, @max_length - 1) <> "…"
+    else
+      line
+    end
+  end
+end
@@ -0,0 +1,60 @@
+defmodule Panko.Sessions.ToolTiming do
+  @moduledoc """
+  How long tool calls took, measured by the parser from a tool use to its
+  result and kept as `duration_ms` in the block's metadata.
+
+  Used by the viewer's tool blocks, the insights page and the commands
+  export to point out slow tooling.
+  """
+
+  @type timing :: %{
+          tool: String.t(),
+          calls: pos_integer(),
+          total_ms: non_neg_integer(),
+          average_ms: non_neg_integer(),
+          max_ms: non_neg_integer()
+        }
+
+  @doc """
+  Returns the duration of a tool block in milliseconds, or `nil` when no
+  result was recorded.
+  """
+  @spec duration_ms(map()) :: non_neg_integer() | nil
+  def duration_ms(%{metadata: %{"duration_ms" => ms}}) when is_integer(ms), do: ms
+  def duration_ms(_block), do: nil
+
+  @doc """
+  Formats a duration as `850ms`, `4.2s` or `2m 05s`.
+  """
+  @spec format(non_neg_integer()) :: String.t()
+  def format(ms) when ms < 1000, do: "#{ms}ms"
+  def format(ms) when ms < 60_000, do: "#{Float.round(ms / 1000, 1)}s"
+
+  def format(ms) do
+    seconds = div(ms, 1000)
+    padded = seconds |> rem(60) |> Integer.to_string() |> String.pad_leading(2, "0")
+    "#{div(seconds, 60)}m #{padded}s"
+  end
+
+  @doc """
+  Aggregates `{tool, duration_ms}` pairs per tool, the slowest in total
+  first.
+  """
+  @spec summary(Enumerable.t()) :: [timing()]
+  def summary(durations) do
+    durations
+    |> Enum.group_by(&elem(&1, 0), &elem(&1, 1))
+    |> Enum.map(fn {tool, values} ->
+      total = Enum.sum(values)
+
+      %{
+        tool: tool,
+        calls: length(values),
+        total_ms: total,
+        average_ms: div(total, length(values)),
+        max_ms: Enum.max(values)
+      }
+    end)
+    |> Enum.sort_by(& &1.total_ms, :desc)
+  end
+end
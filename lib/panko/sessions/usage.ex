@@ -0,0 +1,76 @@
+defmodule Panko.Sessions.Usage do
+  @moduledoc """
+  Aggregates token usage recorded in block metadata.
+
+  Parsers attach the API `usage` map of each assistant message to the
+  first block produced from it, along with the message id and model, so
+  totals and cost estimates can be computed from blocks alone.
+  """
+
+  @token_keys ~w(input_tokens output_tokens cache_creation_input_tokens cache_read_input_tokens)a
+
+  @type t :: %{
+          input_tokens: non_neg_integer(),
+          output_tokens: non_neg_integer(),
+          cache_creation_input_tokens: non_neg_integer(),
+          cache_read_input_tokens: non_neg_integer()
+        }
+
+  @doc """
+  Sums token usage across blocks, counting each API message once.
+  """
+  @spec totals([map()]) :: t()
+  def totals(blocks) do
+    blocks
+    |> messages()
+    |> Enum.reduce(empty(), fn {_message_id, %{"usage" => usage}}, acc ->
+      Enum.reduce(@token_keys, acc, fn key, acc ->
+        Map.update!(acc, key, &(&1 + token_count(usage[Atom.to_string(key)])))
+      end)
+    end)
+  end
+
+  @doc """
+  Estimates the cost in USD of the usage recorded in `blocks`.
+
+  Each API message is priced with the model recorded next to its usage;
+  messages from unknown models are skipped. Returns `nil` when no message
+  could be priced.
+  """
+  @spec cost([map()]) :: float() | nil
+  def cost(blocks) do
+    blocks
+    |> messages()
+    |> Enum.map(fn {_message_id, metadata} ->
+      Panko.Sessions.Pricing.cost(metadata["model"], metadata["usage"])
+    end)
+    |> Enum.reject(&is_nil/1)
+    |> case do
+      [] -> nil
+      costs -> Enum.sum(costs)
+    end
+  end
+
+  @doc """
+  Returns a usage map with all counts at zero.
+  """
+  @spec empty() :: t()
+  def empty, do: Map.new(@token_keys, &{&1, 0})
+
+  defp messages(blocks) do
+    blocks
+    |> Enum.flat_map(fn block ->
+      case block.metadata do
+        %{"usage" => usage} = metadata when is_map(usage) ->
+          [{metadata["message_id"] || block.position, metadata}]
+
+        _ ->
+          []
+      end
+    end)
+    |> Enum.uniq_by(fn {message_id, _metadata} -> message_id end)
+  end
+
+  defp token_count(count) when is_integer(count), do: count
+  defp token_count(_), do: 0
+end
@@ -0,0 +1,23 @@
+defmodule Panko.Sessions.Workers.UsageBackfill do
+  @moduledoc """
+  Oban worker that caches the usage of sessions imported before it was
+  stored on the session row. Queued by `Panko.Sessions.Stats` when it
+  meets such a session.
+  """
+  use Oban.Worker, queue: :default, unique: [period: 300]
+
+  @impl Oban.Worker
+  def perform(_job), do: backfill()
+
+  # Each batch drops out of the :missing_usage read once cached.
+  defp backfill do
+    case Panko.Sessions.list_sessions_missing_usage!() do
+      [] ->
+        :ok
+
+      sessions ->
+        Enum.each(sessions, &Panko.Sessions.cache_session_usage!/1)
+        backfill()
+    end
+  end
+end
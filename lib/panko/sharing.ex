@@ -6,10 +6,15 @@ defmodule Panko.Sharing do
       define :create_share, action: :create, args: [:session_id]
       define :unpublish_share, action: :unpublish
       define :republish_share, action: :republish
+      define :record_share_view, action: :record_view
+      define :record_share_bytes, action: :record_bytes, args: [:bytes]
       define :get_share_by_slug, action: :by_slug, args: [:slug]
+      define :get_share, action: :read, get_by: [:slug]
       define :list_active_shares, action: :active
       define :find_share_for_session, action: :for_session, args: [:session_id]
       define :list_shared_session_ids, action: :shared_session_ids
     end
+
+    resource Panko.Sharing.ShareView
   end
 end
@@ -0,0 +1,15 @@
+defmodule Panko.Sharing.Changes.NotifyPublished do
+  @moduledoc """
+  Sends the `share_published` desktop notification with the share's URL
+  once it is created. See `Panko.Notifications`.
+  """
+  use Ash.Resource.Change
+
+  @impl true
+  def change(changeset, _opts, _context) do
+    Ash.Changeset.after_action(changeset, fn _changeset, share ->
+      Panko.Notifications.notify(:share_published, "Share published", Panko.share_url(share))
+      {:ok, share}
+    end)
+  end
+end
@@ -0,0 +1,24 @@
+defmodule Panko.Sharing.Changes.RecordVisitor do
+  @moduledoc """
+  Records the visitor given in the `visitor_hash` argument as a
+  `Panko.Sharing.ShareView` once the view has been counted.
+  """
+  use Ash.Resource.Change
+
+  @impl true
+  def change(changeset, _opts, _context) do
+    case Ash.Changeset.get_argument(changeset, :visitor_hash) do
+      nil ->
+        changeset
+
+      visitor_hash ->
+        Ash.Changeset.after_action(changeset, fn _changeset, share ->
+          Panko.Sharing.ShareView
+          |> Ash.Changeset.for_create(:record, %{share_id: share.id, visitor_hash: visitor_hash})
+          |> Ash.create!()
+
+          {:ok, share}
+        end)
+    end
+  end
+end
@@ -0,0 +1,29 @@
+defmodule Panko.Sharing.Changes.SetExpiry do
+  @moduledoc """
+  Sets `expires_at` from the `expires_in` argument, falling back to the
+  configured default expiry when neither is given.
+  """
+  use Ash.Resource.Change
+
+  alias Panko.Sharing.Expiry
+
+  @impl true
+  def change(changeset, _opts, _context) do
+    if Ash.Changeset.get_attribute(changeset, :expires_at) do
+      changeset
+    else
+      expires_in = Ash.Changeset.get_argument(changeset, :expires_in) || Expiry.default()
+
+      case Expiry.expires_at(expires_in) do
+        {:ok, expires_at} ->
+          Ash.Changeset.force_change_attribute(changeset, :expires_at, expires_at)
+
+        {:error, :invalid_expiry} ->
+          Ash.Changeset.add_error(changeset,
+            field: :expires_in,
+            message: "must be a duration like 12h, 7d or 2w, or never"
+          )
+      end
+    end
+  end
+end
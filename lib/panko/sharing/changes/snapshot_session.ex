@@ -0,0 +1,32 @@
+defmodule Panko.Sharing.Changes.SnapshotSession do
+  @moduledoc """
+  Freezes the share at the session's current length when the `snapshot`
+  argument is set (default: `PANKO_SHARE_SNAPSHOTS`), so blocks imported
+  after the share starts are not shown to its viewers.
+  """
+  use Ash.Resource.Change
+
+  require Ash.Query
+
+  @impl true
+  def change(changeset, _opts, _context) do
+    snapshot =
+      case Ash.Changeset.get_argument(changeset, :snapshot) do
+        nil -> Application.get_env(:panko, :share_snapshots, false)
+        snapshot -> snapshot
+      end
+
+    session_id = Ash.Changeset.get_attribute(changeset, :session_id)
+
+    if snapshot and session_id do
+      blocks =
+        Panko.Sessions.Block
+        |> Ash.Query.filter(session_id == ^session_id)
+        |> Ash.count!()
+
+      Ash.Changeset.force_change_attribute(changeset, :snapshot_blocks, blocks)
+    else
+      changeset
+    end
+  end
+end
@@ -0,0 +1,27 @@
+defmodule Panko.Sharing.Changes.SyncTeamRegistry do
+  @moduledoc """
+  Queues a `Panko.Sharing.Workers.TeamRegistrySync` job for the share once
+  the action succeeds, when a team registry is configured.
+  """
+  use Ash.Resource.Change
+
+  @impl true
+  def change(changeset, _opts, _context) do
+    if Panko.Sharing.TeamRegistry.url() do
+      Ash.Changeset.after_action(changeset, fn _changeset, share ->
+        {:ok, _job} =
+          %{share_id: share.id}
+          |> Panko.Sharing.Workers.TeamRegistrySync.new()
+          |> Oban.insert()
+
+        {:ok, share}
+      end)
+    else
+      changeset
+    end
+  end
+
+  # Only adds a hook, so updates stay atomic.
+  @impl true
+  def atomic(changeset, opts, context), do: {:ok, change(changeset, opts, context)}
+end
@@ -0,0 +1,47 @@
+defmodule Panko.Sharing.Expiry do
+  @moduledoc """
+  Parses share expiry durations such as `"30m"`, `"12h"`, `"7d"` or `"2w"`.
+
+  `"never"` (or an empty string) disables expiry.
+  """
+
+  @units %{"m" => 60, "h" => 3600, "d" => 86_400, "w" => 604_800}
+
+  @doc """
+  Parses a duration into seconds, or `:never`.
+  """
+  @spec parse(String.t() | nil) :: {:ok, pos_integer() | :never} | {:error, :invalid_expiry}
+  def parse(value) when value in [nil, "", "never"], do: {:ok, :never}
+
+  def parse(value) when is_binary(value) do
+    case Regex.run(~r/^\s*(\d+)\s*([mhdw])\s*$/, String.downcase(value)) do
+      [_, amount, unit] when amount != "0" ->
+        {:ok, String.to_integer(amount) * Map.fetch!(@units, unit)}
+
+      _ ->
+        {:error, :invalid_expiry}
+    end
+  end
+
+  def parse(_value), do: {:error, :invalid_expiry}
+
+  @doc """
+  Returns the expiry timestamp for a duration counted from `now`, or `nil`
+  when the duration is `"never"`.
+  """
+  @spec expires_at(String.t() | nil, DateTime.t()) ::
+          {:ok, DateTime.t() | nil} | {:error, :invalid_expiry}
+  def expires_at(value, now \\ DateTime.utc_now()) do
+    case parse(value) do
+      {:ok, :never} -> {:ok, nil}
+      {:ok, seconds} -> {:ok, now |> DateTime.add(seconds, :second) |> DateTime.truncate(:second)}
+      error -> error
+    end
+  end
+
+  @doc """
+  The configured default expiry, `PANKO_DEFAULT_EXPIRY` at runtime.
+  """
+  @spec default() :: String.t()
+  def default, do: Application.get_env(:panko, :default_share_expiry, "7d")
+end
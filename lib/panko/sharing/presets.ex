@@ -0,0 +1,86 @@
+defmodule Panko.Sharing.Presets do
+  @moduledoc """
+  Named sets of share options, so the same flags need not be repeated for
+  every share.
+
+  Presets are configured with `PANKO_SHARE_PRESETS`, separated by `;`,
+  each a name and comma-separated options:
+
+      demo=expires:2h,snapshot;review=expires:30d,live
+
+  Options are `expires:DURATION` (see `Panko.Sharing.Expiry`),
+  `snapshot` and `live`, the latter sharing the session as it grows even
+  when `PANKO_SHARE_SNAPSHOTS` is on.
+  """
+
+  alias Panko.Sharing.Expiry
+
+  @type params :: %{optional(:expires_in) => String.t(), optional(:snapshot) => boolean()}
+
+  @doc """
+  Parses a preset list into a map from name to share params.
+  """
+  @spec parse(String.t() | nil) :: {:ok, %{String.t() => params()}} | {:error, String.t()}
+  def parse(value) when value in [nil, ""], do: {:ok, %{}}
+
+  def parse(value) do
+    value
+    |> String.split(";", trim: true)
+    |> Enum.reduce_while({:ok, %{}}, fn preset, {:ok, presets} ->
+      case parse_preset(String.trim(preset)) do
+        {:ok, name, params} -> {:cont, {:ok, Map.put(presets, name, params)}}
+        {:error, message} -> {:halt, {:error, message}}
+      end
+    end)
+  end
+
+  @doc """
+  Returns the configured presets, ignoring an invalid configuration.
+  """
+  @spec all() :: %{String.t() => params()}
+  def all do
+    case parse(Application.get_env(:panko, :share_presets)) do
+      {:ok, presets} -> presets
+      {:error, _message} -> %{}
+    end
+  end
+
+  @doc """
+  Returns the share params of a configured preset.
+  """
+  @spec fetch(String.t()) :: {:ok, params()} | :error
+  def fetch(name), do: Map.fetch(all(), name)
+
+  defp parse_preset(preset) do
+    with [name, options] <- String.split(preset, "=", parts: 2),
+         name = String.trim(name),
+         true <- Regex.match?(~r/^[a-z0-9][a-z0-9_-]*$/, name) do
+      options
+      |> String.split(",", trim: true)
+      |> Enum.reduce_while({:ok, %{}}, fn option, {:ok, params} ->
+        case parse_option(String.trim(option)) do
+          {:ok, option_params} -> {:cont, {:ok, Map.merge(params, option_params)}}
+          :error -> {:halt, {:error, "unknown option #{inspect(option)} in preset #{name}"}}
+        end
+      end)
+      |> case do
+        {:ok, params} -> {:ok, name, params}
+        error -> error
+      end
+    else
+      _ -> {:error, "invalid preset #{inspect(preset)}, expected NAME=OPTION,..."}
+    end
+  end
+
+  defp parse_option("snapshot"), do: {:ok, %{snapshot: true}}
+  defp parse_option("live"), do: {:ok, %{snapshot: false}}
+
+  defp parse_option("expires:" <> duration) do
+    case Expiry.parse(duration) do
+      {:ok, _seconds} -> {:ok, %{expires_in: String.trim(duration)}}
+      {:error, _} -> :error
+    end
+  end
+
+  defp parse_option(_option), do: :error
+end
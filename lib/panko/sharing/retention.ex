@@ -0,0 +1,62 @@
+defmodule Panko.Sharing.Retention do
+  @moduledoc """
+  Deletes stopped shares so the shares table does not grow without bound.
+
+  A share is stopped once it has been unpublished or has expired. Stopped
+  shares are deleted when they were stopped longer ago than
+  `max_stopped_share_age`, and only the newest `max_share_history` stopped
+  shares are kept. Both limits are off unless configured, so nothing is
+  deleted by default. Active shares are never touched.
+  """
+
+  alias Panko.Sharing.Expiry
+  alias Panko.Sharing.Share
+
+  require Ash.Query
+
+  @doc """
+  Deletes stopped shares outside the retention policy and returns them.
+
+  ## Options
+
+    * `:max_age` - a duration such as `"30d"`, or `"never"` to keep shares
+      regardless of age (default: the `:max_stopped_share_age` config)
+    * `:max_history` - number of stopped shares to keep, or `nil` for no
+      limit (default: the `:max_share_history` config)
+    * `:dry_run` - only return the shares that would be deleted
+    * `:now` - the current time, for testing
+  """
+  @spec prune(keyword()) :: {:ok, [Share.t()]} | {:error, :invalid_expiry}
+  def prune(opts \\ []) do
+    max_age = Keyword.get(opts, :max_age, Application.get_env(:panko, :max_stopped_share_age))
+    max_history = Keyword.get(opts, :max_history, Application.get_env(:panko, :max_share_history))
+    now = Keyword.get(opts, :now, DateTime.utc_now())
+
+    with {:ok, age} <- Expiry.parse(max_age) do
+      stopped =
+        Share
+        |> Ash.Query.filter(is_shared == false)
+        |> Ash.Query.sort(unshared_at: :desc_nils_last, updated_at: :desc)
+        |> Ash.read!()
+
+      prunable =
+        stopped
+        |> Enum.with_index()
+        |> Enum.filter(fn {share, index} ->
+          too_old?(share, age, now) or (is_integer(max_history) and index >= max_history)
+        end)
+        |> Enum.map(&elem(&1, 0))
+
+      unless opts[:dry_run], do: Enum.each(prunable, &Ash.destroy!/1)
+
+      {:ok, prunable}
+    end
+  end
+
+  defp too_old?(_share, :never, _now), do: false
+
+  defp too_old?(share, age, now) do
+    stopped_at = share.unshared_at || share.expires_at || share.updated_at
+    DateTime.diff(now, stopped_at) > age
+  end
+end
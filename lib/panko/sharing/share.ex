@@ -1,13 +1,25 @@
 defmodule Panko.Sharing.Share do
   use Ash.Resource,
     domain: Panko.Sharing,
-    data_layer: AshPostgres.DataLayer
+    data_layer: AshPostgres.DataLayer,
+    notifiers: [Ash.Notifier.PubSub]
 
   postgres do
     table "shares"
     repo Panko.Repo
   end
 
+  # Every share change (creation, unpublish/republish, expiry, views) is
+  # broadcast per session for open viewers and globally for the dashboard.
+  pub_sub do
+    module PankoWeb.Endpoint
+    prefix "shares"
+    publish_all :create, ["session", :session_id]
+    publish_all :update, ["session", :session_id]
+    publish_all :create, ["changed"]
+    publish_all :update, ["changed"]
+  end
+
   attributes do
     uuid_primary_key :id
 
@@ -37,6 +49,30 @@ defmodule Panko.Sharing.Share do
       public? true
     end
 
+    attribute :view_count, :integer do
+      allow_nil? false
+      default 0
+      public? true
+    end
+
+    attribute :last_viewed_at, :utc_datetime do
+      allow_nil? true
+      public? true
+    end
+
+    # Size of the share page responses, see `PankoWeb.Plugs.ShareTraffic`.
+    attribute :bytes_served, :integer do
+      allow_nil? false
+      default 0
+      public? true
+    end
+
+    # Set for snapshot shares: only blocks before this position are shown.
+    attribute :snapshot_blocks, :integer do
+      allow_nil? true
+      public? true
+    end
+
     attribute :user_id, :uuid do
       allow_nil? true
       public? true
@@ -51,6 +87,38 @@ defmodule Panko.Sharing.Share do
       allow_nil? false
       public? true
     end
+
+    has_many :views, Panko.Sharing.ShareView do
+      public? true
+    end
+  end
+
+  aggregates do
+    count :unique_visitors, :views do
+      public? true
+    end
+  end
+
+  calculations do
+    # Expired shares stay published until the ShareReaper's next run.
+    calculate :status,
+              :atom,
+              expr(
+                cond do
+                  not is_nil(expires_at) and expires_at <= now() and
+                      (is_shared or unshared_at >= expires_at) ->
+                    :expired
+
+                  is_shared ->
+                    :active
+
+                  true ->
+                    :stopped
+                end
+              ) do
+      constraints one_of: [:active, :expired, :stopped]
+      public? true
+    end
   end
 
   identities do
@@ -62,21 +130,54 @@ defmodule Panko.Sharing.Share do
 
     create :create do
       primary? true
-      accept [:session_id, :expires_at]
+      accept [:session_id, :slug, :expires_at]
+      argument :expires_in, :string, allow_nil?: true
+      argument :snapshot, :boolean, allow_nil?: true
+
+      validate match(:slug, ~r/^[a-z0-9][a-z0-9-]{1,62}[a-z0-9]$/),
+        message: "must be 3-64 lowercase letters, digits or dashes"
+
       change {Panko.Sharing.Changes.GenerateSlug, []}
+      change {Panko.Sharing.Changes.SetExpiry, []}
+      change {Panko.Sharing.Changes.SnapshotSession, []}
       change set_attribute(:shared_at, &DateTime.utc_now/0)
+      change {Panko.Sharing.Changes.SyncTeamRegistry, []}
+      change {Panko.Sharing.Changes.NotifyPublished, []}
     end
 
     update :unpublish do
       accept []
       change set_attribute(:is_shared, false)
       change set_attribute(:unshared_at, &DateTime.utc_now/0)
+      change {Panko.Sharing.Changes.SyncTeamRegistry, []}
     end
 
+    # Republishing starts a new expiry, or an expired share would be
+    # reported expired and reaped again right away.
     update :republish do
       accept []
+      argument :expires_in, :string, allow_nil?: true
+      require_atomic? false
       change set_attribute(:is_shared, true)
       change set_attribute(:unshared_at, nil)
+      change set_attribute(:expires_at, nil)
+      change {Panko.Sharing.Changes.SetExpiry, []}
+      change {Panko.Sharing.Changes.SyncTeamRegistry, []}
+    end
+
+    update :record_view do
+      accept []
+      argument :visitor_hash, :string, allow_nil?: true
+      require_atomic? false
+      change atomic_update(:view_count, expr(view_count + 1))
+      change set_attribute(:last_viewed_at, &DateTime.utc_now/0)
+      change {Panko.Sharing.Changes.RecordVisitor, []}
+    end
+
+    update :record_bytes do
+      accept []
+      argument :bytes, :integer, allow_nil?: false, constraints: [min: 0]
+      change atomic_update(:bytes_served, expr(bytes_served + ^arg(:bytes)))
     end
 
     read :by_slug do
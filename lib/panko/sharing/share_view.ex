@@ -0,0 +1,79 @@
+defmodule Panko.Sharing.ShareView do
+  @moduledoc """
+  A distinct visitor of a share.
+
+  Visitors are identified by a salted hash of their IP address, so unique
+  visitors can be counted without storing addresses. Each visit upserts
+  the visitor's row and bumps `last_viewed_at`.
+  """
+  use Ash.Resource,
+    domain: Panko.Sharing,
+    data_layer: AshPostgres.DataLayer
+
+  postgres do
+    table "share_views"
+    repo Panko.Repo
+
+    references do
+      reference :share, on_delete: :delete
+    end
+  end
+
+  attributes do
+    uuid_primary_key :id
+
+    attribute :visitor_hash, :string do
+      allow_nil? false
+      public? true
+    end
+
+    attribute :first_viewed_at, :utc_datetime do
+      allow_nil? false
+      public? true
+    end
+
+    attribute :last_viewed_at, :utc_datetime do
+      allow_nil? false
+      public? true
+    end
+  end
+
+  relationships do
+    belongs_to :share, Panko.Sharing.Share do
+      allow_nil? false
+      public? true
+    end
+  end
+
+  identities do
+    identity :unique_visitor, [:share_id, :visitor_hash]
+  end
+
+  actions do
+    defaults [:read, :destroy]
+
+    create :record do
+      accept [:share_id, :visitor_hash]
+      upsert? true
+      upsert_identity :unique_visitor
+      upsert_fields [:last_viewed_at]
+      change set_attribute(:first_viewed_at, &DateTime.utc_now/0)
+      change set_attribute(:last_viewed_at, &DateTime.utc_now/0)
+    end
+  end
+
+  @doc """
+  Hashes a visitor's IP address with the endpoint secret as salt.
+  """
+  @spec visitor_hash(:inet.ip_address() | String.t()) :: String.t()
+  def visitor_hash(ip) when is_tuple(ip), do: ip |> :inet.ntoa() |> to_string() |> visitor_hash()
+
+  def visitor_hash(ip) when is_binary(ip) do
+    salt = PankoWeb.Endpoint.config(:secret_key_base) || ""
+
+    :sha256
+    |> :crypto.hash([salt, ip])
+    |> Base.encode16(case: :lower)
+    |> binary_part(0, 32)
+  end
+end
@@ -0,0 +1,95 @@
+defmodule Panko.Sharing.TeamRegistry do
+  @moduledoc """
+  Announces shares to a team endpoint, so teammates can find each other's
+  live shares.
+
+  When `PANKO_TEAM_REGISTRY_URL` is set, every share that starts, stops or
+  restarts is POSTed there as JSON (see `entry/1`) by
+  `Panko.Sharing.Workers.TeamRegistrySync`. A GET on the same URL must
+  return a JSON array of such entries, which `mix panko.shares browse`
+  lists. `PANKO_TEAM_REGISTRY_TOKEN`, when set, is sent as a bearer token.
+  """
+
+  @doc """
+  Returns the registry URL, or nil when syncing is off.
+  """
+  @spec url() :: String.t() | nil
+  def url, do: Application.get_env(:panko, :team_registry_url)
+
+  @doc """
+  Builds the entry announced for a share with its session loaded.
+  """
+  @spec entry(Panko.Sharing.Share.t()) :: map()
+  def entry(share) do
+    %{
+      slug: share.slug,
+      url: PankoWeb.Endpoint.url() <> "/s/#{share.slug}",
+      title: share.session.title,
+      owner: Application.get_env(:panko, :team_owner),
+      origin_id: Application.get_env(:panko, :instance_origin_id),
+      active: share.is_shared,
+      shared_at: share.shared_at,
+      expires_at: share.expires_at
+    }
+  end
+
+  @doc """
+  POSTs an entry to the registry.
+  """
+  @spec publish(map()) :: :ok | {:error, term()}
+  def publish(entry) do
+    with {:ok, _body} <- request(:post, Jason.encode!(entry)), do: :ok
+  end
+
+  @doc """
+  Fetches the registry and returns the entries that are live at `now`.
+  """
+  @spec live_shares(DateTime.t()) :: {:ok, [map()]} | {:error, term()}
+  def live_shares(now \\ DateTime.utc_now()) do
+    with {:ok, body} <- request(:get, nil),
+         {:ok, entries} when is_list(entries) <- Jason.decode(body) do
+      {:ok, Enum.filter(entries, &live?(&1, now))}
+    else
+      {:ok, _other} -> {:error, :invalid_response}
+      {:error, reason} -> {:error, reason}
+    end
+  end
+
+  @doc false
+  def live?(%{"active" => true} = entry, now) do
+    case entry["expires_at"] && DateTime.from_iso8601(entry["expires_at"]) do
+      {:ok, expires_at, _offset} -> DateTime.compare(expires_at, now) == :gt
+      _ -> true
+    end
+  end
+
+  def live?(_entry, _now), do: false
+
+  defp request(method, body) do
+    case url() do
+      nil -> {:error, :not_configured}
+      url -> http(method, String.to_charlist(url), body)
+    end
+  end
+
+  defp http(method, url, body) do
+    headers =
+      case Application.get_env(:panko, :team_registry_token) do
+        nil -> []
+        token -> [{~c"authorization", String.to_charlist("Bearer " <> token)}]
+      end
+
+    request =
+      if body,
+        do: {url, headers, ~c"application/json", body},
+        else: {url, headers}
+
+    options = Panko.HTTP.options(timeout: 10_000)
+
+    case :httpc.request(method, request, options, body_format: :binary) do
+      {:ok, {{_, status, _}, _headers, body}} when status in 200..299 -> {:ok, body}
+      {:ok, {{_, status, _}, _headers, _body}} -> {:error, {:http_status, status}}
+      {:error, reason} -> {:error, reason}
+    end
+  end
+end
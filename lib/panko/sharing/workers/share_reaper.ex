@@ -3,13 +3,16 @@ defmodule Panko.Sharing.Workers.ShareReaper do
   Oban worker that periodically reaps expired shares.
 
   Runs on a cron schedule (hourly by default) and unpublishes any
-  shares whose `expires_at` timestamp has passed.
+  shares whose `expires_at` timestamp has passed, then prunes stopped
+  shares according to `Panko.Sharing.Retention`, which keeps every share
+  unless a retention limit is configured.
   """
   use Oban.Worker, queue: :shares
 
   alias Panko.Sharing.Share
 
   require Ash.Query
+  require Logger
 
   @impl Oban.Worker
   def perform(_job) do
@@ -21,9 +24,18 @@ defmodule Panko.Sharing.Workers.ShareReaper do
       |> Ash.read!()
 
     for share <- expired_shares do
-      Panko.Sharing.unpublish_share(share)
+      with {:ok, share} <- Panko.Sharing.unpublish_share(share) do
+        Panko.Notifications.notify(:share_expired, "Share expired", Panko.share_url(share))
+      end
     end
 
-    :ok
+    case Panko.Sharing.Retention.prune() do
+      {:ok, _pruned} ->
+        :ok
+
+      {:error, reason} ->
+        Logger.warning("Could not prune stopped shares: #{inspect(reason)}")
+        :ok
+    end
   end
 end
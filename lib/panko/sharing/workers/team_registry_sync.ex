@@ -0,0 +1,17 @@
+defmodule Panko.Sharing.Workers.TeamRegistrySync do
+  @moduledoc """
+  Oban worker that announces a share's current state to the team
+  registry, retrying while the registry is unreachable.
+  """
+  use Oban.Worker, queue: :shares, max_attempts: 5
+
+  alias Panko.Sharing.TeamRegistry
+
+  @impl Oban.Worker
+  def perform(%Oban.Job{args: %{"share_id" => share_id}}) do
+    case Ash.get(Panko.Sharing.Share, share_id, load: [:session]) do
+      {:ok, share} -> share |> TeamRegistry.entry() |> TeamRegistry.publish()
+      {:error, _} -> {:cancel, :share_deleted}
+    end
+  end
+end
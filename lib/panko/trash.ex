@@ -0,0 +1,85 @@
+defmodule Panko.Trash do
+  @moduledoc """
+  Moves files to the desktop trash instead of deleting them.
+
+  On macOS files go to `~/.Trash`. Elsewhere the freedesktop.org trash in
+  `$XDG_DATA_HOME/Trash` (usually `~/.local/share/Trash`) is used, which
+  file managers on Linux and the BSDs show and can restore from. Windows
+  has no trash directory Panko can write to, so `move/2` fails there.
+  """
+
+  @doc """
+  Moves `path` to the trash, or to the freedesktop.org style trash rooted
+  at `trash_dir` when given.
+
+  Names already in the trash get a numeric suffix.
+  """
+  @spec move(String.t(), String.t() | nil) :: :ok | {:error, term()}
+  def move(path, trash_dir \\ nil) do
+    cond do
+      not File.exists?(path) -> {:error, :enoent}
+      trash_dir -> move_freedesktop(path, trash_dir)
+      match?({:unix, :darwin}, :os.type()) -> move_macos(path)
+      match?({:unix, _}, :os.type()) -> move_freedesktop(path, default_dir())
+      true -> {:error, :trash_unsupported}
+    end
+  end
+
+  defp default_dir do
+    data_home = System.get_env("XDG_DATA_HOME") || Path.expand("~/.local/share")
+    Path.join(data_home, "Trash")
+  end
+
+  defp move_macos(path) do
+    trash = Path.expand("~/.Trash")
+    rename(path, unique_path(trash, Path.basename(path)))
+  end
+
+  # The .trashinfo file is what lets file managers restore the file.
+  defp move_freedesktop(path, trash) do
+    files = Path.join(trash, "files")
+    info = Path.join(trash, "info")
+    target = unique_path(files, Path.basename(path), info)
+    name = Path.basename(target)
+
+    deleted_at =
+      NaiveDateTime.local_now() |> NaiveDateTime.truncate(:second) |> NaiveDateTime.to_iso8601()
+
+    trashinfo = """
+    [Trash Info]
+    Path=#{URI.encode(Path.expand(path))}
+    DeletionDate=#{deleted_at}
+    """
+
+    with :ok <- File.mkdir_p(files),
+         :ok <- File.mkdir_p(info),
+         :ok <- File.write(Path.join(info, name <> ".trashinfo"), trashinfo) do
+      rename(path, target)
+    end
+  end
+
+  defp unique_path(dir, name, info_dir \\ nil) do
+    ext = Path.extname(name)
+    base = Path.rootname(name)
+
+    Stream.iterate(0, &(&1 + 1))
+    |> Stream.map(fn
+      0 -> name
+      n -> "#{base}.#{n}#{ext}"
+    end)
+    |> Enum.find(fn candidate ->
+      not File.exists?(Path.join(dir, candidate)) and
+        (is_nil(info_dir) or not File.exists?(Path.join(info_dir, candidate <> ".trashinfo")))
+    end)
+    |> then(&Path.join(dir, &1))
+  end
+
+  defp rename(source, target) do
+    with :ok <- File.mkdir_p(Path.dirname(target)) do
+      case File.rename(source, target) do
+        {:error, :exdev} -> with {:ok, _} <- File.copy(source, target), do: File.rm(source)
+        result -> result
+      end
+    end
+  end
+end
@@ -0,0 +1,41 @@
+defmodule PankoWeb.ClientIP do
+  @moduledoc """
+  Finds the address of the client behind a request.
+
+  `X-Forwarded-For` is only honored when the peer is one of the proxies
+  in `PANKO_TRUSTED_PROXIES`; anyone else could send the header to pose
+  as another client. The client is then the last forwarded address that
+  is not itself a trusted proxy, as earlier entries are whatever the
+  client sent.
+  """
+
+  @doc """
+  Returns the client address given the peer's address and the values of
+  the `X-Forwarded-For` headers, as a tuple or, for a forwarded client,
+  a string.
+  """
+  @spec from(:inet.ip_address() | nil, [String.t()], [String.t()]) ::
+          :inet.ip_address() | String.t() | nil
+  def from(peer, forwarded, trusted \\ Application.get_env(:panko, :trusted_proxies, [])) do
+    trusted = Enum.flat_map(trusted, &parse/1)
+
+    if peer in trusted do
+      forwarded
+      |> Enum.flat_map(&String.split(&1, ","))
+      |> Enum.map(&String.trim/1)
+      |> Enum.reverse()
+      |> Enum.find(peer, &(not trusted?(&1, trusted)))
+    else
+      peer
+    end
+  end
+
+  defp trusted?(address, trusted), do: Enum.any?(parse(address), &(&1 in trusted))
+
+  defp parse(address) do
+    case :inet.parse_address(String.to_charlist(address)) do
+      {:ok, ip} -> [ip]
+      {:error, _} -> []
+    end
+  end
+end
@@ -0,0 +1,46 @@
+defmodule PankoWeb.Components.AgentBadge do
+  @moduledoc """
+  Tags sessions with the agent that recorded them, each in its own theme
+  color so mixed session lists can be told apart at a glance. The same
+  colors mark the agent filter on the sessions page, which doubles as
+  their legend.
+  """
+  use Phoenix.Component
+
+  @agents [
+    claude_code: {"claude", "badge-primary"},
+    codex: {"codex", "badge-info"},
+    imported: {"imported", "badge-neutral"}
+  ]
+
+  @doc """
+  Returns the agents in display order.
+  """
+  @spec agents() :: [atom()]
+  def agents, do: Keyword.keys(@agents)
+
+  @doc """
+  Returns the short label of an agent.
+  """
+  @spec label(atom()) :: String.t()
+  def label(agent), do: agent |> info() |> elem(0)
+
+  @doc """
+  Returns the badge color class of an agent.
+  """
+  @spec color(atom()) :: String.t()
+  def color(agent), do: agent |> info() |> elem(1)
+
+  attr :agent, :atom, required: true
+  attr :class, :any, default: nil
+
+  def agent_badge(assigns) do
+    ~H"""
+    <span class={["badge badge-xs badge-soft font-mono shrink-0", color(@agent), @class]}>
+      {label(@agent)}
+    </span>
+    """
+  end
+
+  defp info(agent), do: Keyword.get(@agents, agent, {to_string(agent), "badge-ghost"})
+end
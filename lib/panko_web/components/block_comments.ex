@@ -0,0 +1,77 @@
+defmodule PankoWeb.Components.BlockComments do
+  @moduledoc """
+  Renders the comments on a block and, when commenting is allowed, a form
+  to add one. Events go to `target`, which handles `"comment"` and
+  `"delete_comment"`.
+  """
+  use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
+
+  attr :comments, :list, required: true
+  attr :position, :integer, required: true
+  attr :target, :any, required: true
+  attr :commenting, :boolean, default: false
+  attr :can_delete, :boolean, default: false
+  attr :ask_name, :boolean, default: false
+  attr :form_key, :integer, default: 0
+
+  def block_comments(assigns) do
+    ~H"""
+    <div :if={@comments != [] or @commenting} class="-mt-3 mb-4 ml-4 space-y-2">
+      <div
+        :for={comment <- @comments}
+        id={"comment-#{comment.id}"}
+        class="flex items-start gap-2 text-sm border-l-2 border-info pl-3"
+      >
+        <div class="flex-1">
+          <span class="font-semibold">{comment.author || gettext("Anonymous")}</span>
+          <time class="text-xs text-base-content/50 ml-1">
+            {Calendar.strftime(comment.inserted_at, "%Y-%m-%d %H:%M")}
+          </time>
+          <p class="whitespace-pre-wrap">{comment.body}</p>
+        </div>
+        <button
+          :if={@can_delete}
+          phx-click="delete_comment"
+          phx-value-id={comment.id}
+          phx-target={@target}
+          class="btn btn-ghost btn-xs"
+          title={gettext("Delete comment")}
+        >
+          <PankoWeb.CoreComponents.icon name="hero-trash-micro" class="size-3" />
+        </button>
+      </div>
+
+      <details :if={@commenting} class="text-sm">
+        <summary class="cursor-pointer text-base-content/50 hover:text-base-content">
+          {gettext("Comment")}
+        </summary>
+        <form
+          id={"comment-form-#{@position}-#{@form_key}"}
+          phx-submit="comment"
+          phx-target={@target}
+          class="mt-2 space-y-2"
+        >
+          <input type="hidden" name="position" value={@position} />
+          <input
+            :if={@ask_name}
+            type="text"
+            name="name"
+            maxlength="80"
+            placeholder={gettext("Your name (optional)")}
+            class="input input-bordered input-sm w-full"
+          />
+          <textarea
+            name="body"
+            maxlength="2000"
+            required
+            rows="2"
+            class="textarea textarea-bordered w-full"
+          ></textarea>
+          <button type="submit" class="btn btn-primary btn-xs">{gettext("Add comment")}</button>
+        </form>
+      </details>
+    </div>
+    """
+  end
+end
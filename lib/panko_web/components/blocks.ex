@@ -10,19 +10,35 @@ defmodule PankoWeb.Components.Blocks do
     SubAgentSpawn
   }
 
-  attr :block, :map, required: true
-
-  def block(%{block: %{block_type: :user_prompt}} = assigns), do: UserPrompt.render(assigns)
+  @renderers %{
+    user_prompt: UserPrompt,
+    assistant_response: AssistantResponse,
+    tool_call: ToolCall,
+    thinking: Thinking,
+    file_edit: FileEdit,
+    sub_agent_spawn: SubAgentSpawn
+  }
 
-  def block(%{block: %{block_type: :assistant_response}} = assigns),
-    do: AssistantResponse.render(assigns)
+  attr :block, :map, required: true
+  attr :editor, :string, default: nil, doc: "see `PankoWeb.Components.EditorLink`"
 
-  def block(%{block: %{block_type: :tool_call}} = assigns), do: ToolCall.render(assigns)
-  def block(%{block: %{block_type: :thinking}} = assigns), do: Thinking.render(assigns)
-  def block(%{block: %{block_type: :file_edit}} = assigns), do: FileEdit.render(assigns)
+  def block(assigns) do
+    case renderer(assigns.block) do
+      nil -> ~H""
+      renderer -> renderer.render(assigns)
+    end
+  end
 
-  def block(%{block: %{block_type: :sub_agent_spawn}} = assigns),
-    do: SubAgentSpawn.render(assigns)
+  @doc """
+  Returns the `PankoWeb.Components.Blocks.Renderer` for a block: a
+  configured renderer for its tool, then for its type, then the built-in
+  one.
+  """
+  @spec renderer(map(), map()) :: module() | nil
+  def renderer(block, custom \\ Application.get_env(:panko, :block_renderers, %{})) do
+    name = block.metadata && block.metadata["name"]
 
-  def block(assigns), do: ~H""
+    Map.get(custom, {block.block_type, name}) || Map.get(custom, block.block_type) ||
+      Map.get(@renderers, block.block_type)
+  end
 end
@@ -1,17 +1,21 @@
 defmodule PankoWeb.Components.Blocks.AssistantResponse do
   use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
+
+  @behaviour PankoWeb.Components.Blocks.Renderer
 
   alias PankoWeb.Components.ContentRenderer
 
   attr :block, :map, required: true
 
+  @impl true
   def render(assigns) do
     assigns = assign(assigns, :segments, ContentRenderer.parse_content(assigns.block.content))
 
     ~H"""
     <article class="block border border-base-300 rounded-lg mb-4 border-l-4 border-l-success overflow-hidden">
       <div class="flex items-center justify-between px-4 py-2 bg-base-200/50 border-b border-base-300">
-        <span class="text-sm font-semibold text-success">Assistant</span>
+        <span class="text-sm font-semibold text-success">{gettext("Assistant")}</span>
         <time :if={@block.timestamp} class="text-xs text-base-content/50">
           {format_time(@block.timestamp)}
         </time>
@@ -44,7 +48,7 @@ defmodule PankoWeb.Components.Blocks.AssistantResponse do
 
     ~H"""
     <div class="bg-base-200 rounded-md px-3 py-2 my-2 font-mono text-sm border border-base-300">
-      <div class="text-xs text-base-content/50 mb-1 font-sans">Command</div>
+      <div class="text-xs text-base-content/50 mb-1 font-sans">{gettext("Command")}</div>
       <div class="whitespace-pre-wrap">{@content}</div>
     </div>
     """
@@ -1,6 +1,14 @@
 defmodule PankoWeb.Components.Blocks.FileEdit do
   use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
 
+  @behaviour PankoWeb.Components.Blocks.Renderer
+
+  import PankoWeb.Components.EditorLink, only: [editor_link: 1]
+
+  alias Panko.Sessions.ToolTiming
+
+  @impl true
   def render(assigns) do
     file_path = get_file_path(assigns.block.metadata)
     tool_name = tool_name(assigns.block.metadata)
@@ -10,6 +18,7 @@ defmodule PankoWeb.Components.Blocks.FileEdit do
       |> assign(:file_path, file_path)
       |> assign(:tool_name, tool_name)
       |> assign(:formatted_input, format_input(assigns.block.metadata))
+      |> assign(:duration_ms, ToolTiming.duration_ms(assigns.block))
 
     ~H"""
     <article class="block border border-base-300 rounded-lg mb-4 border-l-4 border-l-error overflow-hidden">
@@ -18,13 +27,23 @@ defmodule PankoWeb.Components.Blocks.FileEdit do
           <span class="badge badge-error badge-sm mr-2">{@tool_name}</span>
           <span class="font-mono text-xs">{@file_path}</span>
         </span>
-        <time :if={@block.timestamp} class="text-xs text-base-content/50">
-          {format_time(@block.timestamp)}
-        </time>
+        <span class="flex items-center gap-2">
+          <.editor_link editor={@editor} path={@file_path} />
+          <span
+            :if={@duration_ms}
+            class="badge badge-ghost badge-xs"
+            title={gettext("Time until the result")}
+          >
+            {ToolTiming.format(@duration_ms)}
+          </span>
+          <time :if={@block.timestamp} class="text-xs text-base-content/50">
+            {format_time(@block.timestamp)}
+          </time>
+        </span>
       </div>
       <details open>
         <summary class="px-4 py-2 cursor-pointer bg-base-200/30 text-sm text-base-content/70 hover:bg-base-200/60">
-          Content
+          {gettext("Content")}
         </summary>
         <pre class="text-xs overflow-x-auto p-4 bg-base-300/30"><code>{@formatted_input}</code></pre>
       </details>
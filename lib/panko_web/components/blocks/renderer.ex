@@ -0,0 +1,17 @@
+defmodule PankoWeb.Components.Blocks.Renderer do
+  @moduledoc """
+  Behaviour for block renderers.
+
+  A renderer receives the assigns of `PankoWeb.Components.Blocks.block/1`
+  (`:block` and `:editor`) and returns HEEx. Renderers are looked up by
+  block type, or by `{block_type, tool_name}` for tool blocks, so
+  individual tools such as `TodoWrite` or an MCP server's calls can get
+  their own rendering. Register extra renderers in config:
+
+      config :panko, :block_renderers, %{
+        {:tool_call, "TodoWrite"} => MyApp.TodoRenderer
+      }
+  """
+
+  @callback render(assigns :: map()) :: Phoenix.LiveView.Rendered.t()
+end
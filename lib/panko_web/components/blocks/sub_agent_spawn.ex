@@ -1,6 +1,10 @@
 defmodule PankoWeb.Components.Blocks.SubAgentSpawn do
   use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
 
+  @behaviour PankoWeb.Components.Blocks.Renderer
+
+  @impl true
   def render(assigns) do
     assigns =
       assigns
@@ -12,7 +16,7 @@ defmodule PankoWeb.Components.Blocks.SubAgentSpawn do
       <div class="flex items-center justify-between px-4 py-2 bg-base-200/50 border-b border-base-300">
         <span class="text-sm font-semibold text-accent">
           <span class={["badge badge-sm mr-2", agent_badge_class(@agent_type)]}>{@agent_type}</span>
-          Sub-Agent
+          {gettext("Sub-Agent")}
         </span>
         <time :if={@block.timestamp} class="text-xs text-base-content/50">
           {format_time(@block.timestamp)}
@@ -1,12 +1,16 @@
 defmodule PankoWeb.Components.Blocks.Thinking do
   use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
 
+  @behaviour PankoWeb.Components.Blocks.Renderer
+
+  @impl true
   def render(assigns) do
     ~H"""
     <article class="block border border-base-300 rounded-lg mb-4 border-l-4 border-l-secondary opacity-85 overflow-hidden">
-      <details class="group">
+      <details class="group" aria-label={gettext("Thinking")}>
         <summary class="flex items-center justify-between px-4 py-2 cursor-pointer bg-base-200/30 hover:bg-base-200/60">
-          <span class="text-sm font-semibold text-secondary italic">Thinking</span>
+          <span class="text-sm font-semibold text-secondary italic">{gettext("Thinking")}</span>
           <time :if={@block.timestamp} class="text-xs text-base-content/50">
             {format_time(@block.timestamp)}
           </time>
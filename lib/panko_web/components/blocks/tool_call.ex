@@ -1,8 +1,16 @@
 defmodule PankoWeb.Components.Blocks.ToolCall do
   use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
+
+  @behaviour PankoWeb.Components.Blocks.Renderer
+
+  import PankoWeb.Components.EditorLink, only: [editor_link: 1, input_path: 1]
+
+  alias Panko.Sessions.ToolTiming
 
   @important_tools ~w(Write Edit Bash Read NotebookEdit)
 
+  @impl true
   def render(assigns) do
     tool_name = assigns.block.metadata["name"] || "Tool"
     input = encode_metadata(assigns.block.metadata["input"])
@@ -14,51 +22,68 @@ defmodule PankoWeb.Components.Blocks.ToolCall do
     assigns =
       assigns
       |> assign(:tool_name, tool_name)
+      |> assign(:file_path, input_path(assigns.block.metadata["input"]))
       |> assign(:encoded_input, input)
       |> assign(:encoded_output, output)
       |> assign(:output_lines, output_lines)
       |> assign(:is_important, is_important)
       |> assign(:is_large, is_large)
+      |> assign(:duration_ms, ToolTiming.duration_ms(assigns.block))
 
     ~H"""
     <article class="block border border-base-300 rounded-lg mb-4 border-l-4 border-l-warning overflow-hidden">
       <div class="flex items-center justify-between px-4 py-2 bg-base-200/50 border-b border-base-300">
         <span class="text-sm font-semibold text-warning">
-          Tool: <span class="font-mono">{@tool_name}</span>
+          {gettext("Tool:")} <span class="font-mono">{@tool_name}</span>
+        </span>
+        <span class="flex items-center gap-2">
+          <.editor_link editor={@editor} path={@file_path} />
+          <time :if={@block.timestamp} class="text-xs text-base-content/50">
+            {format_time(@block.timestamp)}
+          </time>
         </span>
-        <time :if={@block.timestamp} class="text-xs text-base-content/50">
-          {format_time(@block.timestamp)}
-        </time>
       </div>
-      <details class="group" open={@is_important}>
+      <details class="group" open={@is_important} aria-label={gettext("Tool input")}>
         <summary class="flex items-center justify-between px-4 py-2 cursor-pointer bg-base-200/30 border-b border-base-300 text-sm text-base-content/70 hover:bg-base-200/60">
-          <span>Input</span>
+          <span>{gettext("Input")}</span>
           <button
             type="button"
             phx-click={copy_to_clipboard("tool-input-#{@block.id}")}
             class="btn btn-ghost btn-xs"
-            title="Copy input"
+            title={gettext("Copy input")}
           >
-            <.icon name="hero-clipboard-micro" class="size-3" /> Copy
+            <.icon name="hero-clipboard-micro" class="size-3" /> {gettext("Copy")}
           </button>
         </summary>
         <pre id={"tool-input-#{@block.id}"} class="text-xs overflow-x-auto p-4 bg-base-300/30"><code>{@encoded_input}</code></pre>
       </details>
-      <details :if={@encoded_output != ""} class="group" open={@is_important && !@is_large}>
+      <details
+        :if={@encoded_output != ""}
+        class="group"
+        open={@is_important && !@is_large}
+        aria-label={gettext("Tool output")}
+      >
         <summary class="flex items-center justify-between px-4 py-2 cursor-pointer bg-base-200/30 border-b border-base-300 text-sm text-base-content/70 hover:bg-base-200/60">
           <span>
-            Output
+            {gettext("Output")}
             <span :if={@is_large} class="text-xs text-base-content/40 ml-1">
-              ({@output_lines} lines)
+              ({ngettext("1 line", "%{count} lines", @output_lines)})
+            </span>
+            <span
+              :if={@duration_ms}
+              class="badge badge-ghost badge-xs ml-1"
+              title={gettext("Time until the result")}
+            >
+              {ToolTiming.format(@duration_ms)}
             </span>
           </span>
           <button
             type="button"
             phx-click={copy_to_clipboard("tool-output-#{@block.id}")}
             class="btn btn-ghost btn-xs"
-            title="Copy output"
+            title={gettext("Copy output")}
           >
-            <.icon name="hero-clipboard-micro" class="size-3" /> Copy
+            <.icon name="hero-clipboard-micro" class="size-3" /> {gettext("Copy")}
           </button>
         </summary>
         <div
@@ -71,9 +96,10 @@ defmodule PankoWeb.Components.Blocks.ToolCall do
             <button
               type="button"
               phx-click={show_full_output("tool-output-wrap-#{@block.id}")}
+              aria-controls={"tool-output-wrap-#{@block.id}"}
               class="w-full py-2 text-sm text-warning bg-base-200 border-t border-base-300 hover:bg-base-300 cursor-pointer"
             >
-              Show full output
+              {gettext("Show full output")}
             </button>
           </div>
         </div>
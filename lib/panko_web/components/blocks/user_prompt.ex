@@ -1,11 +1,15 @@
 defmodule PankoWeb.Components.Blocks.UserPrompt do
   use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
 
+  @behaviour PankoWeb.Components.Blocks.Renderer
+
+  @impl true
   def render(assigns) do
     ~H"""
     <article class="block border border-base-300 rounded-lg mb-4 border-l-4 border-l-info overflow-hidden">
       <div class="flex items-center justify-between px-4 py-2 bg-base-200/50 border-b border-base-300">
-        <span class="text-sm font-semibold text-info">User</span>
+        <span class="text-sm font-semibold text-info">{gettext("User")}</span>
         <time :if={@block.timestamp} class="text-xs text-base-content/50">
           {format_time(@block.timestamp)}
         </time>
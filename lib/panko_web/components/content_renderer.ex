@@ -13,6 +13,10 @@ defmodule PankoWeb.Components.ContentRenderer do
   - `<system-reminder>...</system-reminder>` - stripped entirely
   """
 
+  use Gettext, backend: PankoWeb.Gettext
+
+  alias Panko.Sessions.Markdown
+
   # Compile the combined split pattern once at compile time.
   # Individual tag_patterns/0 must remain a function because Regex structs
   # contain references that cannot be escaped into function bodies via @attrs.
@@ -59,26 +63,39 @@ defmodule PankoWeb.Components.ContentRenderer do
   end
 
   @doc """
-  Renders a markdown string to safe HTML using Earmark.
+  Renders a markdown string to safe HTML, see `Panko.Sessions.Markdown.to_html/1`.
 
   Returns a `Phoenix.HTML.safe()` value suitable for direct use in HEEx templates.
+  Code blocks get a copy button, handled by `[data-copy-code]` in `app.js`.
   """
   @spec render_markdown(String.t()) :: Phoenix.HTML.safe()
   def render_markdown(text) when is_binary(text) do
-    case Earmark.as_html(text, compact_output: true) do
-      {:ok, html, _warnings} ->
-        Phoenix.HTML.raw(HtmlSanitizeEx.markdown_html(html))
-
-      {:error, _html, _errors} ->
-        escaped = text |> Phoenix.HTML.html_escape() |> Phoenix.HTML.safe_to_string()
-        Phoenix.HTML.raw("<p>#{escaped}</p>")
-    end
+    text
+    |> Markdown.to_html()
+    |> add_copy_buttons()
+    |> Phoenix.HTML.raw()
   end
 
   def render_markdown(_), do: Phoenix.HTML.raw("")
 
+  defdelegate balance_fences(text), to: Markdown
+
   # -- Private helpers --
 
+  # Runs after sanitizing, which would strip the button.
+  defp add_copy_buttons(html) do
+    button = """
+    <button type="button" data-copy-code class="btn btn-ghost btn-xs absolute top-1 right-1" \
+    aria-label="#{escape(gettext("Copy code"))}">#{escape(gettext("Copy"))}</button>\
+    """
+
+    Regex.replace(~r{<pre>.*?</pre>}s, html, fn pre ->
+      ~s(<div class="relative">) <> button <> pre <> "</div>"
+    end)
+  end
+
+  defp escape(text), do: text |> Phoenix.HTML.html_escape() |> Phoenix.HTML.safe_to_string()
+
   # Tag patterns ordered so more specific tags match before shorter ones.
   # Must be a function (not @attr) because Regex structs contain references
   # that the compiler cannot escape into function bodies.
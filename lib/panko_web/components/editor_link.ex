@@ -0,0 +1,69 @@
+defmodule PankoWeb.Components.EditorLink do
+  @moduledoc """
+  Links file paths in tool blocks to a local editor.
+
+  The editor is `vscode`, `cursor`, `zed`, or a URL template containing
+  `{path}` and optionally `{line}`, such as
+  `idea://open?file={path}&line={line}`. Links are only rendered in the
+  owner's session view, configured with `PANKO_EDITOR`; share viewers do
+  not have the files.
+  """
+  use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
+
+  @presets %{
+    "vscode" => "vscode://file{path}:{line}",
+    "cursor" => "cursor://file{path}:{line}",
+    "zed" => "zed://file{path}:{line}"
+  }
+
+  @doc """
+  Returns the editor URL for an absolute `path`, or nil when no editor is
+  configured or the path is relative.
+  """
+  @spec url(String.t() | nil, String.t() | nil, pos_integer()) :: String.t() | nil
+  def url(editor, path, line \\ 1)
+  def url(nil, _path, _line), do: nil
+  def url(_editor, nil, _line), do: nil
+
+  def url(editor, path, line) do
+    template = Map.get(@presets, editor, editor)
+
+    if String.starts_with?(path, "/") and String.contains?(template, "{path}") do
+      template
+      |> String.replace("{path}", URI.encode(path))
+      |> String.replace("{line}", to_string(line))
+    end
+  end
+
+  @doc """
+  Returns the file a tool input refers to, if any.
+  """
+  @spec input_path(map() | nil) :: String.t() | nil
+  def input_path(%{"file_path" => path}) when is_binary(path), do: path
+  def input_path(%{"notebook_path" => path}) when is_binary(path), do: path
+  def input_path(%{"path" => path}) when is_binary(path), do: path
+  def input_path(_input), do: nil
+
+  attr :editor, :string, default: nil
+  attr :path, :string, default: nil
+  attr :line, :integer, default: 1
+
+  @doc """
+  Renders an "open in editor" link, or nothing when `url/3` gives none.
+  """
+  def editor_link(assigns) do
+    assigns = assign(assigns, :url, url(assigns.editor, assigns.path, assigns.line))
+
+    ~H"""
+    <a
+      :if={@url}
+      href={@url}
+      class="btn btn-ghost btn-xs font-normal"
+      title={gettext("Open %{path} in editor", path: @path)}
+    >
+      <PankoWeb.CoreComponents.icon name="hero-code-bracket-micro" class="size-3" /> {gettext("Open")}
+    </a>
+    """
+  end
+end
@@ -43,7 +43,7 @@ defmodule PankoWeb.Layouts do
           <span class="badge badge-ghost badge-sm font-mono text-xs">v0.1.0</span>
         </a>
       </div>
-      <div class="flex-none">
+      <nav class="flex-none" aria-label={gettext("Main")}>
         <ul class="flex items-center gap-2">
           <li>
             <a href="/" class="btn btn-ghost btn-sm">
@@ -54,10 +54,10 @@ defmodule PankoWeb.Layouts do
             <.theme_toggle />
           </li>
         </ul>
-      </div>
+      </nav>
     </header>
 
-    <main class="px-4 py-8 sm:px-6 lg:px-8">
+    <main id="main" class="px-4 py-8 sm:px-6 lg:px-8">
       <div class="mx-auto max-w-6xl space-y-4">
         {render_slot(@inner_block) || @inner_content}
       </div>
@@ -111,38 +111,51 @@ defmodule PankoWeb.Layouts do
   end
 
   @doc """
-  Provides dark vs light theme toggle based on themes defined in app.css.
+  Provides a system, light, dark and high-contrast theme toggle based on
+  the themes defined in app.css.
 
   See <head> in root.html.heex which applies the theme before page load.
   """
   def theme_toggle(assigns) do
     ~H"""
     <div class="card relative flex flex-row items-center border-2 border-base-300 bg-base-300 rounded-full">
-      <div class="absolute w-1/3 h-full rounded-full border-1 border-base-200 bg-base-100 brightness-200 left-0 [[data-theme=light]_&]:left-1/3 [[data-theme=dark]_&]:left-2/3 transition-[left]" />
+      <div class="absolute w-1/4 h-full rounded-full border-1 border-base-200 bg-base-100 brightness-200 left-0 [[data-theme=light]_&]:left-1/4 [[data-theme=dark]_&]:left-2/4 [[data-theme=contrast]_&]:left-3/4 transition-[left]" />
 
       <button
-        class="flex p-2 cursor-pointer w-1/3"
+        class="flex p-2 cursor-pointer w-1/4"
         phx-click={JS.dispatch("phx:set-theme")}
         data-phx-theme="system"
+        aria-label={gettext("System theme")}
       >
         <.icon name="hero-computer-desktop-micro" class="size-4 opacity-75 hover:opacity-100" />
       </button>
 
       <button
-        class="flex p-2 cursor-pointer w-1/3"
+        class="flex p-2 cursor-pointer w-1/4"
         phx-click={JS.dispatch("phx:set-theme")}
         data-phx-theme="light"
+        aria-label={gettext("Light theme")}
       >
         <.icon name="hero-sun-micro" class="size-4 opacity-75 hover:opacity-100" />
       </button>
 
       <button
-        class="flex p-2 cursor-pointer w-1/3"
+        class="flex p-2 cursor-pointer w-1/4"
         phx-click={JS.dispatch("phx:set-theme")}
         data-phx-theme="dark"
+        aria-label={gettext("Dark theme")}
       >
         <.icon name="hero-moon-micro" class="size-4 opacity-75 hover:opacity-100" />
       </button>
+
+      <button
+        class="flex p-2 cursor-pointer w-1/4"
+        phx-click={JS.dispatch("phx:set-theme")}
+        data-phx-theme="contrast"
+        aria-label={gettext("High contrast theme")}
+      >
+        <.icon name="hero-eye-micro" class="size-4 opacity-75 hover:opacity-100" />
+      </button>
     </div>
     """
   end
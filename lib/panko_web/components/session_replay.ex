@@ -0,0 +1,307 @@
+defmodule PankoWeb.Components.SessionReplay do
+  @moduledoc """
+  A LiveComponent that renders a session's blocks with a replay mode and
+  block comments.
+
+  A table of contents of the user prompts sits above the blocks. Each
+  block has buttons copying it as Markdown or as its raw content.
+
+  The blocks form an ARIA feed; `j` and `k` move focus between them (see
+  `assets/js/app.js`).
+
+  Replay starts from an empty page and reveals blocks one at a time,
+  waiting between them as long as the agent did, divided by the chosen
+  speed. Waits are clamped so long pauses do not stall a demo and bursts
+  of tool calls stay readable. Stopping shows every block again.
+
+  Comments are passed in by the parent LiveView, which reloads them when
+  `comments:session:<id>` broadcasts. With `commenting` set, readers can
+  add comments, signed with `author` or, when that is nil, a name they
+  enter; `can_delete` allows removing them. On a share page `share_slug`
+  is set, and comments are only accepted while that share is still
+  published and unexpired.
+
+  Bookmarks are passed in the same way and listed above the blocks; with
+  `bookmarking` set, each block has a button toggling its bookmark.
+  """
+  use PankoWeb, :live_component
+
+  import PankoWeb.Components.Blocks
+  import PankoWeb.Components.BlockComments
+  import PankoWeb.Components.SessionToc
+
+  alias Panko.Sessions.TableOfContents
+
+  @speeds [1, 4, 16, 64]
+  @min_delay 250
+  @max_delay 5_000
+  @untimed_delay 1_000
+
+  @impl true
+  def mount(socket) do
+    {:ok,
+     assign(socket, replaying: false, paused: false, shown: 0, speed: 4, run: 0, form_key: 0)}
+  end
+
+  @impl true
+  def update(%{tick: run}, socket) do
+    if socket.assigns.replaying and not socket.assigns.paused and run == socket.assigns.run do
+      {:ok, advance(socket)}
+    else
+      {:ok, socket}
+    end
+  end
+
+  def update(assigns, socket) do
+    {:ok,
+     socket
+     |> assign(assigns)
+     |> assign_new(:editor, fn -> nil end)
+     |> assign_new(:comments, fn -> [] end)
+     |> assign_new(:commenting, fn -> false end)
+     |> assign_new(:can_delete, fn -> false end)
+     |> assign_new(:author, fn -> nil end)
+     |> assign_new(:share_slug, fn -> nil end)
+     |> assign_new(:bookmarks, fn -> [] end)
+     |> assign_new(:bookmarking, fn -> false end)}
+  end
+
+  @impl true
+  def handle_event("replay", _params, socket) do
+    socket = assign(socket, replaying: true, paused: false, shown: 0, run: socket.assigns.run + 1)
+    {:noreply, advance(socket)}
+  end
+
+  def handle_event("pause", _params, socket) do
+    {:noreply, assign(socket, paused: true, run: socket.assigns.run + 1)}
+  end
+
+  def handle_event("resume", _params, socket) do
+    {:noreply, socket |> assign(paused: false) |> schedule()}
+  end
+
+  def handle_event("stop", _params, socket) do
+    {:noreply, assign(socket, replaying: false, paused: false, run: socket.assigns.run + 1)}
+  end
+
+  def handle_event("speed", %{"speed" => speed}, socket) do
+    case Integer.parse(speed) do
+      {speed, ""} when speed in @speeds -> {:noreply, assign(socket, speed: speed)}
+      _ -> {:noreply, socket}
+    end
+  end
+
+  def handle_event("comment", %{"position" => position, "body" => body} = params, socket) do
+    %{session_id: session_id, author: author, commenting: commenting} = socket.assigns
+
+    with true <- commenting,
+         true <- share_open?(socket.assigns.share_slug),
+         {position, ""} <- Integer.parse(position),
+         {:ok, _comment} <-
+           Panko.Sessions.add_comment(session_id, position, body, %{
+             author: author || params["name"]
+           }) do
+      {:noreply, assign(socket, form_key: socket.assigns.form_key + 1)}
+    else
+      _ -> {:noreply, socket}
+    end
+  end
+
+  def handle_event("delete_comment", %{"id" => id}, socket) do
+    comment = Enum.find(socket.assigns.comments, &(&1.id == id))
+    if socket.assigns.can_delete and comment, do: Panko.Sessions.delete_comment(comment)
+    {:noreply, socket}
+  end
+
+  def handle_event("toggle_bookmark", %{"position" => position}, socket) do
+    %{session_id: session_id, bookmarks: bookmarks, bookmarking: bookmarking} = socket.assigns
+
+    with true <- bookmarking,
+         {position, ""} <- Integer.parse(position) do
+      case Enum.find(bookmarks, &(&1.position == position)) do
+        nil -> Panko.Sessions.add_bookmark(session_id, position)
+        bookmark -> Panko.Sessions.delete_bookmark(bookmark)
+      end
+    end
+
+    {:noreply, socket}
+  end
+
+  @impl true
+  def render(assigns) do
+    visible =
+      if assigns.replaying, do: Enum.take(assigns.blocks, assigns.shown), else: assigns.blocks
+
+    assigns =
+      assign(assigns,
+        speeds: @speeds,
+        visible: visible,
+        comments_by_position: Enum.group_by(assigns.comments, & &1.position),
+        toc: TableOfContents.entries(assigns.blocks),
+        bookmarked: MapSet.new(assigns.bookmarks, & &1.position),
+        bookmark_entries: TableOfContents.bookmarks(assigns.blocks, assigns.bookmarks)
+      )
+
+    ~H"""
+    <div>
+      <div class="flex items-center gap-2 mb-4 text-sm">
+        <button
+          :if={!@replaying}
+          phx-click="replay"
+          phx-target={@myself}
+          class="btn btn-ghost btn-sm gap-1"
+        >
+          <.icon name="hero-play-micro" class="size-4" /> {gettext("Replay")}
+        </button>
+        <div :if={@replaying} class="flex items-center gap-2">
+          <button
+            phx-click={if @paused, do: "resume", else: "pause"}
+            phx-target={@myself}
+            class="btn btn-ghost btn-sm gap-1"
+          >
+            <.icon name={if @paused, do: "hero-play-micro", else: "hero-pause-micro"} class="size-4" />
+            {if @paused, do: gettext("Resume"), else: gettext("Pause")}
+          </button>
+          <button phx-click="stop" phx-target={@myself} class="btn btn-ghost btn-sm gap-1">
+            <.icon name="hero-stop-micro" class="size-4" /> {gettext("Stop")}
+          </button>
+          <span class="text-base-content/50">{@shown} / {length(@blocks)}</span>
+        </div>
+        <div :if={@replaying} class="join ml-auto">
+          <button
+            :for={speed <- @speeds}
+            phx-click="speed"
+            phx-value-speed={speed}
+            phx-target={@myself}
+            class={["btn btn-xs join-item", speed == @speed && "btn-active"]}
+          >
+            {speed}×
+          </button>
+        </div>
+      </div>
+
+      <.session_toc entries={@toc} />
+      <.session_bookmarks entries={@bookmark_entries} />
+
+      <div
+        class="space-y-2"
+        role="feed"
+        aria-label={gettext("Session blocks")}
+        aria-busy={to_string(@replaying and not @paused)}
+      >
+        <div :for={blk <- @visible} id={TableOfContents.anchor(blk)}>
+          <.block block={blk} editor={@editor} />
+          <.copy_buttons
+            block={blk}
+            bookmarking={@bookmarking}
+            bookmarked={blk.position in @bookmarked}
+            target={@myself}
+          />
+          <.block_comments
+            comments={Map.get(@comments_by_position, blk.position, [])}
+            position={blk.position}
+            target={@myself}
+            commenting={@commenting}
+            can_delete={@can_delete}
+            ask_name={is_nil(@author)}
+            form_key={@form_key}
+          />
+        </div>
+      </div>
+    </div>
+    """
+  end
+
+  # The copied text rides along in hidden textareas, since the clipboard
+  # API needs the click that triggers it and a server round trip loses it.
+  defp copy_buttons(assigns) do
+    ~H"""
+    <div class="flex justify-end gap-1 -mt-3 mb-2 text-xs">
+      <textarea
+        id={"block-markdown-#{@block.position}"}
+        hidden
+        readonly
+      >{as_markdown(@block)}</textarea>
+      <textarea id={"block-raw-#{@block.position}"} hidden readonly>{as_raw(@block)}</textarea>
+      <button
+        :if={@bookmarking}
+        id={"bookmark-#{@block.position}"}
+        type="button"
+        phx-click="toggle_bookmark"
+        phx-value-position={@block.position}
+        phx-target={@target}
+        aria-pressed={to_string(@bookmarked)}
+        class={["btn btn-ghost btn-xs", @bookmarked && "text-warning"]}
+      >
+        <.icon
+          name={if @bookmarked, do: "hero-bookmark-solid", else: "hero-bookmark-micro"}
+          class="size-3"
+        />
+        {if @bookmarked, do: gettext("Bookmarked"), else: gettext("Bookmark")}
+      </button>
+      <button
+        type="button"
+        phx-click={JS.dispatch("phx:copy", to: "#block-markdown-#{@block.position}")}
+        class="btn btn-ghost btn-xs"
+      >
+        <.icon name="hero-clipboard-document-micro" class="size-3" /> {gettext("Copy Markdown")}
+      </button>
+      <button
+        type="button"
+        phx-click={JS.dispatch("phx:copy", to: "#block-raw-#{@block.position}")}
+        class="btn btn-ghost btn-xs"
+      >
+        <.icon name="hero-clipboard-micro" class="size-3" /> {gettext("Copy raw")}
+      </button>
+    </div>
+    """
+  end
+
+  # A share page can stay open after its share is stopped or expires. The
+  # plain read is used, as `get_share_by_slug` loads the whole session.
+  defp share_open?(nil), do: true
+
+  defp share_open?(slug) do
+    case Panko.Sharing.get_share(slug) do
+      {:ok, %{is_shared: false}} -> false
+      {:ok, %{expires_at: nil}} -> true
+      {:ok, %{expires_at: expires_at}} -> DateTime.compare(expires_at, DateTime.utc_now()) == :gt
+      {:error, _} -> false
+    end
+  end
+
+  defp as_markdown(block) do
+    block |> Panko.Sessions.Exporters.Markdown.render_block() |> IO.iodata_to_binary()
+  end
+
+  defp as_raw(%{content: content}) when is_binary(content) and content != "", do: content
+  defp as_raw(%{metadata: metadata}) when is_map(metadata),
+    do: Jason.encode!(metadata, pretty: true)
+  defp as_raw(_block), do: ""
+
+  defp advance(socket) do
+    shown = socket.assigns.shown + 1
+
+    if shown >= length(socket.assigns.blocks) do
+      assign(socket, replaying: false)
+    else
+      socket |> assign(shown: shown) |> schedule()
+    end
+  end
+
+  defp schedule(socket) do
+    %{blocks: blocks, shown: shown, speed: speed, run: run} = socket.assigns
+    delay = delay(Enum.at(blocks, shown - 1), Enum.at(blocks, shown), speed)
+    send_update_after(self(), __MODULE__, [id: socket.assigns.id, tick: run], delay)
+    socket
+  end
+
+  defp delay(%{timestamp: %DateTime{} = from}, %{timestamp: %DateTime{} = to}, speed) do
+    (DateTime.diff(to, from, :millisecond) / speed)
+    |> round()
+    |> max(@min_delay)
+    |> min(@max_delay)
+  end
+
+  defp delay(_from, _to, speed), do: max(div(@untimed_delay, speed), @min_delay)
+end
@@ -0,0 +1,53 @@
+defmodule PankoWeb.Components.SessionToc do
+  @moduledoc """
+  Renders a collapsible table of contents linking to each user prompt,
+  from `Panko.Sessions.TableOfContents`, and the list of bookmarked
+  blocks. Both render nothing without entries.
+  """
+  use Phoenix.Component
+  use Gettext, backend: PankoWeb.Gettext
+
+  attr :entries, :list, required: true
+
+  def session_toc(assigns) do
+    ~H"""
+    <details :if={@entries != []} class="mb-4 text-sm border border-base-300 rounded-lg">
+      <summary class="px-4 py-2 cursor-pointer bg-base-200/30 hover:bg-base-200/60">
+        {gettext("Contents")}
+        <span class="text-base-content/50">({length(@entries)})</span>
+      </summary>
+      <nav aria-label={gettext("Contents")} class="px-4 py-2">
+        <ol class="list-decimal list-inside space-y-1">
+          <li :for={entry <- @entries}>
+            <a href={"##{entry.anchor}"} class="link link-hover">{entry.title}</a>
+          </li>
+        </ol>
+      </nav>
+    </details>
+    """
+  end
+
+  attr :entries, :list, required: true
+
+  def session_bookmarks(assigns) do
+    ~H"""
+    <nav
+      :if={@entries != []}
+      id="bookmarks"
+      aria-label={gettext("Bookmarks")}
+      class="mb-4 text-sm border border-base-300 rounded-lg px-4 py-2"
+    >
+      <h2 class="font-medium mb-1">
+        {gettext("Bookmarks")}
+        <span class="text-base-content/50">({length(@entries)})</span>
+      </h2>
+      <ul class="space-y-1">
+        <li :for={entry <- @entries} class="flex gap-2">
+          <span class="text-base-content/40">★</span>
+          <a href={"##{entry.anchor}"} class="link link-hover">{entry.title}</a>
+        </li>
+      </ul>
+    </nav>
+    """
+  end
+end
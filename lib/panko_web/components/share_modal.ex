@@ -3,11 +3,22 @@ defmodule PankoWeb.Components.ShareModal do
   A LiveComponent that handles sharing a session.
 
   Shows a share button that creates a share, displays the URL with a copy button,
-  and offers unpublish/republish controls.
+  and offers unpublish/republish controls. When the server listens on all
+  interfaces, the share's URLs on the local network are listed too. Share
+  presets (see `Panko.Sharing.Presets`) are offered in a menu next to the
+  share button.
+
+  Before a share is created, the session is audited with
+  `Panko.Sessions.Privacy`; any findings are listed in a dialog and the
+  share is only created once confirmed.
   """
   use PankoWeb, :live_component
 
+  alias Panko.Sessions.Privacy
   alias Panko.Sharing
+  alias Panko.Sharing.Presets
+
+  @load [:unique_visitors]
 
   @impl true
   def update(assigns, socket) do
@@ -18,6 +29,9 @@ defmodule PankoWeb.Components.ShareModal do
      |> assign(assigns)
      |> assign_new(:copied, fn -> false end)
      |> assign_new(:show_modal, fn -> false end)
+     |> assign_new(:privacy, fn -> nil end)
+     |> assign_new(:lan_urls, fn -> PankoWeb.LanUrls.endpoint() end)
+     |> assign_new(:presets, fn -> Presets.all() |> Map.keys() |> Enum.sort() end)
      |> assign(share: share)}
   end
 
@@ -25,15 +39,36 @@ defmodule PankoWeb.Components.ShareModal do
   def render(assigns) do
     ~H"""
     <div>
-      <%!-- No share exists: show "Share" button --%>
-      <button
-        :if={is_nil(@share)}
-        phx-click="create_share"
-        phx-target={@myself}
-        class="btn btn-primary btn-sm gap-2"
-      >
-        <.icon name="hero-share-micro" class="size-4" /> Share
-      </button>
+      <%!-- No share exists: show "Share" button, and the presets if any --%>
+      <div :if={is_nil(@share)} class="join">
+        <button
+          phx-click="create_share"
+          phx-target={@myself}
+          class="btn btn-primary btn-sm gap-2 join-item"
+        >
+          <.icon name="hero-share-micro" class="size-4" /> Share
+        </button>
+        <div :if={@presets != []} class="dropdown dropdown-end join-item">
+          <div tabindex="0" role="button" class="btn btn-primary btn-sm" title="Share with a preset">
+            <.icon name="hero-chevron-down-micro" class="size-4" />
+          </div>
+          <ul
+            tabindex="0"
+            class="dropdown-content menu bg-base-100 rounded-box z-10 w-48 p-2 shadow-sm"
+          >
+            <li :for={preset <- @presets}>
+              <button
+                phx-click="create_share"
+                phx-value-preset={preset}
+                phx-target={@myself}
+                id={"share-preset-#{preset}"}
+              >
+                {preset}
+              </button>
+            </li>
+          </ul>
+        </div>
+      </div>
 
       <%!-- Share exists and is active: show "Shared" button that opens modal --%>
       <button
@@ -54,6 +89,48 @@ defmodule PankoWeb.Components.ShareModal do
         <.icon name="hero-share-micro" class="size-4" /> Reshare
       </button>
 
+      <%!-- Privacy findings to confirm before sharing. A new id per attempt
+           mounts, and so opens, the dialog again after it was dismissed. --%>
+      <dialog
+        :if={@privacy}
+        id={"privacy-modal-#{@id}-#{@privacy.attempt}"}
+        class="modal"
+        phx-mounted={open_modal("privacy-modal-#{@id}-#{@privacy.attempt}")}
+      >
+        <div class="modal-box">
+          <h3 class="text-lg font-bold mb-2">Review before sharing</h3>
+          <p class="text-sm mb-4">
+            This session contains {Privacy.describe(@privacy.findings)}.
+            Anyone with the link will be able to see them.
+          </p>
+          <ul class="text-xs font-mono space-y-1 mb-4 max-h-60 overflow-y-auto">
+            <li :for={finding <- @privacy.findings}>
+              <span class="badge badge-warning badge-xs">{finding.rule}</span>
+              {finding.match}
+              <span class="text-base-content/50">{finding_location(finding)}</span>
+            </li>
+          </ul>
+          <div class="modal-action">
+            <form method="dialog">
+              <button class="btn btn-sm">Cancel</button>
+            </form>
+            <button
+              id={"share-anyway-#{@id}"}
+              phx-click="create_share"
+              phx-value-preset={@privacy.preset}
+              phx-value-confirmed="true"
+              phx-target={@myself}
+              class="btn btn-warning btn-sm"
+            >
+              Share anyway
+            </button>
+          </div>
+        </div>
+        <form method="dialog" class="modal-backdrop">
+          <button>close</button>
+        </form>
+      </dialog>
+
       <%!-- Modal dialog for active shares --%>
       <dialog
         :if={@share && @share.is_shared}
@@ -93,8 +170,26 @@ defmodule PankoWeb.Components.ShareModal do
             </button>
           </div>
 
-          <p class="text-xs text-base-content/60 mb-4">
-            Anyone with this link can view this session.
+          <div :if={@lan_urls != []} class="text-xs mb-4">
+            <p class="text-base-content/60 mb-1">On your network:</p>
+            <ul class="font-mono space-y-1">
+              <li :for={entry <- @lan_urls}>
+                {entry.url}/s/{@share.slug}
+                <span :if={entry.tailscale} class="badge badge-ghost badge-xs">tailscale</span>
+              </li>
+            </ul>
+          </div>
+
+          <p class="text-xs text-base-content/60 mb-2">
+            Anyone with this link can view this session{expiry_suffix(@share.expires_at)}.
+          </p>
+
+          <p class="text-xs text-base-content/50 mb-4">
+            {views_label(@share.view_count)}{visitors_label(@share.unique_visitors)}
+            &middot; {kilobytes(@share.bytes_served)} served
+            <span :if={@share.last_viewed_at}>
+              &middot; last viewed {format_time(@share.last_viewed_at)}
+            </span>
           </p>
 
           <div class="modal-action">
@@ -119,19 +214,28 @@ defmodule PankoWeb.Components.ShareModal do
   end
 
   @impl true
-  def handle_event("create_share", _params, socket) do
-    case Sharing.create_share(socket.assigns.session_id) do
-      {:ok, share} ->
-        {:noreply, assign(socket, share: share, show_modal: true, copied: false)}
+  def handle_event("create_share", params, socket) do
+    findings =
+      if params["confirmed"] == "true",
+        do: [],
+        else: privacy_findings(socket.assigns.session_id)
 
-      {:error, _changeset} ->
-        {:noreply, put_flash(socket, :error, "Failed to create share")}
+    if findings == [] do
+      create_share(socket, params["preset"])
+    else
+      privacy = %{
+        findings: findings,
+        preset: params["preset"],
+        attempt: System.unique_integer([:positive])
+      }
+
+      {:noreply, assign(socket, privacy: privacy)}
     end
   end
 
   @impl true
   def handle_event("unpublish_share", _params, socket) do
-    case Sharing.unpublish_share(socket.assigns.share) do
+    case Sharing.unpublish_share(socket.assigns.share, load: @load) do
       {:ok, share} ->
         {:noreply, assign(socket, share: share, show_modal: false)}
 
@@ -142,7 +246,7 @@ defmodule PankoWeb.Components.ShareModal do
 
   @impl true
   def handle_event("republish_share", _params, socket) do
-    case Sharing.republish_share(socket.assigns.share) do
+    case Sharing.republish_share(socket.assigns.share, load: @load) do
       {:ok, share} ->
         {:noreply, assign(socket, share: share, show_modal: true, copied: false)}
 
@@ -156,8 +260,36 @@ defmodule PankoWeb.Components.ShareModal do
     {:noreply, assign(socket, copied: true)}
   end
 
+  defp create_share(socket, preset_name) do
+    preset =
+      case Presets.fetch(preset_name || "") do
+        {:ok, preset} -> preset
+        :error -> %{}
+      end
+
+    case Sharing.create_share(socket.assigns.session_id, preset, load: @load) do
+      {:ok, share} ->
+        {:noreply, assign(socket, share: share, show_modal: true, copied: false, privacy: nil)}
+
+      {:error, _changeset} ->
+        {:noreply, put_flash(socket, :error, "Failed to create share")}
+    end
+  end
+
+  defp privacy_findings(session_id) do
+    case Panko.Sessions.get_session(session_id, load: [:blocks]) do
+      {:ok, session} -> Privacy.scan(session)
+      {:error, _} -> []
+    end
+  end
+
+  defp finding_location(finding) do
+    where = if finding.position, do: "block #{finding.position}", else: "title"
+    if finding.count > 1, do: "#{where}, #{finding.count} times", else: where
+  end
+
   defp find_share(session_id) do
-    Sharing.find_share_for_session!(session_id)
+    Sharing.find_share_for_session!(session_id, load: @load)
     |> List.first()
   end
 
@@ -170,6 +302,22 @@ defmodule PankoWeb.Components.ShareModal do
   defp port_string(%URI{port: nil}), do: ""
   defp port_string(%URI{port: port}), do: ":#{port}"
 
+  defp views_label(1), do: "Viewed 1 time"
+  defp views_label(count), do: "Viewed #{count || 0} times"
+
+  defp visitors_label(count) when is_integer(count) and count > 0 do
+    if count == 1, do: " by 1 visitor", else: " by #{count} visitors"
+  end
+
+  defp visitors_label(_count), do: ""
+
+  defp kilobytes(bytes), do: "#{div(bytes || 0, 1000)} KB"
+
+  defp expiry_suffix(nil), do: ""
+  defp expiry_suffix(expires_at), do: " until #{format_time(expires_at)} UTC"
+
+  defp format_time(%DateTime{} = dt), do: Calendar.strftime(dt, "%Y-%m-%d %H:%M")
+
   defp open_modal(id) do
     JS.dispatch("modal:open", to: "##{id}")
   end
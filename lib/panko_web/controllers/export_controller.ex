@@ -0,0 +1,30 @@
+defmodule PankoWeb.ExportController do
+  use PankoWeb, :controller
+
+  alias Panko.Sessions
+  alias Panko.Sessions.Exporters.Bundle
+
+  def project(conn, %{"project" => project} = params) do
+    bundle = params["bundle"] || "markdown"
+
+    sessions =
+      Sessions.list_project_sessions!(project,
+        load: [:blocks, :sub_agents, :comments, :bookmarks]
+      )
+
+    with [_ | _] <- sessions,
+         {:ok, document, _report} <- Bundle.build(sessions, bundle) do
+      send_download(conn, {:binary, IO.iodata_to_binary(document)},
+        filename: "#{Path.basename(project)}#{extension(bundle)}"
+      )
+    else
+      [] -> send_resp(conn, 404, "No sessions found")
+      {:error, _reason} -> send_resp(conn, 400, "Unsupported bundle")
+    end
+  end
+
+  def project(conn, _params), do: send_resp(conn, 400, "Missing project")
+
+  defp extension("zip"), do: ".zip"
+  defp extension(_bundle), do: ".md"
+end
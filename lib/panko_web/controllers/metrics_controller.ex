@@ -0,0 +1,11 @@
+defmodule PankoWeb.MetricsController do
+  use PankoWeb, :controller
+
+  def index(conn, _params) do
+    metrics = Panko.Metrics.snapshot() ++ PankoWeb.RequestMetrics.snapshot()
+
+    conn
+    |> put_resp_content_type("text/plain; version=0.0.4")
+    |> send_resp(200, Panko.Metrics.to_prometheus(metrics))
+  end
+end
@@ -12,8 +12,10 @@ defmodule PankoWeb.Endpoint do
   ]
 
   socket "/live", Phoenix.LiveView.Socket,
-    websocket: [connect_info: [:uri, session: @session_options]],
-    longpoll: [connect_info: [:uri, session: @session_options]]
+    websocket: [connect_info: [:uri, :peer_data, :x_headers, session: @session_options]],
+    longpoll: [connect_info: [:uri, :peer_data, :x_headers, session: @session_options]]
+
+  plug PankoWeb.Plugs.HostAllowlist
 
   # Serve at "/" the static files from "priv/static" directory.
   #
@@ -0,0 +1,69 @@
+defmodule PankoWeb.LanUrls do
+  @moduledoc """
+  Lists the URLs teammates on the same network can reach Panko at, when
+  it listens on all interfaces (`PANKO_HTTP_IP` set to `0.0.0.0` or `::`).
+
+  Addresses come from the machine's network interfaces, skipping loopback
+  and link-local ones. Tailscale addresses (100.64.0.0/10) are marked so
+  they can be told apart from the local network.
+  """
+
+  @type entry :: %{interface: String.t(), url: String.t(), tailscale: boolean()}
+
+  @wildcards [{0, 0, 0, 0}, {0, 0, 0, 0, 0, 0, 0, 0}]
+
+  @doc """
+  Returns the network URLs of the running endpoint, or `[]` when it is
+  not running or only listens on one address.
+  """
+  @spec endpoint() :: [entry()]
+  def endpoint do
+    case PankoWeb.Endpoint.server_info(:http) do
+      {:ok, {ip, port}} -> list(ip, port)
+      _ -> []
+    end
+  catch
+    # The endpoint is not started.
+    :exit, _ -> []
+  end
+
+  @doc """
+  Returns the network URLs for a server listening on `ip` and `port`.
+  """
+  @spec list(:inet.ip_address(), :inet.port_number()) :: [entry()]
+  def list(ip, port) when ip in @wildcards do
+    case :inet.getifaddrs() do
+      {:ok, ifaddrs} -> from_ifaddrs(ifaddrs, port, ipv6: ip == {0, 0, 0, 0, 0, 0, 0, 0})
+      {:error, _} -> []
+    end
+  end
+
+  def list(_ip, _port), do: []
+
+  @doc """
+  Builds the entries from `:inet.getifaddrs/0` output. IPv6 addresses are
+  only included with `ipv6: true`.
+  """
+  @spec from_ifaddrs([{charlist(), keyword()}], :inet.port_number(), keyword()) :: [entry()]
+  def from_ifaddrs(ifaddrs, port, opts \\ []) do
+    for {name, attrs} <- ifaddrs,
+        {:addr, addr} <- attrs,
+        reachable?(addr, Keyword.get(opts, :ipv6, false)) do
+      %{interface: to_string(name), url: url(addr, port), tailscale: tailscale?(addr)}
+    end
+  end
+
+  defp reachable?({127, _, _, _}, _ipv6), do: false
+  defp reachable?({169, 254, _, _}, _ipv6), do: false
+  defp reachable?({_, _, _, _}, _ipv6), do: true
+  defp reachable?({0, 0, 0, 0, 0, 0, 0, 1}, _ipv6), do: false
+  defp reachable?({first, _, _, _, _, _, _, _}, _ipv6) when first in 0xFE80..0xFEBF, do: false
+  defp reachable?({_, _, _, _, _, _, _, _}, ipv6), do: ipv6
+
+  defp tailscale?({100, second, _, _}), do: second in 64..127
+  defp tailscale?({0xFD7A, 0x115C, 0xA1E0, _, _, _, _, _}), do: true
+  defp tailscale?(_addr), do: false
+
+  defp url({_, _, _, _} = addr, port), do: "http://#{:inet.ntoa(addr)}:#{port}"
+  defp url(addr, port), do: "http://[#{:inet.ntoa(addr)}]:#{port}"
+end
@@ -0,0 +1,127 @@
+defmodule PankoWeb.InsightsLive do
+  use PankoWeb, :live_view
+
+  alias Panko.Sessions.Stats
+  alias Panko.Sessions.ToolTiming
+
+  @days 30
+
+  @impl true
+  def mount(_params, _session, socket) do
+    since = DateTime.add(DateTime.utc_now(), -@days * 86_400)
+
+    rows = Stats.stored_rows(%{since: since})
+
+    insights = Stats.insights(rows, days: @days)
+    peak = insights.per_day |> Enum.map(& &1.sessions) |> Enum.max(fn -> 0 end)
+
+    {:ok,
+     assign(socket,
+       insights: insights,
+       peak: max(peak, 1),
+       tools: since |> Stats.tool_timings() |> Enum.take(10),
+       days: @days,
+       page_title: "Insights"
+     )}
+  end
+
+  @impl true
+  def render(assigns) do
+    ~H"""
+    <div class="container mx-auto px-4 py-8 max-w-4xl">
+      <div class="flex items-center justify-between mb-6">
+        <h1 class="text-3xl font-bold">Insights</h1>
+        <.link navigate={~p"/"} class="btn btn-ghost btn-sm gap-1">
+          <.icon name="hero-arrow-left-micro" class="size-4" /> Sessions
+        </.link>
+      </div>
+      <p class="text-sm text-base-content/60 mb-6">Last {@days} days</p>
+
+      <div class="stats stats-vertical sm:stats-horizontal shadow w-full mb-8">
+        <div class="stat">
+          <div class="stat-title">Sessions</div>
+          <div class="stat-value">{@insights.totals.sessions}</div>
+        </div>
+        <div class="stat">
+          <div class="stat-title">Messages</div>
+          <div class="stat-value">{@insights.totals.messages}</div>
+        </div>
+        <div class="stat">
+          <div class="stat-title">Average length</div>
+          <div class="stat-value">{format_duration(@insights.average_duration)}</div>
+        </div>
+        <div class="stat">
+          <div class="stat-title">Estimated cost</div>
+          <div class="stat-value">{format_cost(@insights.totals.estimated_cost_usd)}</div>
+        </div>
+      </div>
+
+      <h2 class="text-lg font-semibold mb-2">Sessions per day</h2>
+      <div id="sessions-per-day" class="flex items-end gap-1 h-40 mb-8 border-b border-base-300">
+        <div
+          :for={day <- @insights.per_day}
+          class="flex-1 bg-primary/70 rounded-t min-h-px"
+          style={"height: #{day.sessions * 100 / @peak}%"}
+          title={"#{Calendar.strftime(day.date, "%b %d")}: #{day.sessions} sessions"}
+        />
+      </div>
+
+      <h2 class="text-lg font-semibold mb-2">Busiest projects</h2>
+      <p :if={@insights.projects == []} class="text-base-content/60">No sessions yet.</p>
+      <table :if={@insights.projects != []} class="table table-sm">
+        <thead>
+          <tr>
+            <th>Project</th>
+            <th class="text-right">Sessions</th>
+            <th class="text-right">Messages</th>
+            <th class="text-right">Cost</th>
+          </tr>
+        </thead>
+        <tbody>
+          <tr :for={project <- @insights.projects}>
+            <td class="font-mono text-xs truncate max-w-xs">{display_project(project.project)}</td>
+            <td class="text-right">{project.sessions}</td>
+            <td class="text-right">{project.messages}</td>
+            <td class="text-right">{format_cost(project.estimated_cost_usd)}</td>
+          </tr>
+        </tbody>
+      </table>
+
+      <h2 class="text-lg font-semibold mt-8 mb-2">Slowest tools</h2>
+      <p :if={@tools == []} class="text-base-content/60">No timed tool calls yet.</p>
+      <table :if={@tools != []} id="tool-timings" class="table table-sm">
+        <thead>
+          <tr>
+            <th>Tool</th>
+            <th class="text-right">Calls</th>
+            <th class="text-right">Average</th>
+            <th class="text-right">Slowest</th>
+            <th class="text-right">Total</th>
+          </tr>
+        </thead>
+        <tbody>
+          <tr :for={tool <- @tools}>
+            <td class="font-mono text-xs">{tool.tool}</td>
+            <td class="text-right">{tool.calls}</td>
+            <td class="text-right">{ToolTiming.format(tool.average_ms)}</td>
+            <td class="text-right">{ToolTiming.format(tool.max_ms)}</td>
+            <td class="text-right">{ToolTiming.format(tool.total_ms)}</td>
+          </tr>
+        </tbody>
+      </table>
+    </div>
+    """
+  end
+
+  defp display_project(project) do
+    project
+    |> String.replace(~r"^/home/[^/]+/", "~/")
+    |> String.replace(~r"^/Users/[^/]+/", "~/")
+  end
+
+  defp format_duration(seconds) when seconds < 60, do: "#{seconds}s"
+  defp format_duration(seconds) when seconds < 3600, do: "#{div(seconds, 60)}m"
+  defp format_duration(seconds), do: "#{div(seconds, 3600)}h #{div(rem(seconds, 3600), 60)}m"
+
+  defp format_cost(cost), do: "$" <> :erlang.float_to_binary(cost / 1, decimals: 2)
+end
@@ -1,23 +1,35 @@
 defmodule PankoWeb.SessionLive do
   use PankoWeb, :live_view
 
-  import PankoWeb.Components.Blocks
+  import PankoWeb.Components.AgentBadge
 
-  alias PankoWeb.Components.ShareModal
+  alias PankoWeb.Components.{SessionReplay, ShareModal}
 
   @impl true
-  def mount(%{"id" => id}, _session, socket) do
-    case Panko.Sessions.get_session(id,
-           load: [:blocks, :sub_agents, :block_count, :message_count]
-         ) do
+  def mount(%{"id" => id} = params, _session, socket) do
+    case load_session(id) do
       {:ok, session} ->
         uri = get_connect_info_uri(socket)
 
+        if connected?(socket) do
+          PankoWeb.Endpoint.subscribe("shares:session:#{session.id}")
+          PankoWeb.Endpoint.subscribe("comments:session:#{session.id}")
+          PankoWeb.Endpoint.subscribe("bookmarks:session:#{session.id}")
+          PankoWeb.Endpoint.subscribe("sessions:source_removed")
+          PankoWeb.Endpoint.subscribe("sessions:imported")
+        end
+
         {:ok,
          assign(socket,
            session: session,
            page_title: session.title || "Session",
-           uri: uri
+           uri: uri,
+           editor: Application.get_env(:panko, :editor),
+           comment_author: Application.get_env(:panko, :comment_author, "Owner"),
+           comments: Panko.Sessions.list_session_comments!(session.id),
+           bookmarks: Panko.Sessions.list_session_bookmarks!(session.id),
+           source_missing: Panko.Sessions.SessionFiles.source_missing?(session),
+           following: params["follow"] in ["1", "true"]
          )}
 
       {:error, _} ->
@@ -25,6 +37,45 @@ defmodule PankoWeb.SessionLive do
     end
   end
 
+  @impl true
+  def handle_info(%Phoenix.Socket.Broadcast{topic: "shares:session:" <> session_id}, socket) do
+    send_update(ShareModal, id: "share-#{session_id}", session_id: session_id)
+    {:noreply, socket}
+  end
+
+  def handle_info(%Phoenix.Socket.Broadcast{topic: "comments:session:" <> session_id}, socket) do
+    {:noreply, assign(socket, comments: Panko.Sessions.list_session_comments!(session_id))}
+  end
+
+  def handle_info(%Phoenix.Socket.Broadcast{topic: "bookmarks:session:" <> session_id}, socket) do
+    {:noreply, assign(socket, bookmarks: Panko.Sessions.list_session_bookmarks!(session_id))}
+  end
+
+  def handle_info(%Phoenix.Socket.Broadcast{topic: "sessions:source_removed"} = msg, socket) do
+    if msg.payload.path == socket.assigns.session.source_path do
+      {:noreply, assign(socket, source_missing: true)}
+    else
+      {:noreply, socket}
+    end
+  end
+
+  # The watcher re-imports the source file as the agent writes to it, so an
+  # open page picks up new blocks without a reload.
+  def handle_info(%Phoenix.Socket.Broadcast{topic: "sessions:imported"} = msg, socket) do
+    with %{data: %{id: id}} when id == socket.assigns.session.id <- msg.payload,
+         {:ok, session} <- load_session(id) do
+      socket = assign(socket, session: session, page_title: session.title || "Session")
+      {:noreply, maybe_follow(socket)}
+    else
+      _ -> {:noreply, socket}
+    end
+  end
+
+  @impl true
+  def handle_event("toggle_follow", _params, socket) do
+    {:noreply, socket |> assign(following: !socket.assigns.following) |> maybe_follow()}
+  end
+
   @impl true
   def render(assigns) do
     ~H"""
@@ -34,25 +85,56 @@ defmodule PankoWeb.SessionLive do
           <.link navigate={~p"/"} class="btn btn-ghost btn-sm gap-1">
             <.icon name="hero-arrow-left-micro" class="size-4" /> Sessions
           </.link>
-          <.live_component
-            module={ShareModal}
-            id={"share-#{@session.id}"}
-            session_id={@session.id}
-            uri={@uri}
-          />
+          <div class="flex items-center gap-2">
+            <button
+              id="follow-toggle"
+              type="button"
+              phx-click="toggle_follow"
+              aria-pressed={to_string(@following)}
+              title="Scroll to new blocks as the session is written"
+              class={["btn btn-sm gap-1", if(@following, do: "btn-primary", else: "btn-ghost")]}
+            >
+              <span :if={@following} class="status status-success animate-pulse"></span>
+              <.icon :if={!@following} name="hero-signal-micro" class="size-4" /> Follow
+            </button>
+            <.live_component
+              module={ShareModal}
+              id={"share-#{@session.id}"}
+              session_id={@session.id}
+              uri={@uri}
+            />
+          </div>
+        </div>
+        <div :if={@source_missing} role="alert" class="alert alert-warning mb-4 text-sm">
+          <.icon name="hero-exclamation-triangle-micro" class="size-4" />
+          <span>
+            The source file <code class="font-mono">{@session.source_path}</code>
+            no longer exists. This page and its shares show the last imported copy.
+          </span>
         </div>
         <h1 class="text-2xl font-bold">{@session.title || "Untitled session"}</h1>
         <p class="text-sm text-base-content/60 mt-1 font-mono">{display_project(@session.project)}</p>
-        <div class="flex gap-4 text-xs text-base-content/50 mt-2">
+        <div class="flex items-center gap-4 text-xs text-base-content/50 mt-2">
+          <.agent_badge agent={@session.source_type} />
           <span>{@session.message_count} messages</span>
           <span>{@session.block_count} blocks</span>
           <span>{format_time(@session.started_at)}</span>
         </div>
       </div>
 
-      <div class="space-y-2">
-        <.block :for={blk <- @session.blocks} block={blk} />
-      </div>
+      <.live_component
+        module={SessionReplay}
+        id={"replay-#{@session.id}"}
+        blocks={@session.blocks}
+        editor={@editor}
+        session_id={@session.id}
+        comments={@comments}
+        bookmarks={@bookmarks}
+        commenting
+        bookmarking
+        can_delete
+        author={@comment_author}
+      />
 
       <footer class="text-center text-xs text-base-content/40 mt-12 py-4 border-t border-base-300">
         {@session.block_count} blocks
@@ -61,6 +143,15 @@ defmodule PankoWeb.SessionLive do
     """
   end
 
+  defp maybe_follow(%{assigns: %{following: true}} = socket),
+    do: push_event(socket, "follow", %{})
+
+  defp maybe_follow(socket), do: socket
+
+  defp load_session(id) do
+    Panko.Sessions.get_session(id, load: [:blocks, :sub_agents, :block_count, :message_count])
+  end
+
   defp display_project(nil), do: ""
 
   defp display_project(project) do
@@ -1,13 +1,17 @@
 defmodule PankoWeb.SessionsLive do
   use PankoWeb, :live_view
 
+  import PankoWeb.Components.AgentBadge
+
   alias Panko.Sessions
   alias Panko.Sharing
+  alias PankoWeb.Components.AgentBadge
 
   @impl true
   def mount(_params, _session, socket) do
     if connected?(socket) do
       PankoWeb.Endpoint.subscribe("sessions:imported")
+      PankoWeb.Endpoint.subscribe("shares:changed")
     end
 
     sessions = load_sessions()
@@ -21,42 +25,58 @@ defmodule PankoWeb.SessionsLive do
        projects: projects,
        project_count: map_size(projects),
        search_query: "",
+       agent: nil,
        expanded_projects: MapSet.new(),
+       last_session: nil,
        page_title: "Sessions"
-     )}
+     )
+     |> restore_view(get_connect_params(socket)["view_state"])}
   end
 
   @impl true
   def handle_info(%Phoenix.Socket.Broadcast{topic: "sessions:imported"}, socket) do
     sessions = load_sessions()
-    projects = group_by_project(sessions)
-
-    projects =
-      if socket.assigns.search_query != "" do
-        filter_projects(projects, socket.assigns.search_query)
-      else
-        projects
-      end
+    projects = visible_projects(sessions, socket.assigns.agent, socket.assigns.search_query)
 
     {:noreply,
      assign(socket, sessions: sessions, projects: projects, project_count: map_size(projects))}
   end
 
+  # View and traffic counts do not affect the dashboard.
+  def handle_info(%Phoenix.Socket.Broadcast{topic: "shares:changed", event: event}, socket) do
+    if event in ["record_view", "record_bytes"] do
+      {:noreply, socket}
+    else
+      {:noreply, assign(socket, shared_session_ids: load_shared_session_ids())}
+    end
+  end
+
   @impl true
   def handle_event("search", %{"query" => query}, socket) do
-    projects = group_by_project(socket.assigns.sessions)
+    filtered = visible_projects(socket.assigns.sessions, socket.assigns.agent, query)
 
-    {filtered, expanded} =
-      if query == "" do
-        {projects, MapSet.new()}
-      else
-        filtered = filter_projects(projects, query)
-        expanded = filtered |> Enum.map(fn {project, _} -> project end) |> MapSet.new()
-        {filtered, expanded}
-      end
+    expanded =
+      if query == "",
+        do: MapSet.new(),
+        else: filtered |> Enum.map(fn {project, _} -> project end) |> MapSet.new()
 
     {:noreply,
-     assign(socket, search_query: query, projects: filtered, expanded_projects: expanded)}
+     socket
+     |> assign(search_query: query, projects: filtered, expanded_projects: expanded)
+     |> save_view()}
+  end
+
+  def handle_event("filter_agent", params, socket) do
+    agent = Enum.find(AgentBadge.agents(), &(Atom.to_string(&1) == params["agent"]))
+    {:noreply, filter_agent(socket, agent)}
+  end
+
+  # Bound to the `a` key in assets/js/app.js: all agents, then each agent
+  # with sessions in turn.
+  def handle_event("cycle_agent", _params, socket) do
+    choices = [nil | Enum.map(agent_counts(socket.assigns.sessions), &elem(&1, 0))]
+    index = Enum.find_index(choices, &(&1 == socket.assigns.agent)) || 0
+    {:noreply, filter_agent(socket, Enum.at(choices, rem(index + 1, length(choices))))}
   end
 
   @impl true
@@ -68,7 +88,7 @@ defmodule PankoWeb.SessionsLive do
         MapSet.put(socket.assigns.expanded_projects, project)
       end
 
-    {:noreply, assign(socket, expanded_projects: expanded)}
+    {:noreply, socket |> assign(expanded_projects: expanded) |> save_view()}
   end
 
   @impl true
@@ -77,8 +97,11 @@ defmodule PankoWeb.SessionsLive do
     <div class="container mx-auto px-4 py-8">
       <div class="flex items-center justify-between mb-6">
         <h1 class="text-3xl font-bold">Sessions</h1>
-        <div class="text-sm text-base-content/50">
-          {length(@sessions)} sessions across {@project_count} projects
+        <div class="flex items-center gap-4 text-sm text-base-content/50">
+          <span>{length(@sessions)} sessions across {@project_count} projects</span>
+          <.link navigate={~p"/insights"} class="btn btn-ghost btn-sm gap-1">
+            <.icon name="hero-chart-bar-micro" class="size-4" /> Insights
+          </.link>
         </div>
       </div>
 
@@ -104,6 +127,36 @@ defmodule PankoWeb.SessionsLive do
         </div>
       </div>
 
+      <%!-- Agent filter, also the legend of the agent colors --%>
+      <div
+        :if={@sessions != []}
+        id="agent-filter"
+        class="flex items-center gap-2 mb-4 text-xs"
+        title="Press a to cycle through agents"
+      >
+        <span class="text-base-content/50">Agent:</span>
+        <button
+          type="button"
+          phx-click="filter_agent"
+          aria-pressed={to_string(is_nil(@agent))}
+          class={["btn btn-xs", if(is_nil(@agent), do: "btn-active", else: "btn-ghost")]}
+        >
+          All
+        </button>
+        <button
+          :for={{agent, count} <- agent_counts(@sessions)}
+          type="button"
+          phx-click="filter_agent"
+          phx-value-agent={agent}
+          aria-pressed={to_string(@agent == agent)}
+          class={["btn btn-xs gap-1", if(@agent == agent, do: "btn-active", else: "btn-ghost")]}
+        >
+          <.agent_badge agent={agent} />
+          <span class="text-base-content/50">{count}</span>
+        </button>
+        <button type="button" phx-click="cycle_agent" data-cycle-agent hidden></button>
+      </div>
+
       <%!-- Empty state --%>
       <div
         :if={@projects == %{} && @search_query == ""}
@@ -163,13 +216,36 @@ defmodule PankoWeb.SessionsLive do
 
           <%!-- Sessions list (collapsed by default) --%>
           <div :if={MapSet.member?(@expanded_projects, project)} class="border-t border-base-300">
+            <div
+              :if={project != "Unknown Project"}
+              class="flex items-center gap-3 px-4 py-2 pl-11 text-xs text-base-content/50 border-b border-base-300"
+            >
+              <.icon name="hero-arrow-down-tray-micro" class="size-3.5" />
+              <span>Export project:</span>
+              <a href={~p"/projects/export?#{[project: project]}"} class="link link-hover">
+                Markdown
+              </a>
+              <a
+                href={~p"/projects/export?#{[project: project, bundle: "zip"]}"}
+                class="link link-hover"
+              >
+                Zip
+              </a>
+            </div>
             <.link
               :for={session <- project_sessions}
+              id={"session-#{session.id}"}
               navigate={~p"/sessions/#{session.id}"}
-              class="flex items-center justify-between px-4 py-3 pl-11 hover:bg-base-200/30 transition-colors border-b border-base-300 last:border-b-0"
+              data-session-link={session.id}
+              aria-current={@last_session == session.id && "true"}
+              class={[
+                "flex items-center justify-between px-4 py-3 pl-11 hover:bg-base-200/30 transition-colors border-b border-base-300 last:border-b-0",
+                @last_session == session.id && "bg-base-200/40"
+              ]}
             >
               <div class="flex-1 min-w-0">
                 <div class="flex items-center gap-2">
+                  <.agent_badge agent={session.source_type} />
                   <span class="text-sm truncate">{session.title || "Untitled session"}</span>
                   <span
                     :if={MapSet.member?(@shared_session_ids, session.id)}
@@ -202,6 +278,74 @@ defmodule PankoWeb.SessionsLive do
     |> MapSet.new()
   end
 
+  defp filter_agent(socket, agent) do
+    %{sessions: sessions, search_query: query} = socket.assigns
+    socket
+    |> assign(agent: agent, projects: visible_projects(sessions, agent, query))
+    |> save_view()
+  end
+
+  # The page's search, agent filter and open projects are kept in the
+  # browser's localStorage by assets/js/app.js, together with the session
+  # last opened from here, and sent back with the connect params, so the
+  # list comes back as it was left.
+  defp restore_view(socket, json) when is_binary(json) do
+    case Jason.decode(json) do
+      {:ok, %{} = view} ->
+        agent = Enum.find(AgentBadge.agents(), &(Atom.to_string(&1) == view["agent"]))
+        query = if is_binary(view["query"]), do: view["query"], else: ""
+        projects = visible_projects(socket.assigns.sessions, agent, query)
+
+        expanded =
+          for project <- List.wrap(view["expanded"]),
+              is_map_key(projects, project),
+              into: MapSet.new(),
+              do: project
+
+        last = find_visible(projects, expanded, view["last_session"])
+
+        socket
+        |> assign(
+          agent: agent,
+          search_query: query,
+          projects: projects,
+          expanded_projects: expanded,
+          last_session: last
+        )
+        |> then(&if(last, do: push_event(&1, "focus", %{id: "session-#{last}"}), else: &1))
+
+      _ ->
+        socket
+    end
+  end
+
+  defp restore_view(socket, _json), do: socket
+
+  defp find_visible(projects, expanded, id) do
+    Enum.find_value(expanded, fn project ->
+      Enum.find_value(projects[project], &(&1.id == id && id))
+    end)
+  end
+
+  defp save_view(socket) do
+    %{search_query: query, agent: agent, expanded_projects: expanded} = socket.assigns
+    push_event(socket, "save-view", %{query: query, agent: agent, expanded: Enum.sort(expanded)})
+  end
+
+  defp visible_projects(sessions, agent, query) do
+    projects =
+      sessions
+      |> Enum.filter(&(is_nil(agent) or &1.source_type == agent))
+      |> group_by_project()
+
+    if query == "", do: projects, else: filter_projects(projects, query)
+  end
+
+  defp agent_counts(sessions) do
+    counts = Enum.frequencies_by(sessions, & &1.source_type)
+    for agent <- AgentBadge.agents(), counts[agent], do: {agent, counts[agent]}
+  end
+
   defp group_by_project(sessions) do
     Enum.group_by(sessions, fn s -> s.project || "Unknown Project" end)
   end
@@ -1,7 +1,8 @@
 defmodule PankoWeb.ShareLive do
   use PankoWeb, :live_view
 
-  import PankoWeb.Components.Blocks
+  alias Panko.Sessions.Redactor
+  alias PankoWeb.Components.SessionReplay
 
   @impl true
   def mount(%{"slug" => slug}, _session, socket) do
@@ -12,11 +13,20 @@ defmodule PankoWeb.ShareLive do
         else
           session = share.session
 
+          if connected?(socket) do
+            Panko.Sharing.record_share_view(share, %{visitor_hash: visitor_hash(socket)})
+            PankoWeb.Endpoint.subscribe("comments:session:#{session.id}")
+            PankoWeb.Endpoint.subscribe("bookmarks:session:#{session.id}")
+          end
+
           {:ok,
            assign(socket,
              share: share,
              session: session,
-             page_title: session.title || "Shared Session"
+             comments: shared_comments(session.id),
+             bookmarks: Panko.Sessions.list_session_bookmarks!(session.id),
+             commenting: Application.get_env(:panko, :share_comments, false),
+             page_title: session.title || gettext("Shared Session")
            )}
         end
 
@@ -25,46 +35,87 @@ defmodule PankoWeb.ShareLive do
     end
   end
 
+  @impl true
+  def handle_info(%Phoenix.Socket.Broadcast{topic: "comments:session:" <> session_id}, socket) do
+    {:noreply, assign(socket, comments: shared_comments(session_id))}
+  end
+
+  def handle_info(%Phoenix.Socket.Broadcast{topic: "bookmarks:session:" <> session_id}, socket) do
+    {:noreply, assign(socket, bookmarks: Panko.Sessions.list_session_bookmarks!(session_id))}
+  end
+
   @impl true
   def render(%{error: :not_found} = assigns) do
     ~H"""
-    <div class="flex items-center justify-center min-h-screen">
+    <main id="main" class="flex items-center justify-center min-h-screen">
       <div class="text-center">
         <h1 class="text-4xl font-bold mb-4">404</h1>
-        <p class="text-base-content/60">This share link is not available.</p>
+        <p class="text-base-content/60">{gettext("This share link is not available.")}</p>
       </div>
-    </div>
+    </main>
     """
   end
 
   def render(%{error: :expired} = assigns) do
     ~H"""
-    <div class="flex items-center justify-center min-h-screen">
+    <main id="main" class="flex items-center justify-center min-h-screen">
       <div class="text-center">
-        <h1 class="text-4xl font-bold mb-4">Expired</h1>
-        <p class="text-base-content/60">This shared session has expired.</p>
+        <h1 class="text-4xl font-bold mb-4">{gettext("Expired")}</h1>
+        <p class="text-base-content/60">{gettext("This shared session has expired.")}</p>
       </div>
-    </div>
+    </main>
     """
   end
 
   def render(assigns) do
     ~H"""
     <div class="container mx-auto px-4 py-8 max-w-4xl">
-      <h1 class="text-2xl font-bold mb-1">{@session.title || "Shared Session"}</h1>
-      <p class="text-sm text-base-content/60 mb-6 font-mono">{display_project(@session.project)}</p>
+      <header>
+        <h1 class="text-2xl font-bold mb-1">{@session.title || gettext("Shared Session")}</h1>
+        <p class="text-sm text-base-content/60 mb-6 font-mono">{display_project(@session.project)}</p>
+        <p :if={@share.snapshot_blocks} class="text-xs text-base-content/50 -mt-4 mb-6">
+          {gettext("Snapshot of the session as of %{time}",
+            time: Calendar.strftime(@share.shared_at, "%Y-%m-%d %H:%M UTC")
+          )}
+        </p>
+      </header>
 
-      <div class="space-y-2">
-        <.block :for={blk <- @session.blocks} block={blk} />
-      </div>
+      <main id="main">
+        <.live_component
+          module={SessionReplay}
+          id={"replay-#{@session.id}"}
+          blocks={shared_blocks(@share, @session.blocks)}
+          session_id={@session.id}
+          comments={@comments}
+          bookmarks={@bookmarks}
+          commenting={@commenting}
+          share_slug={@share.slug}
+        />
+      </main>
 
       <footer class="text-center text-xs text-base-content/40 mt-12 py-4 border-t border-base-300">
-        Shared with <a href="https://github.com/jordangarrison/panko" class="link">Panko</a>
+        {gettext("Shared with")} <a href="https://github.com/jordangarrison/panko" class="link">Panko</a>
       </footer>
     </div>
     """
   end
 
+  # Comments are written freely, so they get the same masking as exports.
+  defp shared_comments(session_id) do
+    session_id
+    |> Panko.Sessions.list_session_comments!()
+    |> Enum.map(fn comment ->
+      {body, _counts} = Redactor.redact(comment.body)
+      author = comment.author && elem(Redactor.redact(comment.author), 0)
+      %{comment | body: body, author: author}
+    end)
+  end
+
+  defp shared_blocks(%{snapshot_blocks: nil}, blocks), do: blocks
+
+  defp shared_blocks(%{snapshot_blocks: count}, blocks),
+    do: Enum.filter(blocks, &(&1.position < count))
+
   defp display_project(nil), do: ""
 
   defp display_project(project) do
@@ -73,6 +124,22 @@ defmodule PankoWeb.ShareLive do
     |> String.replace(~r"^/Users/[^/]+/", "~/")
   end
 
+  defp visitor_hash(socket) do
+    forwarded =
+      for {"x-forwarded-for", value} <- get_connect_info(socket, :x_headers) || [], do: value
+
+    peer =
+      case get_connect_info(socket, :peer_data) do
+        %{address: address} -> address
+        _ -> nil
+      end
+
+    case PankoWeb.ClientIP.from(peer, forwarded) do
+      nil -> nil
+      client -> Panko.Sharing.ShareView.visitor_hash(client)
+    end
+  end
+
   defp expired?(%{expires_at: nil}), do: false
 
   defp expired?(%{expires_at: expires_at}) do
@@ -0,0 +1,11 @@
+defmodule PankoWeb.LiveLocale do
+  @moduledoc """
+  LiveView on_mount hook that applies the locale chosen by
+  `PankoWeb.Plugs.Locale` to the LiveView process.
+  """
+
+  def on_mount(:default, _params, session, socket) do
+    if locale = session["locale"], do: Gettext.put_locale(PankoWeb.Gettext, locale)
+    {:cont, socket}
+  end
+end
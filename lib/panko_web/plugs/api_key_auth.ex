@@ -7,20 +7,31 @@ defmodule PankoWeb.Plugs.ApiKeyAuth do
   - ?api_key=<key> query parameter
   - Session storage (for browser-based auth)
 
-  If PANKO_API_KEY is not set, all requests pass through.
+  If PANKO_API_KEY is not set, all requests pass through, unless the plug
+  is given `local_only: true`, in which case only direct requests from
+  loopback addresses do.
   """
   import Plug.Conn
 
   def init(opts), do: opts
 
-  def call(conn, _opts) do
+  def call(conn, opts) do
     case Application.get_env(:panko, :api_key) do
-      nil -> conn
-      "" -> conn
+      key when key in [nil, ""] -> if opts[:local_only], do: require_local(conn), else: conn
       expected_key -> verify_key(conn, expected_key)
     end
   end
 
+  # Requests relayed by a reverse proxy arrive from loopback too, so any
+  # forwarded request counts as remote.
+  defp require_local(conn) do
+    if loopback?(conn.remote_ip) and get_req_header(conn, "x-forwarded-for") == [] do
+      conn
+    else
+      unauthorized(conn)
+    end
+  end
+
   defp verify_key(conn, expected_key) do
     provided =
       get_bearer_token(conn) ||
@@ -30,13 +41,21 @@ defmodule PankoWeb.Plugs.ApiKeyAuth do
     if provided != nil and Plug.Crypto.secure_compare(provided, expected_key) do
       conn
     else
-      conn
-      |> put_resp_content_type("text/plain")
-      |> send_resp(401, "Unauthorized")
-      |> halt()
+      unauthorized(conn)
     end
   end
 
+  defp loopback?({127, _, _, _}), do: true
+  defp loopback?({0, 0, 0, 0, 0, 0, 0, 1}), do: true
+  defp loopback?(_ip), do: false
+
+  defp unauthorized(conn) do
+    conn
+    |> put_resp_content_type("text/plain")
+    |> send_resp(401, "Unauthorized")
+    |> halt()
+  end
+
   defp get_bearer_token(conn) do
     case get_req_header(conn, "authorization") do
       ["Bearer " <> token] -> token
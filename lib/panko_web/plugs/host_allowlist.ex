@@ -0,0 +1,62 @@
+defmodule PankoWeb.Plugs.HostAllowlist do
+  @moduledoc """
+  Optional allowlist of Host headers.
+
+  When `PANKO_ALLOWED_HOSTS` is set (e.g. the hostname of a tunnel or
+  reverse proxy), requests for any other host are refused with 421, so
+  scanners probing the address with made-up hosts never reach the app.
+  Entries may start with `*.` to allow subdomains. Loopback hosts are
+  always allowed for requests from a loopback address, for local use and
+  health checks; the Host header alone is not trusted, since any client
+  can send `Host: localhost`.
+
+  LiveView connections are checked against the same hosts through the
+  endpoint's `check_origin`, set in `config/runtime.exs`.
+  """
+  import Plug.Conn
+
+  @loopback ["localhost", "127.0.0.1", "::1", "[::1]"]
+
+  def init(opts), do: opts
+
+  def call(conn, _opts) do
+    case Application.get_env(:panko, :allowed_hosts, []) do
+      [] ->
+        conn
+
+      hosts ->
+        if local?(conn) or allowed?(conn.host, hosts) do
+          conn
+        else
+          conn
+          |> put_resp_content_type("text/plain")
+          |> send_resp(421, "Unknown host")
+          |> halt()
+        end
+    end
+  end
+
+  @doc """
+  Whether `host` matches one of `hosts`.
+  """
+  @spec allowed?(String.t(), [String.t()]) :: boolean()
+  def allowed?(host, hosts) do
+    host = String.downcase(host)
+
+    Enum.any?(hosts, fn
+      "*." <> domain -> String.ends_with?(host, "." <> String.downcase(domain))
+      allowed -> host == String.downcase(allowed)
+    end)
+  end
+
+  # Requests relayed by a reverse proxy arrive from loopback too, so any
+  # forwarded request counts as remote.
+  defp local?(conn) do
+    String.downcase(conn.host) in @loopback and loopback?(conn.remote_ip) and
+      get_req_header(conn, "x-forwarded-for") == []
+  end
+
+  defp loopback?({127, _, _, _}), do: true
+  defp loopback?({0, 0, 0, 0, 0, 0, 0, 1}), do: true
+  defp loopback?(_ip), do: false
+end
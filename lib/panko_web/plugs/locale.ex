@@ -0,0 +1,66 @@
+defmodule PankoWeb.Plugs.Locale do
+  @moduledoc """
+  Picks the locale for a request: `PANKO_LOCALE` when set, otherwise the
+  best `Accept-Language` match among the locales in `priv/gettext`,
+  falling back to the Gettext default.
+
+  The locale is stored in the session for `PankoWeb.LiveLocale`.
+  """
+  import Plug.Conn
+
+  def init(opts), do: opts
+
+  def call(conn, _opts) do
+    locale =
+      Application.get_env(:panko, :locale) ||
+        conn
+        |> get_req_header("accept-language")
+        |> List.first()
+        |> from_accept_language(Gettext.known_locales(PankoWeb.Gettext)) ||
+        Gettext.get_locale(PankoWeb.Gettext)
+
+    Gettext.put_locale(PankoWeb.Gettext, locale)
+    put_session(conn, "locale", locale)
+  end
+
+  @doc """
+  Returns the first of `known` locales in an `Accept-Language` header, by
+  quality, matching `pt-BR` to `pt_BR` and then to `pt`. Returns nil when
+  none match.
+  """
+  @spec from_accept_language(String.t() | nil, [String.t()]) :: String.t() | nil
+  def from_accept_language(nil, _known), do: nil
+
+  def from_accept_language(header, known) do
+    header
+    |> String.split(",", trim: true)
+    |> Enum.map(&parse_language_range/1)
+    |> Enum.reject(&is_nil/1)
+    |> Enum.sort_by(fn {_tag, quality} -> quality end, :desc)
+    |> Enum.find_value(fn {tag, _quality} -> match_locale(tag, known) end)
+  end
+
+  defp parse_language_range(range) do
+    case range |> String.trim() |> String.split(";", parts: 2) do
+      [tag] ->
+        {tag, 1.0}
+
+      [tag, "q=" <> quality] ->
+        case Float.parse(quality) do
+          {quality, _} -> {tag, quality}
+          :error -> nil
+        end
+
+      _ ->
+        nil
+    end
+  end
+
+  defp match_locale(tag, known) do
+    locale = String.replace(tag, "-", "_")
+    language = locale |> String.split("_") |> hd()
+
+    Enum.find(known, &(String.downcase(&1) == String.downcase(locale))) ||
+      Enum.find(known, &(String.downcase(&1) == String.downcase(language)))
+  end
+end
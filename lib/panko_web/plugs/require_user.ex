@@ -0,0 +1,21 @@
+defmodule PankoWeb.Plugs.RequireUser do
+  @moduledoc """
+  Redirects requests without a signed-in user to sign-in.
+
+  The controller counterpart of `PankoWeb.LiveUserAuth`.
+  """
+  import Plug.Conn
+  import Phoenix.Controller, only: [redirect: 2]
+
+  def init(opts), do: opts
+
+  def call(conn, _opts) do
+    if conn.assigns[:current_user] do
+      conn
+    else
+      conn
+      |> redirect(to: "/sign-in")
+      |> halt()
+    end
+  end
+end
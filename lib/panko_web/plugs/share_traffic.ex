@@ -0,0 +1,29 @@
+defmodule PankoWeb.Plugs.ShareTraffic do
+  @moduledoc """
+  Adds the size of each successful `/s/:slug` response to the share's
+  `bytes_served`.
+
+  Only the HTTP responses are counted, not the LiveView updates that
+  follow over the websocket, which are small next to the first render.
+  """
+  import Plug.Conn
+
+  def init(opts), do: opts
+
+  def call(conn, _opts) do
+    case conn.path_params do
+      %{"slug" => slug} -> register_before_send(conn, &record(&1, slug))
+      _ -> conn
+    end
+  end
+
+  defp record(%{status: 200, resp_body: body} = conn, slug) when not is_nil(body) do
+    with {:ok, %{is_shared: true} = share} <- Panko.Sharing.get_share(slug) do
+      Panko.Sharing.record_share_bytes(share, IO.iodata_length(body))
+    end
+
+    conn
+  end
+
+  defp record(conn, _slug), do: conn
+end
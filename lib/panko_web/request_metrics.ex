@@ -0,0 +1,85 @@
+defmodule PankoWeb.RequestMetrics do
+  @moduledoc """
+  Per-route request counts and latencies, recorded from the router's
+  telemetry and served at `/metrics` next to `Panko.Metrics`.
+
+  Requests are grouped by method and route pattern (`/s/:slug`), so
+  shares do not get a series each. Requests slower than
+  `PANKO_SLOW_REQUEST_MS` are also logged as warnings. Like the event
+  counters, everything lives in memory and resets on restart.
+  """
+
+  require Logger
+
+  @table __MODULE__
+  @events [[:phoenix, :router_dispatch, :stop], [:phoenix, :router_dispatch, :exception]]
+
+  @doc """
+  Creates the table and attaches the telemetry handler. Called once on
+  application start.
+  """
+  def setup do
+    :ets.new(@table, [:set, :public, :named_table, write_concurrency: true])
+    :telemetry.attach_many(__MODULE__, @events, &__MODULE__.handle_event/4, nil)
+  end
+
+  @doc false
+  def handle_event([:phoenix, :router_dispatch, event], %{duration: duration}, metadata, _) do
+    method = metadata.conn.method
+    status = if event == :exception, do: 500, else: metadata.conn.status
+    record(method, metadata.route, status, duration)
+  end
+
+  @doc """
+  Records one request; `duration` is in native time units.
+  """
+  def record(method, route, status, duration) do
+    if :ets.whereis(@table) != :undefined do
+      :ets.update_counter(@table, {:requests, method, route, status}, 1, {nil, 0})
+
+      :ets.update_counter(
+        @table,
+        {:duration, method, route},
+        [{2, 1}, {3, duration}],
+        {nil, 0, 0}
+      )
+    end
+
+    log_slow(method, route, status, System.convert_time_unit(duration, :native, :millisecond))
+  end
+
+  @doc """
+  Returns the recorded metrics as `{name, type, help, samples}` tuples for
+  `Panko.Metrics.to_prometheus/1`, where samples are `{labels, value}`.
+  """
+  def snapshot do
+    entries = if :ets.whereis(@table) == :undefined, do: [], else: :ets.tab2list(@table)
+
+    requests =
+      for {{:requests, method, route, status}, count} <- entries,
+          do: {[method: method, route: route, status: status], count}
+
+    durations =
+      for {{:duration, method, route}, count, sum} <- entries,
+          do: {[method: method, route: route], count, sum}
+
+    [
+      {"panko_http_requests_total", :counter, "HTTP requests by route and status",
+       Enum.sort(requests)},
+      {"panko_http_request_duration_seconds_sum", :counter, "Time spent serving each route",
+       Enum.map(durations, fn {labels, _count, sum} -> {labels, seconds(sum)} end)},
+      {"panko_http_request_duration_seconds_count", :counter, "Requests timed per route",
+       Enum.map(durations, fn {labels, count, _sum} -> {labels, count} end)}
+    ]
+  end
+
+  defp seconds(native), do: System.convert_time_unit(native, :native, :microsecond) / 1_000_000
+
+  defp log_slow(method, route, status, ms) do
+    threshold = Application.get_env(:panko, :slow_request_ms, 1000)
+
+    if threshold && ms >= threshold do
+      Logger.warning("Slow request: #{method} #{route} returned #{status} in #{ms}ms")
+    end
+  end
+end
@@ -10,12 +10,25 @@ defmodule PankoWeb.Router do
     plug :protect_from_forgery
     plug :put_secure_browser_headers
     plug :load_from_session
+    plug PankoWeb.Plugs.Locale
+  end
+
+  pipeline :require_user do
+    plug PankoWeb.Plugs.RequireUser
   end
 
   pipeline :api do
     plug :accepts, ["json"]
   end
 
+  pipeline :share do
+    plug PankoWeb.Plugs.ShareTraffic
+  end
+
+  pipeline :metrics do
+    plug PankoWeb.Plugs.ApiKeyAuth, local_only: true
+  end
+
   # Public auth routes (sign in, register, sign out, auth callbacks)
   scope "/", PankoWeb do
     pipe_through :browser
@@ -34,18 +47,32 @@ defmodule PankoWeb.Router do
 
     ash_authentication_live_session :authenticated,
       otp_app: :panko,
-      on_mount: [{PankoWeb.LiveUserAuth, :live_user_required}],
+      on_mount: [{PankoWeb.LiveUserAuth, :live_user_required}, PankoWeb.LiveLocale],
       layout: {PankoWeb.Layouts, :app} do
       live "/", SessionsLive, :index
       live "/sessions/:id", SessionLive, :show
+      live "/insights", InsightsLive, :index
     end
   end
 
+  scope "/", PankoWeb do
+    pipe_through [:browser, :require_user]
+
+    get "/projects/export", ExportController, :project
+  end
+
+  # Prometheus metrics -- API key, or localhost when no key is set
+  scope "/", PankoWeb do
+    pipe_through :metrics
+
+    get "/metrics", MetricsController, :index
+  end
+
   # Public share routes -- no auth
   scope "/s", PankoWeb do
-    pipe_through :browser
+    pipe_through [:browser, :share]
 
-    live_session :public do
+    live_session :public, on_mount: [PankoWeb.LiveLocale] do
       live "/:slug", ShareLive, :show
     end
   end
@@ -52,6 +52,13 @@ defmodule PankoWeb.Telemetry do
         unit: {:native, :millisecond}
       ),
 
+      # Session Metrics
+      summary("panko.sessions.scan.stop.duration", unit: {:native, :millisecond}),
+      summary("panko.sessions.parse.stop.duration",
+        tags: [:parser],
+        unit: {:native, :millisecond}
+      ),
+
       # VM Metrics
       summary("vm.memory.total", unit: {:byte, :kilobyte}),
       summary("vm.total_run_queue_lengths.total"),
@@ -22,7 +22,7 @@ defmodule Panko.MixProject do
   def application do
     [
       mod: {Panko.Application, []},
-      extra_applications: [:logger, :runtime_tools]
+      extra_applications: [:logger, :runtime_tools, :inets, :ssl]
     ]
   end
 
@@ -0,0 +1,23 @@
+defmodule Panko.Repo.Migrations.AddShareViewTracking do
+  @moduledoc """
+  Updates resources based on their most recent snapshots.
+
+  This file was autogenerated with `mix ash_postgres.generate_migrations`
+  """
+
+  use Ecto.Migration
+
+  def up do
+    alter table(:shares) do
+      add(:view_count, :bigint, null: false, default: 0)
+      add(:last_viewed_at, :utc_datetime)
+    end
+  end
+
+  def down do
+    alter table(:shares) do
+      remove(:last_viewed_at)
+      remove(:view_count)
+    end
+  end
+end
@@ -0,0 +1,46 @@
+defmodule Panko.Repo.Migrations.CreateShareViews do
+  @moduledoc """
+  Updates resources based on their most recent snapshots.
+
+  This file was autogenerated with `mix ash_postgres.generate_migrations`
+  """
+
+  use Ecto.Migration
+
+  def up do
+    create table(:share_views, primary_key: false) do
+      add(:id, :uuid, null: false, default: fragment("gen_random_uuid()"), primary_key: true)
+      add(:visitor_hash, :text, null: false)
+      add(:first_viewed_at, :utc_datetime, null: false)
+      add(:last_viewed_at, :utc_datetime, null: false)
+
+      add(
+        :share_id,
+        references(:shares,
+          column: :id,
+          name: "share_views_share_id_fkey",
+          type: :uuid,
+          prefix: "public",
+          on_delete: :delete_all
+        ),
+        null: false
+      )
+    end
+
+    create unique_index(:share_views, [:share_id, :visitor_hash],
+             name: "share_views_unique_visitor_index"
+           )
+  end
+
+  def down do
+    drop_if_exists(
+      unique_index(:share_views, [:share_id, :visitor_hash],
+        name: "share_views_unique_visitor_index"
+      )
+    )
+
+    drop(constraint(:share_views, "share_views_share_id_fkey"))
+
+    drop(table(:share_views))
+  end
+end
@@ -0,0 +1,41 @@
+defmodule Panko.Repo.Migrations.CreateComments do
+  @moduledoc """
+  Updates resources based on their most recent snapshots.
+
+  This file was autogenerated with `mix ash_postgres.generate_migrations`
+  """
+
+  use Ecto.Migration
+
+  def up do
+    create table(:comments, primary_key: false) do
+      add(:id, :uuid, null: false, default: fragment("gen_random_uuid()"), primary_key: true)
+      add(:position, :bigint, null: false)
+      add(:body, :text, null: false)
+      add(:author, :text)
+
+      add(:inserted_at, :utc_datetime_usec,
+        null: false,
+        default: fragment("(now() AT TIME ZONE 'utc')")
+      )
+
+      add(
+        :session_id,
+        references(:sessions,
+          column: :id,
+          name: "comments_session_id_fkey",
+          type: :uuid,
+          prefix: "public",
+          on_delete: :delete_all
+        ),
+        null: false
+      )
+    end
+  end
+
+  def down do
+    drop(constraint(:comments, "comments_session_id_fkey"))
+
+    drop(table(:comments))
+  end
+end
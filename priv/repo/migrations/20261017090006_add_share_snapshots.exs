@@ -0,0 +1,21 @@
+defmodule Panko.Repo.Migrations.AddShareSnapshots do
+  @moduledoc """
+  Updates resources based on their most recent snapshots.
+
+  This file was autogenerated with `mix ash_postgres.generate_migrations`
+  """
+
+  use Ecto.Migration
+
+  def up do
+    alter table(:shares) do
+      add(:snapshot_blocks, :bigint)
+    end
+  end
+
+  def down do
+    alter table(:shares) do
+      remove(:snapshot_blocks)
+    end
+  end
+end
@@ -0,0 +1,29 @@
+defmodule Panko.Repo.Migrations.AddSessionUsage do
+  @moduledoc """
+  Updates resources based on their most recent snapshots.
+
+  This file was autogenerated with `mix ash_postgres.generate_migrations`
+  """
+
+  use Ecto.Migration
+
+  def up do
+    alter table(:sessions) do
+      add(:input_tokens, :bigint)
+      add(:output_tokens, :bigint)
+      add(:cache_creation_input_tokens, :bigint)
+      add(:cache_read_input_tokens, :bigint)
+      add(:estimated_cost_usd, :float)
+    end
+  end
+
+  def down do
+    alter table(:sessions) do
+      remove(:estimated_cost_usd)
+      remove(:cache_read_input_tokens)
+      remove(:cache_creation_input_tokens)
+      remove(:output_tokens)
+      remove(:input_tokens)
+    end
+  end
+end
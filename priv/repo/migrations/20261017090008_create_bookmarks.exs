@@ -0,0 +1,48 @@
+defmodule Panko.Repo.Migrations.CreateBookmarks do
+  @moduledoc """
+  Updates resources based on their most recent snapshots.
+
+  This file was autogenerated with `mix ash_postgres.generate_migrations`
+  """
+
+  use Ecto.Migration
+
+  def up do
+    create table(:bookmarks, primary_key: false) do
+      add(:id, :uuid, null: false, default: fragment("gen_random_uuid()"), primary_key: true)
+      add(:position, :bigint, null: false)
+      add(:label, :text)
+
+      add(:inserted_at, :utc_datetime_usec,
+        null: false,
+        default: fragment("(now() AT TIME ZONE 'utc')")
+      )
+
+      add(
+        :session_id,
+        references(:sessions,
+          column: :id,
+          name: "bookmarks_session_id_fkey",
+          type: :uuid,
+          prefix: "public",
+          on_delete: :delete_all
+        ),
+        null: false
+      )
+    end
+
+    create unique_index(:bookmarks, [:session_id, :position],
+             name: "bookmarks_session_position_index"
+           )
+  end
+
+  def down do
+    drop_if_exists(
+      unique_index(:bookmarks, [:session_id, :position], name: "bookmarks_session_position_index")
+    )
+
+    drop(constraint(:bookmarks, "bookmarks_session_id_fkey"))
+
+    drop(table(:bookmarks))
+  end
+end
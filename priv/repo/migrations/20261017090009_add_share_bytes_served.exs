@@ -0,0 +1,21 @@
+defmodule Panko.Repo.Migrations.AddShareBytesServed do
+  @moduledoc """
+  Updates resources based on their most recent snapshots.
+
+  This file was autogenerated with `mix ash_postgres.generate_migrations`
+  """
+
+  use Ecto.Migration
+
+  def up do
+    alter table(:shares) do
+      add(:bytes_served, :bigint, null: false, default: 0)
+    end
+  end
+
+  def down do
+    alter table(:shares) do
+      remove(:bytes_served)
+    end
+  end
+end
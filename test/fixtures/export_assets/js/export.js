@@ -0,0 +1,2 @@
+// Stands in for the compiled export script in tests
+window.pankoExport = true
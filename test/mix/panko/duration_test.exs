@@ -0,0 +1,25 @@
+defmodule Mix.Panko.DurationTest do
+  use ExUnit.Case, async: true
+
+  alias Mix.Panko.Duration
+
+  test "ago! returns the time the duration ago" do
+    expected = DateTime.add(DateTime.utc_now(), -7 * 86_400)
+    since = Duration.ago!("7d", "--since")
+
+    assert_in_delta DateTime.to_unix(since), DateTime.to_unix(expected), 5
+  end
+
+  test "ago! ignores a missing option" do
+    assert Duration.ago!(nil, "--since") == nil
+  end
+
+  test "ago! raises a usage error naming the option" do
+    error =
+      assert_raise Mix.Error, ~r/Invalid --older-than "soon"/, fn ->
+        Duration.ago!("soon", "--older-than")
+      end
+
+    assert error.exit_status == 2
+  end
+end
@@ -0,0 +1,31 @@
+defmodule Mix.Panko.ExitStatusTest do
+  use ExUnit.Case, async: true
+
+  alias Mix.Panko.ExitStatus
+
+  test "each kind of failure has its own status" do
+    kinds = [
+      :usage,
+      :not_found,
+      :parse_error,
+      :unreachable,
+      :port_unavailable,
+      :registry_error,
+      :drift,
+      :privacy
+    ]
+    codes = Enum.map(kinds, &ExitStatus.code/1)
+
+    assert codes == Enum.uniq(codes)
+    refute 1 in codes
+  end
+
+  test "raise! carries the status in the Mix error" do
+    error =
+      assert_raise Mix.Error, "No such file", fn ->
+        ExitStatus.raise!(:not_found, "No such file")
+      end
+
+    assert error.exit_status == 3
+  end
+end
@@ -0,0 +1,24 @@
+defmodule Mix.Panko.StyleTest do
+  use ExUnit.Case, async: true
+
+  alias Mix.Panko.Style
+
+  describe "enabled?/3" do
+    test "always and never override the environment" do
+      assert Style.enabled?("always", "1", false)
+      refute Style.enabled?("never", nil, true)
+    end
+
+    test "auto colors terminals unless NO_COLOR is set" do
+      assert Style.enabled?(nil, nil, true)
+      assert Style.enabled?("auto", "", true)
+      refute Style.enabled?("auto", "1", true)
+      refute Style.enabled?(nil, nil, false)
+    end
+
+    test "rejects other values as a usage error" do
+      error = assert_raise Mix.Error, fn -> Style.enabled?("sometimes", nil, true) end
+      assert error.exit_status == 2
+    end
+  end
+end
@@ -0,0 +1,26 @@
+defmodule Mix.Panko.TimingsTest do
+  use ExUnit.Case, async: true
+
+  alias Mix.Panko.Timings
+
+  defp native(ms), do: System.convert_time_unit(ms, :millisecond, :native)
+
+  test "summarizes scan and parse spans" do
+    entries = [
+      {:scan, native(2), %{count: 3}},
+      {:parse, native(4), %{path: "/p/a.jsonl"}},
+      {:parse, native(10), %{path: "/p/b.jsonl"}},
+      {:parse, native(1), %{path: "/p/c.jsonl"}}
+    ]
+
+    assert Timings.summary(entries, native(12)) == [
+             "scan   2.0 ms  (3 files)",
+             "parse  15.0 ms  (3 files, mean 5.0 ms, slowest 10.0 ms b.jsonl)",
+             "total  12.0 ms"
+           ]
+  end
+
+  test "omits stages that did not run" do
+    assert Timings.summary([], native(1)) == ["total  1.0 ms"]
+  end
+end
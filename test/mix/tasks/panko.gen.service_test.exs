@@ -0,0 +1,34 @@
+defmodule Mix.Tasks.Panko.Gen.ServiceTest do
+  use ExUnit.Case, async: false
+
+  import ExUnit.CaptureIO
+
+  defp print(args), do: capture_io(fn -> Mix.Tasks.Panko.Gen.Service.run(["--print" | args]) end)
+
+  test "prints a systemd unit running the release" do
+    unit = print(["--systemd", "--release", "/opt/panko", "--env-file", "/etc/panko/env"])
+
+    assert unit =~ "ExecStart=/opt/panko/bin/server\n"
+    assert unit =~ "EnvironmentFile=-/etc/panko/env\n"
+    assert unit =~ "Environment=PHX_SERVER=true\n"
+    assert unit =~ "WantedBy=default.target\n"
+  end
+
+  test "prints a LaunchAgent loading the environment file" do
+    plist = print(["--launchd", "--release", "/opt/panko", "--env-file", "/etc/panko/env"])
+
+    assert plist =~ "<string>com.github.jordangarrison.panko</string>"
+    assert plist =~ ~s(. &quot;/etc/panko/env&quot;; exec &quot;/opt/panko/bin/server&quot;)
+  end
+
+  test "rejects unknown options and arguments" do
+    for args <- [["--relase", "/opt/panko"], ["install"]] do
+      error =
+        assert_raise Mix.Error, ~r/Usage: mix panko.gen.service/, fn ->
+          Mix.Tasks.Panko.Gen.Service.run(args)
+        end
+
+      assert error.exit_status == 2
+    end
+  end
+end
@@ -0,0 +1,29 @@
+defmodule Mix.Tasks.Panko.ListTest do
+  use Panko.DataCase, async: false
+
+  setup do
+    Mix.shell(Mix.Shell.Process)
+    on_exit(fn -> Mix.shell(Mix.Shell.IO) end)
+
+    {:ok, session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    %{session: session}
+  end
+
+  test "--tsv prints a header and a row per session", %{session: session} do
+    Mix.Tasks.Panko.List.run(["--tsv"])
+
+    assert_received {:mix_shell, :info, ["id\texternal_id\t" <> _]}
+    assert_received {:mix_shell, :info, [row]}
+    assert String.starts_with?(row, "#{session.id}\ttest-abc-123\t")
+  end
+
+  test "rejects a misspelled output option instead of printing text" do
+    error =
+      assert_raise Mix.Error, ~r/Usage: mix panko.list/, fn ->
+        Mix.Tasks.Panko.List.run(["--jsno"])
+      end
+
+    assert error.exit_status == 2
+    refute_received {:mix_shell, :info, _}
+  end
+end
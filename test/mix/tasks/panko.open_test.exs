@@ -0,0 +1,66 @@
+defmodule Mix.Tasks.Panko.OpenTest do
+  use Panko.DataCase, async: false
+
+  @moduletag :tmp_dir
+
+  setup do
+    Mix.shell(Mix.Shell.Process)
+    on_exit(fn -> Mix.shell(Mix.Shell.IO) end)
+
+    {:ok, session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    %{session: session}
+  end
+
+  # Opens with a command that writes the URL it is given to a file. The
+  # command runs in the background, so the file is waited for.
+  defp open(args, tmp_dir) do
+    file = Path.join(tmp_dir, "opened")
+    command = ~s(sh -c 'printf %s "$0" > #{file}.tmp && mv #{file}.tmp #{file}' {url})
+
+    Mix.Tasks.Panko.Open.run(args ++ ["--open-with", command])
+    await_file(file, 50)
+  end
+
+  defp await_file(file, tries) do
+    case File.read(file) do
+      {:ok, content} ->
+        content
+
+      {:error, :enoent} when tries > 0 ->
+        Process.sleep(20)
+        await_file(file, tries - 1)
+    end
+  end
+
+  test "opens the URL with --open-with, marked for tab reuse", %{
+    session: session,
+    tmp_dir: tmp_dir
+  } do
+    url = open([session.id, "--follow"], tmp_dir)
+
+    assert url =~ "/sessions/#{session.id}?follow=1&opened="
+    assert_received {:mix_shell, :info, [printed]}
+    assert printed == PankoWeb.Endpoint.url() <> "/sessions/#{session.id}?follow=1"
+  end
+
+  test "--no-reuse opens the URL without a token", %{session: session, tmp_dir: tmp_dir} do
+    assert open([session.id, "--no-reuse"], tmp_dir) ==
+             PankoWeb.Endpoint.url() <> "/sessions/#{session.id}"
+  end
+
+  test "--no-browser only prints the URL", %{session: session} do
+    Mix.Tasks.Panko.Open.run([session.id, "--no-browser", "--open-with", "false"])
+
+    assert_received {:mix_shell, :info, [url]}
+    assert url == PankoWeb.Endpoint.url() <> "/sessions/#{session.id}"
+  end
+
+  test "exits with the not-found status for unknown sessions", %{tmp_dir: tmp_dir} do
+    for args <- [[Ecto.UUID.generate()], ["latest", "--project", tmp_dir]] do
+      error =
+        assert_raise Mix.Error, fn -> Mix.Tasks.Panko.Open.run(args ++ ["--no-browser"]) end
+
+      assert error.exit_status == 3
+    end
+  end
+end
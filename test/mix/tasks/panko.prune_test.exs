@@ -0,0 +1,33 @@
+defmodule Mix.Tasks.Panko.PruneTest do
+  use Panko.DataCase, async: false
+
+  @moduletag :tmp_dir
+
+  setup %{tmp_dir: tmp_dir} do
+    Mix.shell(Mix.Shell.Process)
+    on_exit(fn -> Mix.shell(Mix.Shell.IO) end)
+
+    path = Path.join(tmp_dir, "simple.jsonl")
+    File.cp!("test/fixtures/simple_session.jsonl", path)
+
+    {:ok, session} = Panko.Sessions.import_from_file(path)
+    %{session: session, path: path}
+  end
+
+  test "--dry-run lists sessions without pruning them", %{session: session, path: path} do
+    Mix.Tasks.Panko.Prune.run(["--older-than", "1d", "--dry-run"])
+
+    assert_received {:mix_shell, :info, ["Would prune 1 session(s)"]}
+    assert File.exists?(path)
+    assert {:ok, _} = Panko.Sessions.get_session(session.id)
+  end
+
+  test "a misspelled --dry-run prunes nothing", %{session: session, path: path} do
+    assert_raise Mix.Error, ~r/Usage: mix panko.prune/, fn ->
+      Mix.Tasks.Panko.Prune.run(["--older-than", "1d", "--dryrun", "--permanent"])
+    end
+
+    assert File.exists?(path)
+    assert {:ok, _} = Panko.Sessions.get_session(session.id)
+  end
+end
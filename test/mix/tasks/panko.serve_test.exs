@@ -0,0 +1,56 @@
+defmodule Mix.Tasks.Panko.ServeTest do
+  use ExUnit.Case, async: false
+
+  setup do
+    endpoint = Application.get_env(:panko, PankoWeb.Endpoint)
+    level = Logger.level()
+
+    on_exit(fn ->
+      Application.put_env(:panko, PankoWeb.Endpoint, endpoint)
+      Logger.configure(level: level)
+    end)
+
+    %{endpoint: endpoint}
+  end
+
+  test "rejects a mistyped option instead of starting with the defaults" do
+    for args <- [["--prot", "4010"], ["--port", "abc"], ["4010"]] do
+      error =
+        assert_raise Mix.Error, ~r/Usage: mix panko.serve/, fn ->
+          Mix.Tasks.Panko.Serve.run(args)
+        end
+
+      assert error.exit_status == 2
+    end
+  end
+
+  test "exits with status 6 when the port is taken" do
+    {:ok, socket} = :gen_tcp.listen(0, ip: {127, 0, 0, 1})
+    {:ok, port} = :inet.port(socket)
+
+    error =
+      assert_raise Mix.Error, "Port #{port} is already in use", fn ->
+        Mix.Tasks.Panko.Serve.run(["--port", Integer.to_string(port)])
+      end
+
+    assert error.exit_status == 6
+    :gen_tcp.close(socket)
+  end
+
+  test "checks the port of an IPv6 address", %{endpoint: endpoint} do
+    ip = {0, 0, 0, 0, 0, 0, 0, 1}
+    {:ok, socket} = :gen_tcp.listen(0, [:inet6, ip: ip])
+    {:ok, port} = :inet.port(socket)
+
+    http = Keyword.put(endpoint[:http], :ip, ip)
+    Application.put_env(:panko, PankoWeb.Endpoint, Keyword.put(endpoint, :http, http))
+
+    error =
+      assert_raise Mix.Error, fn ->
+        Mix.Tasks.Panko.Serve.run(["--port", Integer.to_string(port)])
+      end
+
+    assert error.exit_status == 6
+    :gen_tcp.close(socket)
+  end
+end
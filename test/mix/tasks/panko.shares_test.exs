@@ -0,0 +1,29 @@
+defmodule Mix.Tasks.Panko.SharesTest do
+  use Panko.DataCase, async: false
+
+  setup do
+    Mix.shell(Mix.Shell.Process)
+    on_exit(fn -> Mix.shell(Mix.Shell.IO) end)
+
+    {:ok, session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    %{share: share}
+  end
+
+  test "stop-all unpublishes every active share", %{share: share} do
+    Mix.Tasks.Panko.Shares.run(["stop-all"])
+
+    assert_received {:mix_shell, :info, ["Stopped 1 share(s)"]}
+    assert {:ok, %{is_shared: false}} = Panko.Sharing.get_share(share.slug)
+  end
+
+  test "rejects unknown options", %{share: share} do
+    error =
+      assert_raise Mix.Error, ~r/Usage: mix panko.shares/, fn ->
+        Mix.Tasks.Panko.Shares.run(["stop-all", "--dryrun"])
+      end
+
+    assert error.exit_status == 2
+    assert {:ok, %{is_shared: true}} = Panko.Sharing.get_share(share.slug)
+  end
+end
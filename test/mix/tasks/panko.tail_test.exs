@@ -0,0 +1,91 @@
+defmodule Mix.Tasks.Panko.TailTest do
+  use ExUnit.Case, async: false
+
+  @moduletag :tmp_dir
+
+  @fixture "test/fixtures/simple_session.jsonl"
+
+  setup %{tmp_dir: tmp_dir} do
+    Mix.shell(Mix.Shell.Process)
+    on_exit(fn -> Mix.shell(Mix.Shell.IO) end)
+
+    lines = @fixture |> File.read!() |> String.split("\n")
+    %{path: Path.join(tmp_dir, "session.jsonl"), lines: lines}
+  end
+
+  defp tail(path, args \\ []) do
+    Task.async(fn ->
+      Mix.Tasks.Panko.Tail.run([path, "--interval", "10", "--color", "never"] ++ args)
+    end)
+  end
+
+  test "prints blocks appended to the file", %{path: path, lines: [first | rest]} do
+    File.write!(path, first <> "\n")
+    task = tail(path)
+
+    assert_receive {:mix_shell, :info, ["▶ user" <> _]}, 1_000
+    refute_received {:mix_shell, :info, ["◀ assistant" <> _]}
+
+    File.write!(path, Enum.join(rest, "\n"), [:append])
+    assert_receive {:mix_shell, :info, ["◀ assistant" <> _]}, 1_000
+    assert_receive {:mix_shell, :info, ["⚙ Bash  ls -la"]}, 1_000
+
+    Task.shutdown(task, :brutal_kill)
+  end
+
+  test "waits for a line to be completed", %{path: path, lines: [first | _]} do
+    {head, rest} = String.split_at(first, div(String.length(first), 2))
+    File.write!(path, head)
+    task = tail(path)
+
+    refute_receive {:mix_shell, :info, ["▶ user" <> _]}, 100
+
+    File.write!(path, rest <> "\n", [:append])
+    assert_receive {:mix_shell, :info, ["▶ user" <> _]}, 1_000
+
+    Task.shutdown(task, :brutal_kill)
+  end
+
+  test "rejects files that are not JSONL sessions", %{tmp_dir: tmp_dir} do
+    path = Path.join(tmp_dir, "session.json")
+    File.write!(path, "{}")
+
+    assert_raise Mix.Error, ~r/Not a JSONL session/, fn -> Mix.Tasks.Panko.Tail.run([path]) end
+  end
+
+  test "prints tool output written after its call", %{path: path, lines: lines} do
+    [user, call, result | _] = lines
+    File.write!(path, Enum.join([user, call], "\n") <> "\n")
+    task = tail(path)
+
+    assert_receive {:mix_shell, :info, ["⚙ Bash  ls -la"]}, 1_000
+    refute_received {:mix_shell, :info, ["  ↳ " <> _]}
+
+    File.write!(path, result <> "\n", [:append])
+    assert_receive {:mix_shell, :info, ["  ↳ total 4 (+1 lines)"]}, 1_000
+
+    Task.shutdown(task, :brutal_kill)
+  end
+
+  test "prints only the last --lines blocks at first", %{path: path, lines: lines} do
+    File.write!(path, Enum.join(lines, "\n"))
+    task = tail(path, ["-n", "1"])
+
+    assert_receive {:mix_shell, :info, ["◀ assistant  The directory contains" <> _]}, 1_000
+    refute_received {:mix_shell, :info, ["▶ user" <> _]}
+
+    Task.shutdown(task, :brutal_kill)
+  end
+
+  test "does not print blocks again when the file is rewritten", %{path: path, lines: lines} do
+    File.write!(path, Enum.join(lines, "\n"))
+    task = tail(path)
+
+    assert_receive {:mix_shell, :info, ["▶ user" <> _]}, 1_000
+
+    File.write!(path, hd(lines) <> "\n")
+    refute_receive {:mix_shell, :info, ["▶ user" <> _]}, 200
+
+    Task.shutdown(task, :brutal_kill)
+  end
+end
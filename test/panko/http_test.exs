@@ -0,0 +1,12 @@
+defmodule Panko.HTTPTest do
+  use ExUnit.Case, async: true
+
+  test "verifies peers against the system certificates" do
+    opts = Panko.HTTP.options(timeout: 5_000)
+
+    assert opts[:timeout] == 5_000
+    assert opts[:ssl][:verify] == :verify_peer
+    assert [_ | _] = opts[:ssl][:cacerts]
+    assert is_function(opts[:ssl][:customize_hostname_check][:match_fun], 2)
+  end
+end
@@ -0,0 +1,69 @@
+defmodule Panko.NotificationsTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Notifications
+  alias Panko.Notifications.Desktop
+
+  test "parses the enabled events" do
+    assert Notifications.parse_events("share_expired, import_failed,unknown") ==
+             [:share_expired, :import_failed]
+
+    assert Notifications.parse_events("all") == Notifications.events()
+    assert Notifications.parse_events("") == []
+  end
+
+  test "delivers enabled events only" do
+    test = self()
+    notifier = fn title, body -> send(test, {:notified, title, body}) && :ok end
+    opts = [events: [:share_published], notifier: notifier]
+
+    assert Notifications.notify(:share_published, "Share published", "http://x/s/a", opts) == :ok
+    assert_received {:notified, "Share published", "http://x/s/a"}
+
+    assert Notifications.notify(:share_expired, "Share expired", "http://x/s/a", opts) ==
+             :disabled
+
+    refute_received {:notified, _title, _body}
+  end
+
+  @tag capture_log: true
+  test "reports failed deliveries" do
+    opts = [events: [:import_failed], notifier: fn _title, _body -> {:error, :no_notifier} end]
+
+    assert Notifications.notify(:import_failed, "Import failed", "a.jsonl", opts) ==
+             {:error, :no_notifier}
+  end
+
+  test "builds the desktop notification command per platform" do
+    assert Desktop.command({:unix, :linux}, "Title", "Body") ==
+             {"notify-send", ["--app-name=Panko", "Title", "Body"]}
+
+    assert Desktop.command({:unix, :darwin}, ~s(Say "hi"), "Body") ==
+             {"osascript", ["-e", ~s(display notification "Body" with title "Say \\"hi\\"")]}
+
+    assert Desktop.command({:win32, :nt}, "Title", "Body") == nil
+  end
+
+  test "runs the desktop command and waits for it" do
+    parent = self()
+
+    runner = fn command, args ->
+      send(parent, {:ran, command, args})
+      :ok
+    end
+
+    notifier = &Desktop.deliver(&1, &2, os_type: {:unix, :linux}, runner: runner)
+    opts = [events: [:export_completed], notifier: notifier]
+
+    assert Notifications.notify(:export_completed, "Export completed", "a.md", opts) == :ok
+    assert_received {:ran, "notify-send", ["--app-name=Panko", "Export completed", "a.md"]}
+  end
+
+  test "gives up on desktop commands that hang" do
+    runner = fn _command, _args -> Process.sleep(:infinity) end
+    opts = [os_type: {:unix, :linux}, runner: runner, timeout: 10]
+
+    assert Desktop.deliver("Title", "Body", opts) == {:error, :timeout}
+    assert Desktop.deliver("Title", "Body", os_type: {:win32, :nt}) == {:error, :no_notifier}
+  end
+end
@@ -97,4 +97,32 @@ defmodule Panko.Sessions.BlockTest do
       assert session.message_count == 2
     end
   end
+
+  describe "tool timings" do
+    test "aggregates durations per tool", %{session: session} do
+      for {metadata, pos} <- [
+            {%{"name" => "Bash", "duration_ms" => 100}, 0},
+            {%{"name" => "Bash", "duration_ms" => 300}, 1},
+            {%{"name" => "Read", "duration_ms" => 50}, 2},
+            {%{"name" => "Read"}, 3}
+          ] do
+        Block
+        |> Ash.Changeset.for_create(:create, %{
+          session_id: session.id,
+          position: pos,
+          block_type: :tool_call,
+          metadata: metadata,
+          timestamp: ~U[2026-03-09 12:00:00Z]
+        })
+        |> Ash.create!()
+      end
+
+      assert [
+               %{tool: "Bash", calls: 2, total_ms: 400, average_ms: 200, max_ms: 300},
+               %{tool: "Read", calls: 1, total_ms: 50, average_ms: 50, max_ms: 50}
+             ] = Panko.Sessions.Stats.tool_timings(nil)
+
+      assert Panko.Sessions.Stats.tool_timings(~U[2026-03-10 00:00:00Z]) == []
+    end
+  end
 end
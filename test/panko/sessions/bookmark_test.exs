@@ -0,0 +1,53 @@
+defmodule Panko.Sessions.BookmarkTest do
+  use Panko.DataCase, async: true
+
+  alias Panko.Sessions
+  alias Panko.Sessions.TableOfContents
+
+  setup do
+    {:ok, session} = Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    %{session: session}
+  end
+
+  test "lists bookmarks by block position", %{session: session} do
+    {:ok, _} = Sessions.add_bookmark(session.id, 2)
+    {:ok, _} = Sessions.add_bookmark(session.id, 0, %{label: "The ask"})
+
+    assert [%{position: 0, label: "The ask"}, %{position: 2, label: nil}] =
+             Sessions.list_session_bookmarks!(session.id)
+  end
+
+  test "allows one bookmark per block", %{session: session} do
+    {:ok, _} = Sessions.add_bookmark(session.id, 1)
+    assert {:error, _} = Sessions.add_bookmark(session.id, 1)
+  end
+
+  test "survive re-importing the session", %{session: session} do
+    {:ok, _} = Sessions.add_bookmark(session.id, 1)
+    {:ok, _} = Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+
+    assert [%{position: 1}] = Sessions.list_session_bookmarks!(session.id)
+  end
+
+  test "are titled with their label or the block's first line", %{session: session} do
+    {:ok, _} = Sessions.add_bookmark(session.id, 0)
+    {:ok, _} = Sessions.add_bookmark(session.id, 1, %{label: "Answer"})
+    session = Ash.load!(session, [:blocks, :bookmarks])
+
+    assert [
+             %{anchor: "block-0", title: "List the files in the current directory"},
+             %{anchor: "block-1", title: "Answer"}
+           ] = TableOfContents.bookmarks(session.blocks, session.bookmarks)
+  end
+
+  test "are anchored in Markdown exports", %{session: session} do
+    {:ok, _} = Sessions.add_bookmark(session.id, 1, %{label: "Answer"})
+    session = Ash.load!(session, [:blocks, :sub_agents, :bookmarks])
+
+    {:ok, markdown} = Panko.Sessions.Exporters.export(session, :markdown)
+    markdown = IO.iodata_to_binary(markdown)
+
+    assert markdown =~ "## Bookmarks\n\n- [Answer](#block-1)\n"
+    assert markdown =~ ~s(<a id="block-1"></a>\n)
+  end
+end
@@ -0,0 +1,97 @@
+defmodule Panko.Sessions.BuilderTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Builder
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Parsers.ClaudeCode
+
+  @fixtures_dir Path.join([__DIR__, "../../fixtures"])
+
+  defp parse!(builder) do
+    {:ok, jsonl} = Builder.to_jsonl(builder)
+    lines = jsonl |> IO.iodata_to_binary() |> String.split("\n")
+    {:ok, attrs} = ClaudeCode.parse_lines(lines)
+    attrs
+  end
+
+  test "writes sessions the Claude Code parser reads back" do
+    attrs =
+      Builder.new("ci-run-42", cwd: "/app")
+      |> Builder.user("Fix the build", at: ~U[2026-10-17 09:00:00Z])
+      |> Builder.thinking("Tests first.")
+      |> Builder.assistant("Running the tests.", at: ~U[2026-10-17 09:00:05Z])
+      |> Builder.tool_call("Bash", %{"command" => "mix test"},
+        output: "1 failure",
+        is_error: true
+      )
+      |> Builder.tool_call("Edit", %{"file_path" => "/app/a.ex", "old_string" => "a"})
+      |> Builder.summary("CI fix")
+      |> parse!()
+
+    assert attrs.external_id == "ci-run-42"
+    assert attrs.project == "/app"
+    assert attrs.title == "CI fix"
+    assert attrs.started_at == ~U[2026-10-17 09:00:00Z]
+
+    assert [user, thinking, text, bash, edit] = attrs.blocks
+    assert {user.block_type, user.content} == {:user_prompt, "Fix the build"}
+    assert {thinking.block_type, thinking.timestamp} == {:thinking, ~U[2026-10-17 09:00:00Z]}
+    assert text.content == "Running the tests."
+    assert %{"output" => "1 failure", "is_error" => true, "id" => "toolu_4"} = bash.metadata
+    assert {edit.block_type, edit.metadata["path"]} == {:file_edit, "/app/a.ex"}
+  end
+
+  test "rejects invalid sessions" do
+    late = ~U[2026-10-17 10:00:00Z]
+    early = ~U[2026-10-17 09:00:00Z]
+
+    assert Builder.new("") |> Builder.user("hi") |> Builder.validate() ==
+             {:error, :missing_session_id}
+
+    assert Builder.new("s")
+           |> Builder.user("a", at: late)
+           |> Builder.user("b", at: early)
+           |> Builder.to_lines() == {:error, {:timestamp_out_of_order, 1}}
+
+    assert Builder.new("s")
+           |> Builder.tool_call("Bash", %{}, id: "t1")
+           |> Builder.tool_call("Bash", %{}, id: "t1")
+           |> Builder.write("unused.jsonl") == {:error, {:duplicate_tool_id, "t1"}}
+  end
+
+  test "writes the same file for the same session" do
+    builder = Builder.new("s") |> Builder.user("hi") |> Builder.assistant("hello")
+    assert Builder.to_lines(builder) == Builder.to_lines(builder)
+  end
+
+  test "converts parsed sessions back to JSONL" do
+    {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+    attrs = session |> Builder.from_session() |> parse!()
+
+    assert Enum.map(attrs.blocks, &{&1.block_type, &1.content}) ==
+             Enum.map(session.blocks, &{&1.block_type, &1.content})
+
+    assert Enum.map(attrs.blocks, &(&1.metadata && &1.metadata["output"])) ==
+             Enum.map(session.blocks, &(&1.metadata && &1.metadata["output"]))
+
+    {:ok, jsonl} = Exporters.export(session, "claude-code")
+    assert IO.iodata_to_binary(jsonl) =~ ~s("sessionId":"test-abc-123")
+  end
+
+  test "keeps sessions with timestamps going backwards in order" do
+    blocks = [
+      %{block_type: :user_prompt, content: "a", timestamp: ~U[2026-10-17 10:00:00Z]},
+      %{block_type: :user_prompt, content: "b", timestamp: ~U[2026-10-17 09:00:00Z]},
+      %{block_type: :user_prompt, content: "c", timestamp: ~U[2026-10-17 11:00:00Z]}
+    ]
+
+    session = %{external_id: "s", project: nil, started_at: nil, blocks: blocks}
+    attrs = session |> Builder.from_session() |> parse!()
+
+    assert Enum.map(attrs.blocks, &{&1.content, &1.timestamp}) == [
+             {"a", ~U[2026-10-17 10:00:00Z]},
+             {"b", ~U[2026-10-17 10:00:00Z]},
+             {"c", ~U[2026-10-17 11:00:00Z]}
+           ]
+  end
+end
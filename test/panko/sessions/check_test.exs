@@ -0,0 +1,75 @@
+defmodule Panko.Sessions.CheckTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Check
+
+  @fixtures_dir Path.join([__DIR__, "../../fixtures"])
+
+  test "passes a valid session" do
+    result = Check.check_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+
+    assert result.ok
+    assert result.source_type == :claude_code
+    assert result.blocks > 0
+    refute Enum.any?(result.diagnostics, &(&1.level == :error))
+  end
+
+  @tag :tmp_dir
+  test "warns about invalid lines", %{tmp_dir: tmp_dir} do
+    path = Path.join(tmp_dir, "broken.jsonl")
+    valid = File.read!(Path.join(@fixtures_dir, "simple_session.jsonl"))
+    File.write!(path, "{not json\n" <> valid)
+
+    result = Check.check_file(path)
+
+    assert result.ok
+    assert %{level: :warning, line: 1} = Enum.find(result.diagnostics, &(&1.line == 1))
+  end
+
+  @tag :tmp_dir
+  test "does not warn about lines the parser recovers", %{tmp_dir: tmp_dir} do
+    path = Path.join(tmp_dir, "recovered.jsonl")
+    valid = File.read!(Path.join(@fixtures_dir, "simple_session.jsonl"))
+    [first | rest] = String.split(valid, "\n")
+    File.write!(path, Enum.join(["\0\0" <> first | rest], "\n"))
+
+    assert Check.check_file(path).diagnostics |> Enum.filter(& &1.line) == []
+  end
+
+  @tag :tmp_dir
+  test "fails files without blocks", %{tmp_dir: tmp_dir} do
+    path = Path.join(tmp_dir, "empty.jsonl")
+    File.write!(path, "")
+
+    refute Check.check_file(path).ok
+  end
+
+  test "fails files no parser understands" do
+    result = Check.check_file("notes.txt")
+
+    refute result.ok
+    assert [%{message: "no parser for this file type"}] = result.diagnostics
+  end
+
+  @tag :tmp_dir
+  test "expands directories and globs", %{tmp_dir: tmp_dir} do
+    nested = Path.join(tmp_dir, "project")
+    File.mkdir_p!(nested)
+
+    for name <- ["a.jsonl", "project/b.jsonl", "project/notes.txt"] do
+      File.write!(Path.join(tmp_dir, name), "")
+    end
+
+    assert Enum.sort(Check.expand_paths([tmp_dir])) ==
+             [Path.join(tmp_dir, "a.jsonl"), Path.join(nested, "b.jsonl")]
+
+    assert Check.expand_paths([Path.join(tmp_dir, "*.jsonl")]) == [Path.join(tmp_dir, "a.jsonl")]
+  end
+
+  test "checks many files in order" do
+    paths =
+      Enum.map(["simple_session.jsonl", "complex_session.jsonl"], &Path.join(@fixtures_dir, &1))
+
+    assert Enum.map(Check.check_files(paths), & &1.path) == paths
+  end
+end
@@ -0,0 +1,62 @@
+defmodule Panko.Sessions.CommentTest do
+  use Panko.DataCase, async: true
+
+  alias Panko.Sessions
+
+  setup do
+    {:ok, session} = Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    %{session: session}
+  end
+
+  test "lists comments by block position", %{session: session} do
+    {:ok, _} = Sessions.add_comment(session.id, 2, "Why ls?", %{author: "ana"})
+    {:ok, _} = Sessions.add_comment(session.id, 0, "Good prompt")
+
+    assert [%{position: 0, author: nil}, %{position: 2, author: "ana"}] =
+             Sessions.list_session_comments!(session.id)
+  end
+
+  test "rejects empty comments", %{session: session} do
+    assert {:error, _} = Sessions.add_comment(session.id, 0, "   ")
+  end
+
+  test "survive re-importing the session", %{session: session} do
+    {:ok, _} = Sessions.add_comment(session.id, 1, "Keep me")
+    {:ok, reimported} = Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+
+    assert reimported.id == session.id
+    assert [%{body: "Keep me"}] = Sessions.list_session_comments!(session.id)
+  end
+
+  test "are included in Markdown exports", %{session: session} do
+    {:ok, _} = Sessions.add_comment(session.id, 0, "Nice\nand short", %{author: "ana"})
+    session = Ash.load!(session, [:blocks, :sub_agents, :comments])
+
+    {:ok, markdown} = Panko.Sessions.Exporters.export(session, :markdown)
+
+    assert IO.iodata_to_binary(markdown) =~ "> **ana** commented:\n>\n> Nice\n> and short\n"
+  end
+
+  test "are included in JSON and HTML exports", %{session: session} do
+    {:ok, _} = Sessions.add_comment(session.id, 0, "Looks right", %{author: "ana"})
+    session = Ash.load!(session, [:blocks, :sub_agents, :comments])
+
+    {:ok, json} = Panko.Sessions.Exporters.export(session, :json)
+    {:ok, html} = Panko.Sessions.Exporters.export(session, :html)
+
+    assert [%{"position" => 0, "author" => "ana", "body" => "Looks right"}] =
+             Jason.decode!(json)["comments"]
+
+    assert IO.iodata_to_binary(html) =~ "Looks right"
+  end
+
+  test "are redacted in exports", %{session: session} do
+    {:ok, _} = Sessions.add_comment(session.id, 0, "password: hunter2hunter2")
+    session = Ash.load!(session, [:blocks, :sub_agents, :comments])
+
+    {:ok, json, report} = Panko.Sessions.Exporters.export_with_report(session, :json)
+
+    assert [%{"body" => "password: [REDACTED]"}] = Jason.decode!(json)["comments"]
+    assert report.redactions_by_rule[:credential] == 1
+  end
+end
@@ -0,0 +1,31 @@
+defmodule Panko.Sessions.DiffTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.{Diff, Exporters}
+
+  @fixtures_dir Path.join([__DIR__, "../../fixtures"])
+
+  setup do
+    {:ok, simple} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+    {:ok, complex} = Exporters.load_file(Path.join(@fixtures_dir, "complex_session.jsonl"))
+    %{simple: simple, complex: complex}
+  end
+
+  test "identical sessions do not diverge", %{simple: simple} do
+    diff = Diff.compare(simple, simple)
+
+    assert diff.diverges_at == nil
+    assert diff.tools.a == diff.tools.b
+    assert diff.files.only_a == []
+  end
+
+  test "reports the first differing prompt, tools and files", %{simple: simple, complex: complex} do
+    diff = Diff.compare(simple, complex)
+
+    assert diff.diverges_at == 1
+    assert diff.tools.a == %{"Bash" => 1}
+    assert diff.tools.b["Write"] == 1
+    assert diff.files.only_b == ["/home/user/complex-project/lib/helper.ex"]
+    assert diff.files.both == []
+  end
+end
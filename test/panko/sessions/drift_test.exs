@@ -0,0 +1,90 @@
+defmodule Panko.Sessions.DriftTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Drift
+
+  defp edit(position, name, path, input) do
+    metadata = %{"name" => name, "path" => path, "input" => input}
+    %{position: position, block_type: :file_edit, metadata: metadata}
+  end
+
+  defp replace(position, path, old, new),
+    do: edit(position, "Edit", path, %{"old_string" => old, "new_string" => new})
+
+  defp statuses(blocks, files) do
+    read = fn path -> if files[path], do: {:ok, files[path]}, else: {:error, :enoent} end
+    %{blocks: blocks} |> Drift.check(read) |> Enum.map(& &1.status)
+  end
+
+  test "reports edits that are present, reverted or changed" do
+    blocks = [
+      replace(1, "/app/a.ex", "old()", "new()"),
+      replace(2, "/app/b.ex", "old()", "new()"),
+      replace(3, "/app/c.ex", "old()", "new()"),
+      replace(4, "/app/d.ex", "old()", "new()")
+    ]
+
+    files = %{
+      "/app/a.ex" => "call(new())",
+      "/app/b.ex" => "call(old())",
+      "/app/c.ex" => "call(other())"
+    }
+
+    assert statuses(blocks, files) == [:present, :reverted, :changed, :missing]
+  end
+
+  test "compares writes with the whole file" do
+    blocks = [
+      edit(1, "Write", "/app/a.ex", %{"content" => "a\n"}),
+      edit(2, "Write", "/app/b.ex", %{"content" => "b\n"})
+    ]
+
+    assert statuses(blocks, %{"/app/a.ex" => "a\n", "/app/b.ex" => "b, edited\n"}) ==
+             [:present, :changed]
+  end
+
+  test "skips edits rewritten later in the session" do
+    blocks = [
+      edit(1, "Write", "/app/a.ex", %{"content" => "one()\n"}),
+      replace(2, "/app/a.ex", "one()", "two()"),
+      replace(3, "/app/a.ex", "two()", "three()")
+    ]
+
+    assert statuses(blocks, %{"/app/a.ex" => "three()\n"}) == [:superseded, :superseded, :present]
+  end
+
+  test "summarizes and flags drift" do
+    entries = Drift.check(%{blocks: [replace(1, "/app/a.ex", "x", "y")]}, fn _ -> {:ok, "x"} end)
+
+    assert Drift.summary(entries).reverted == 1
+    assert Drift.drifted?(entries)
+    refute Drift.drifted?([])
+  end
+
+  @tag :tmp_dir
+  test "reads the working tree relative to the repository", %{tmp_dir: dir} do
+    File.write!(Path.join(dir, "a.ex"), "new()")
+    read = Drift.worktree_reader(dir)
+
+    assert read.("a.ex") == {:ok, "new()"}
+    assert read.(Path.join(dir, "a.ex")) == {:ok, "new()"}
+  end
+
+  @tag :tmp_dir
+  test "reads files of a project in a subdirectory of a repository", %{tmp_dir: repo} do
+    project = Path.join(repo, "apps/web")
+    File.mkdir_p!(project)
+    File.write!(Path.join(project, "a.ex"), "call(new())")
+
+    git = fn args -> {_, 0} = System.cmd("git", args, cd: repo, stderr_to_stdout: true) end
+    git.(["init", "--quiet"])
+    git.(["add", "."])
+    git.(["-c", "user.name=Panko", "-c", "user.email=panko@example.com", "commit", "-qm", "a"])
+
+    read = Drift.git_reader(project, "HEAD")
+
+    assert read.(Path.join(project, "a.ex")) == {:ok, "call(new())"}
+    assert read.("a.ex") == {:ok, "call(new())"}
+    assert {:error, _} = read.(Path.join(project, "b.ex"))
+  end
+end
@@ -0,0 +1,47 @@
+defmodule Panko.Sessions.Exporters.BundleTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Exporters.Bundle
+
+  @fixtures_dir Path.join([__DIR__, "../../../fixtures"])
+
+  setup do
+    sessions =
+      for name <- ["simple_session.jsonl", "complex_session.jsonl"] do
+        {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, name))
+        session
+      end
+
+    %{sessions: sessions}
+  end
+
+  test "markdown concatenates transcripts after an index", %{sessions: sessions} do
+    assert {:ok, iodata, report} = Bundle.build(sessions, :markdown)
+    markdown = IO.iodata_to_binary(iodata)
+
+    assert markdown =~ "2 sessions"
+    assert markdown =~ "1. List the files in the current directory"
+    assert markdown =~ "# List the files in the current directory\n"
+    assert markdown =~ "# Create a helper module"
+    refute markdown =~ "external_id:"
+    assert report.redactions == 0
+  end
+
+  test "zip holds an index and one file per session", %{sessions: sessions} do
+    assert {:ok, zip, _report} = Bundle.build(sessions, "zip", format: :json)
+    assert {:ok, files} = :zip.unzip(zip, [:memory])
+
+    names = files |> Enum.map(fn {name, _} -> to_string(name) end) |> Enum.sort()
+    assert "index.md" in names
+    assert Enum.count(names, &String.ends_with?(&1, ".json")) == 2
+
+    {_name, index} = Enum.find(files, fn {name, _} -> name == ~c"index.md" end)
+    assert index =~ ~r/\[List the files in the current directory\]\(\d{3}-list-the-files/
+  end
+
+  test "rejects unknown bundles and formats", %{sessions: sessions} do
+    assert {:error, :unknown_bundle} = Bundle.build(sessions, "tar")
+    assert {:error, :unknown_format} = Bundle.build(sessions, :zip, format: :docx)
+  end
+end
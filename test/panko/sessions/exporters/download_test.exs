@@ -0,0 +1,36 @@
+defmodule Panko.Sessions.Exporters.DownloadTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Exporters.Download
+
+  @fixture Path.join([__DIR__, "../../../fixtures/simple_session.jsonl"])
+
+  @tag :tmp_dir
+  test "writes sessions without overwriting existing files", %{tmp_dir: dir} do
+    {:ok, session} = Exporters.load_file(@fixture)
+    dir = Path.join(dir, "exports")
+
+    assert {:ok, first, _report} = Download.write(session, dir, :markdown)
+    assert {:ok, second, _report} = Download.write(session, dir, :markdown)
+    assert {:ok, third, _report} = Download.write(session, dir, :markdown)
+
+    assert Path.basename(first) == Download.slug(session) <> ".md"
+    assert Path.basename(second) == Download.slug(session) <> "-20260309-120000.md"
+    assert Path.basename(third) == Download.slug(session) <> "-20260309-120000-2.md"
+    assert File.read!(first) == File.read!(third)
+  end
+
+  test "names files after the session title" do
+    assert Download.slug(%{title: "Fix the *build*, again!", external_id: "abc"}) ==
+             "fix-the-build-again"
+
+    assert Download.slug(%{title: nil, external_id: "abc-123"}) == "abc-123"
+    assert Download.slug(%{title: "???", external_id: nil}) == "session"
+  end
+
+  test "reports unknown formats" do
+    {:ok, session} = Exporters.load_file(@fixture)
+    assert Download.write(session, "/tmp", :pdf) == {:error, :unknown_format}
+  end
+end
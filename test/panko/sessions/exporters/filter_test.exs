@@ -0,0 +1,59 @@
+defmodule Panko.Sessions.Exporters.FilterTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Exporters.Filter
+
+  @fixtures_dir Path.join([__DIR__, "../../../fixtures"])
+
+  setup do
+    {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "complex_session.jsonl"))
+    %{session: session}
+  end
+
+  defp types(session), do: Enum.map(session.blocks, & &1.block_type)
+
+  test "keeps everything by default", %{session: session} do
+    assert Filter.filter(session, []).blocks == session.blocks
+  end
+
+  test "drops thinking blocks", %{session: session} do
+    refute :thinking in types(Filter.filter(session, thinking: false))
+  end
+
+  test "keeps only the given block types", %{session: session} do
+    filtered = Filter.filter(session, only: [:user_prompt, :assistant_response])
+    assert Enum.uniq(types(filtered)) == [:user_prompt, :assistant_response]
+  end
+
+  test "excludes the given block types", %{session: session} do
+    filtered = Filter.filter(session, exclude: [:file_edit, :sub_agent_spawn])
+    refute :file_edit in types(filtered)
+    refute :sub_agent_spawn in types(filtered)
+  end
+
+  test "keeps blocks within a position range", %{session: session} do
+    filtered = Filter.filter(session, range: 1..2)
+    assert Enum.map(filtered.blocks, & &1.position) == [1, 2]
+  end
+
+  test "keeps blocks within a time window", %{session: session} do
+    filtered = Filter.filter(session, since: ~U[2026-03-10 08:00:05Z])
+    assert [%{block_type: :assistant_response}] = filtered.blocks
+  end
+
+  test "strips tool output and system text", %{session: session} do
+    [first | rest] = session.blocks
+
+    tool = %{first | block_type: :tool_call, metadata: %{"name" => "Bash", "output" => "ok"}}
+    noisy = %{first | content: "hi<system-reminder>secret</system-reminder>"}
+    empty = %{first | content: "<system-reminder>only</system-reminder>"}
+    session = %{session | blocks: [tool, noisy, empty | rest]}
+
+    filtered = Filter.filter(session, tool_output: false, system: false)
+
+    assert [%{metadata: %{"name" => "Bash"} = metadata}, %{content: "hi"} | _] = filtered.blocks
+    refute Map.has_key?(metadata, "output")
+    assert length(filtered.blocks) == length(session.blocks) - 1
+  end
+end
@@ -0,0 +1,72 @@
+defmodule Panko.Sessions.Exporters.MessagesTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Exporters
+
+  @fixtures_dir Path.join([__DIR__, "../../../fixtures"])
+
+  setup do
+    {:ok, simple} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+    {:ok, complex} = Exporters.load_file(Path.join(@fixtures_dir, "complex_session.jsonl"))
+    %{simple: simple, complex: complex}
+  end
+
+  defp export!(session, format) do
+    {:ok, json} = Exporters.export(session, format)
+    Jason.decode!(json)
+  end
+
+  describe "openai-messages" do
+    test "maps tool calls to function calls with tool messages", %{simple: session} do
+      assert [user, assistant, tool, final] = export!(session, "openai-messages")
+
+      assert user == %{"role" => "user", "content" => "List the files in the current directory"}
+      assert assistant["content"] == "I'll list the files for you."
+
+      assert [%{"id" => "toolu_1", "type" => "function", "function" => function}] =
+               assistant["tool_calls"]
+
+      assert function["name"] == "Bash"
+      assert Jason.decode!(function["arguments"]) == %{"command" => "ls -la"}
+
+      assert tool["role"] == "tool"
+      assert tool["tool_call_id"] == "toolu_1"
+      assert tool["content"] =~ "file.txt"
+
+      assert final["role"] == "assistant"
+      refute Map.has_key?(final, "tool_calls")
+    end
+
+    test "omits thinking blocks", %{complex: session} do
+      messages = export!(session, :openai_messages)
+      contents = messages |> Enum.map(& &1["content"]) |> Enum.filter(&is_binary/1)
+      refute Enum.any?(contents, &(&1 =~ "think about how to structure"))
+    end
+  end
+
+  describe "anthropic-messages" do
+    test "maps tool calls to tool_use and tool_result blocks", %{simple: session} do
+      assert [user, assistant, results, final] = export!(session, "anthropic-messages")
+
+      assert user["role"] == "user"
+      assert [%{"type" => "text", "text" => "I'll list the files for you."}, tool_use] =
+               assistant["content"]
+
+      assert tool_use == %{
+               "type" => "tool_use",
+               "id" => "toolu_1",
+               "name" => "Bash",
+               "input" => %{"command" => "ls -la"}
+             }
+
+      assert results["role"] == "user"
+      assert [%{"type" => "tool_result", "tool_use_id" => "toolu_1"}] = results["content"]
+      assert final["role"] == "assistant"
+    end
+
+    test "roles alternate", %{complex: session} do
+      roles = session |> export!(:anthropic_messages) |> Enum.map(& &1["role"])
+      assert roles |> Enum.chunk_every(2, 1, :discard) |> Enum.all?(fn [a, b] -> a != b end)
+    end
+  end
+end
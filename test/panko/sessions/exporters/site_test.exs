@@ -0,0 +1,45 @@
+defmodule Panko.Sessions.Exporters.SiteTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Exporters.Site
+
+  @fixtures_dir Path.join([__DIR__, "../../../fixtures"])
+
+  @tag :tmp_dir
+  test "writes a page per session and an index grouped by project", %{tmp_dir: dir} do
+    sessions =
+      for name <- ["simple_session.jsonl", "complex_session.jsonl"] do
+        {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, name))
+        session
+      end
+
+    assert {:ok, %{pages: 2}} = Site.build(sessions, dir)
+
+    index = File.read!(Path.join(dir, "index.html"))
+    assert index =~ "/home/user/my-project"
+    assert index =~ "/home/user/complex-project"
+    assert index =~ ~s(href="sessions/test-abc-123.html")
+
+    page = File.read!(Path.join([dir, "sessions", "test-abc-123.html"]))
+    assert page =~ ~s(<link rel="stylesheet" href="../assets/app.css")
+    assert page =~ ~s(href="../index.html")
+    refute page =~ "<style>"
+    assert File.exists?(Path.join([dir, "assets", "app.css"]))
+  end
+
+  @tag :tmp_dir
+  test "gives clashing sessions their own page", %{tmp_dir: dir} do
+    {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+    copy = %{session | title: "Second copy"}
+
+    assert {:ok, %{pages: 2}} = Site.build([session, copy], dir)
+
+    assert File.read!(Path.join([dir, "sessions", "test-abc-123.html"])) =~ session.title
+    assert File.read!(Path.join([dir, "sessions", "test-abc-123-2.html"])) =~ "Second copy"
+
+    index = File.read!(Path.join(dir, "index.html"))
+    assert index =~ ~s(href="sessions/test-abc-123.html")
+    assert index =~ ~s(href="sessions/test-abc-123-2.html")
+  end
+end
@@ -0,0 +1,248 @@
+defmodule Panko.Sessions.ExportersTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Exporters
+
+  @fixtures_dir Path.join([__DIR__, "../../fixtures"])
+
+  describe "load_file/1" do
+    test "builds an in-memory session with blocks" do
+      path = Path.join(@fixtures_dir, "complex_session.jsonl")
+      assert {:ok, session} = Exporters.load_file(path)
+
+      assert session.external_id == "test-complex-456"
+      assert session.id != nil
+      assert [%Panko.Sessions.Block{} | _] = session.blocks
+      assert Enum.all?(session.blocks, & &1.id)
+      assert [%Panko.Sessions.SubAgent{}] = session.sub_agents
+    end
+
+    test "returns error for unknown files" do
+      assert {:error, :no_parser_found} = Exporters.load_file("/tmp/session.txt")
+    end
+  end
+
+  describe "export/3" do
+    setup do
+      {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+      %{session: session}
+    end
+
+    test "returns error for unknown formats", %{session: session} do
+      assert {:error, :unknown_format} = Exporters.export(session, "docx")
+    end
+
+    test "html produces a standalone document", %{session: session} do
+      assert {:ok, iodata} = Exporters.export(session, :html)
+      html = IO.iodata_to_binary(iodata)
+
+      assert html =~ "<!DOCTYPE html>"
+      assert html =~ "<title>List the files in the current directory · Panko</title>"
+      assert html =~ "ls -la"
+      assert html =~ "body { margin: 0; }"
+      assert html =~ "window.pankoExport = true"
+      refute html =~ "phx-track-static"
+    end
+
+    @tag :tmp_dir
+    test "html fails without built assets", %{session: session, tmp_dir: dir} do
+      assert {:error, :assets_missing} = Exporters.export(session, :html, assets_dir: dir)
+
+      assert {:error, :assets_missing} =
+               Exporters.export(session, :html, assets_dir: dir, stylesheet_href: "app.css")
+    end
+
+    test "json follows the versioned schema", %{session: session} do
+      assert {:ok, json} = Exporters.export(session, :json)
+      document = Jason.decode!(json)
+
+      assert IO.iodata_to_binary(json) =~ ~r/\A\{\s*"schema": "panko.session"/
+      assert document["schema"] == "panko.session"
+      assert document["schema_version"] == 1
+      assert document["session"]["external_id"] == "test-abc-123"
+      assert document["session"]["source_type"] == "claude_code"
+      assert document["usage"]["input_tokens"] == 300
+      assert document["usage"]["output_tokens"] == 80
+
+      [prompt | _] = document["blocks"]
+      assert prompt["type"] == "user_prompt"
+      assert prompt["role"] == "user"
+      assert prompt["tool"] == nil
+
+      tool = Enum.find(document["blocks"], &(&1["type"] == "tool_call"))
+      assert tool["role"] == "assistant"
+      assert tool["tool"]["name"] == "Bash"
+      assert tool["tool"]["input"] == %{"command" => "ls -la"}
+    end
+
+    test "markdown writes front matter and a heading per block", %{session: session} do
+      assert {:ok, iodata} = Exporters.export(session, :markdown)
+      markdown = IO.iodata_to_binary(iodata)
+
+      assert markdown =~ ~r/\A---\ntitle: "List the files in the current directory"\n/
+      assert markdown =~ "external_id: \"test-abc-123\""
+      assert markdown =~ "# List the files in the current directory\n"
+      assert markdown =~ "## User · 12:00:00"
+      assert markdown =~ "## Tool: Bash"
+      assert markdown =~ "```json\n{\n  \"command\": \"ls -la\"\n}\n```"
+    end
+
+    test "markdown and html link each prompt from a table of contents", %{session: session} do
+      follow_up = %Panko.Sessions.Block{
+        block_type: :user_prompt,
+        position: length(session.blocks),
+        content: "Now show [hidden] files"
+      }
+
+      session = %{session | blocks: session.blocks ++ [follow_up]}
+
+      {:ok, iodata} = Exporters.export(session, :markdown)
+      markdown = IO.iodata_to_binary(iodata)
+      assert markdown =~ "## Contents\n\n1. [List the files in the current directory](#block-0)\n"
+      assert markdown =~ "1. [Now show \\[hidden\\] files](#block-#{follow_up.position})"
+      assert markdown =~ ~s(<a id="block-#{follow_up.position}"></a>\n## User)
+
+      {:ok, iodata} = Exporters.export(session, :markdown, toc: false)
+      refute IO.iodata_to_binary(iodata) =~ "## Contents"
+
+      {:ok, iodata} = Exporters.export(session, :html)
+      html = IO.iodata_to_binary(iodata)
+      assert html =~ ~s(href="#block-#{follow_up.position}")
+      assert html =~ ~s(id="block-#{follow_up.position}")
+    end
+
+    test "markdown can omit front matter", %{session: session} do
+      {:ok, iodata} = Exporters.export(session, :markdown, front_matter: false)
+      assert IO.iodata_to_binary(iodata) =~ ~r/\A# List the files/
+    end
+  end
+
+  describe "export_with_report/3" do
+    setup do
+      {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+      [first | rest] = session.blocks
+      first = %{first | content: "use OPENAI_API_KEY=sk-abcdefghijklmnopqrstuvwx please"}
+      %{session: %{session | blocks: [first | rest]}}
+    end
+
+    test "redacts secrets by default and reports them", %{session: session} do
+      assert {:ok, iodata, report} = Exporters.export_with_report(session, :markdown)
+      markdown = IO.iodata_to_binary(iodata)
+
+      refute markdown =~ "sk-abcdefghijklmnopqrstuvwx"
+      assert markdown =~ "[REDACTED]"
+      assert report.redactions >= 1
+      assert report.redactions_by_rule[:openai_key] == 1
+    end
+
+    test "keeps content with redact: false", %{session: session} do
+      assert {:ok, iodata, %{redactions: 0}} =
+               Exporters.export_with_report(session, :markdown, redact: false)
+
+      assert IO.iodata_to_binary(iodata) =~ "sk-abcdefghijklmnopqrstuvwx"
+    end
+  end
+
+  describe "summary" do
+    test "digests goal, changes, outcome and usage" do
+      {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "complex_session.jsonl"))
+      {:ok, iodata} = Exporters.export(session, :summary)
+      summary = IO.iodata_to_binary(iodata)
+
+      assert summary =~ "## Goal\n\nCreate a helper module and explore the codebase"
+      assert summary =~ "- `/home/user/complex-project/lib/helper.ex`"
+      assert summary =~ "- Explore the codebase structure"
+      assert summary =~ "## Outcome\n\nI've created the helper module"
+      assert summary =~ "- Input tokens: 1300"
+    end
+
+    test "lists shell commands and can wrap the digest for an LLM" do
+      {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+      {:ok, iodata} = Exporters.export(session, :summary, llm_prompt: true)
+      summary = IO.iodata_to_binary(iodata)
+
+      assert summary =~ ~r/\ASummarize the following AI coding session/
+      assert summary =~ "- `ls -la`"
+    end
+  end
+
+  describe "prompts and commands" do
+    setup do
+      {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+      %{session: session}
+    end
+
+    test "prompts lists user messages only, numbered", %{session: session} do
+      {:ok, iodata} = Exporters.export(session, :prompts)
+      prompts = IO.iodata_to_binary(iodata)
+
+      assert prompts =~ ~r/\A## 1 · 12:00:00\n\nList the files in the current directory\n/
+      refute prompts =~ "ls -la"
+    end
+
+    test "commands writes an annotated script", %{session: session} do
+      {:ok, iodata} = Exporters.export(session, :commands)
+      script = IO.iodata_to_binary(iodata)
+
+      assert script =~ ~r/\A#!\/usr\/bin\/env bash\n/
+      assert script =~ ~r/# #1 · \d\d:\d\d:\d\d · exit 0 · 1.0s\nls -la\n/
+    end
+
+    test "exit codes come from tool results" do
+      alias Panko.Sessions.Exporters.Commands
+
+      failed = %{metadata: %{"is_error" => true, "output" => "Exit code 2\nno such file"}}
+      assert Commands.exit_code(failed) == 2
+      assert Commands.exit_code(%{metadata: %{"is_error" => true, "output" => "boom"}}) == nil
+      assert Commands.exit_code(%{metadata: %{"output" => "ok"}}) == 0
+      assert Commands.exit_code(%{metadata: %{"name" => "Bash"}}) == nil
+    end
+  end
+
+  describe "template" do
+    setup do
+      {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+      %{session: session}
+    end
+
+    @tag :tmp_dir
+    test "renders an EEx template with session assigns", %{session: session, tmp_dir: dir} do
+      path = Path.join(dir, "report.md.eex")
+
+      File.write!(path, """
+      # Incident: <%= @session.title %>
+      <%= for block <- @blocks, block.block_type == :user_prompt do %>
+      - <%= block.content %><% end %>
+      Tokens in: <%= @usage.input_tokens %>, commands: <%= Enum.join(@summary.commands, ";") %>
+      """)
+
+      assert {:ok, iodata} = Exporters.export(session, :template, template: path)
+      report = IO.iodata_to_binary(iodata)
+
+      assert report =~ "# Incident: List the files in the current directory"
+      assert report =~ "- List the files in the current directory"
+      assert report =~ "Tokens in: 300, commands: ls -la"
+    end
+
+    @tag :tmp_dir
+    test "reports template problems", %{session: session, tmp_dir: dir} do
+      assert {:error, :template_required} = Exporters.export(session, :template)
+
+      assert {:error, {:template_error, _}} =
+               Exporters.export(session, :template, template: Path.join(dir, "missing.eex"))
+
+      path = Path.join(dir, "broken.eex")
+      File.write!(path, "<%= @session.nope %>")
+      assert {:error, {:template_error, _}} = Exporters.export(session, :template, template: path)
+    end
+  end
+
+  describe "Markdown.fence/2" do
+    alias Panko.Sessions.Exporters.Markdown
+
+    test "uses a fence longer than any backtick run in the content" do
+      assert Markdown.fence("plain") == "```\nplain\n```\n"
+      assert Markdown.fence("```elixir\n:ok\n```", "md") == "````md\n```elixir\n:ok\n```\n````\n"
+    end
+  end
+end
@@ -0,0 +1,32 @@
+defmodule Panko.Sessions.GrepTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.{Exporters, Grep}
+
+  @fixtures_dir Path.join([__DIR__, "../../fixtures"])
+
+  setup do
+    {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+    %{session: session}
+  end
+
+  test "finds matching lines with block and line references", %{session: session} do
+    assert [hit] = Grep.grep(session, ~r/current directory/)
+    assert hit.position == 0
+    assert hit.line == 1
+    assert hit.block_type == :user_prompt
+  end
+
+  test "includes context lines" do
+    block = %{position: 3, block_type: :assistant_response, content: "a\nb\nc\nd", metadata: nil}
+
+    assert [%{line: 3, before: ["b"], after: ["d"]}] =
+             Grep.grep(%{blocks: [block]}, ~r/^c$/, before: 1, after: 1)
+  end
+
+  test "searches tool output" do
+    block = %{position: 1, block_type: :tool_call, content: nil, metadata: %{"output" => "ok"}}
+
+    assert [%{line: 1, text: "ok"}] = Grep.grep(%{blocks: [block]}, ~r/ok/)
+  end
+end
@@ -16,6 +16,19 @@ defmodule Panko.Sessions.ImportTest do
       assert length(session.blocks) > 0
     end
 
+    test "caches token usage on the session" do
+      path = Path.join(@fixtures_dir, "simple_session.jsonl")
+      assert {:ok, session} = Panko.Sessions.import_from_file(path)
+
+      assert session.input_tokens == 300
+      assert session.output_tokens == 80
+      assert_in_delta session.estimated_cost_usd, (300 * 5 + 80 * 25) / 1_000_000, 1.0e-9
+
+      assert [row] = Panko.Sessions.Stats.stored_rows(%{})
+      assert row.input_tokens == 300
+      assert row.tools == nil
+    end
+
     test "upserts on reimport (same external_id)" do
       path = Path.join(@fixtures_dir, "simple_session.jsonl")
 
@@ -25,6 +38,22 @@ defmodule Panko.Sessions.ImportTest do
       assert session1.id == session2.id
     end
 
+    @tag :tmp_dir
+    test "imports a Panko JSON export", %{tmp_dir: dir} do
+      fixture = Path.join(@fixtures_dir, "simple_session.jsonl")
+      {:ok, original} = Panko.Sessions.Exporters.load_file(fixture)
+      {:ok, json} = Panko.Sessions.Exporters.export(original, :json)
+      path = Path.join(dir, "archived.json")
+      File.write!(path, json)
+
+      assert {:ok, session} = Panko.Sessions.import_from_file(path)
+      assert session.external_id == "test-abc-123"
+      assert session.source_path == path
+
+      session = Ash.load!(session, [:blocks])
+      assert length(session.blocks) == length(original.blocks)
+    end
+
     test "returns error for unparseable file" do
       assert {:error, _} = Panko.Sessions.import_from_file("/tmp/nonexistent.jsonl")
     end
@@ -0,0 +1,34 @@
+defmodule Panko.Sessions.ParseCacheTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.ParseCache
+
+  @fixture Path.join([__DIR__, "../../fixtures/simple_session.jsonl"])
+
+  @moduletag :tmp_dir
+
+  setup %{tmp_dir: dir} do
+    path = Path.join(dir, "session.jsonl")
+    File.cp!(@fixture, path)
+    %{path: path}
+  end
+
+  test "reuses the parse of an unchanged file", %{path: path} do
+    assert {:ok, attrs} = ParseCache.parse(path)
+    assert [{^path, _stamp, ^attrs, _used}] = :ets.lookup(ParseCache, path)
+    assert {:ok, ^attrs} = ParseCache.parse(path)
+  end
+
+  test "parses the file again once it changes", %{path: path} do
+    {:ok, attrs} = ParseCache.parse(path)
+    [first | _] = path |> File.read!() |> String.split("\n")
+    File.write!(path, first <> "\n")
+
+    assert {:ok, changed} = ParseCache.parse(path)
+    assert length(changed.blocks) < length(attrs.blocks)
+  end
+
+  test "passes errors through without caching them", %{tmp_dir: dir} do
+    assert {:error, :no_parser_found} = ParseCache.parse(Path.join(dir, "notes.txt"))
+  end
+end
@@ -51,6 +51,30 @@ defmodule Panko.Sessions.Parsers.ClaudeCodeTest do
       assert tool_block.metadata["input"] == %{"command" => "ls -la"}
     end
 
+    test "attaches message usage to the first block of each assistant message" do
+      path = Path.join(@fixtures_dir, "simple_session.jsonl")
+      {:ok, attrs} = ClaudeCode.parse(path)
+
+      [first_response | _] = Enum.filter(attrs.blocks, &(&1.block_type == :assistant_response))
+      assert first_response.metadata["usage"] == %{"input_tokens" => 100, "output_tokens" => 50}
+      assert first_response.metadata["message_id"] == "msg_1"
+      assert first_response.metadata["model"] == "claude-opus-4-6"
+
+      tool_block = Enum.find(attrs.blocks, &(&1.block_type == :tool_call))
+      refute Map.has_key?(tool_block.metadata, "usage")
+    end
+
+    test "folds tool results into the originating block" do
+      path = Path.join(@fixtures_dir, "simple_session.jsonl")
+      {:ok, attrs} = ClaudeCode.parse(path)
+
+      tool_block = Enum.find(attrs.blocks, &(&1.block_type == :tool_call))
+      assert tool_block.metadata["id"] == "toolu_1"
+      assert tool_block.metadata["output"] == "total 4\n-rw-r--r-- 1 user user 100 file.txt"
+      assert tool_block.metadata["duration_ms"] == 1000
+      refute Map.has_key?(tool_block.metadata, "is_error")
+    end
+
     test "returns error for non-existent file" do
       assert {:error, _} = ClaudeCode.parse("/nonexistent/file.jsonl")
     end
@@ -131,6 +155,43 @@ defmodule Panko.Sessions.Parsers.ClaudeCodeTest do
       assert hd(user_blocks).content == "Create a helper module and explore the codebase"
     end
 
+    @tag :tmp_dir
+    test "uses the summary record of this session as the title", %{tmp_dir: dir} do
+      lines = File.read!(Path.join(@fixtures_dir, "simple_session.jsonl"))
+
+      summaries = [
+        ~s({"type":"summary","summary":"Earlier session","leafUuid":"elsewhere"}),
+        ~s({"type":"summary","summary":"Listing project files","leafUuid":"a1"})
+      ]
+
+      path = Path.join(dir, "summarized.jsonl")
+      File.write!(path, Enum.join(summaries, "\n") <> "\n" <> lines)
+      assert {:ok, %{title: "Listing project files"}} = ClaudeCode.parse(path)
+
+      File.write!(path, hd(summaries) <> "\n" <> lines)
+      assert {:ok, %{title: "List the files in the current directory"}} = ClaudeCode.parse(path)
+    end
+
+    @tag :tmp_dir
+    test "skips command noise when titling by the first prompt", %{tmp_dir: dir} do
+      caveat = "<local-command-caveat>Caveat: generated by local commands</local-command-caveat>"
+      command = "<command-name>/clear</command-name>\n<command-args></command-args>"
+
+      text = [%{type: "text", text: "Why does\n  the build fail?"}]
+
+      lines =
+        [
+          %{type: "user", sessionId: "s", uuid: "u1", isMeta: true, message: %{content: caveat}},
+          %{type: "user", sessionId: "s", uuid: "u2", message: %{content: command}},
+          %{type: "user", sessionId: "s", uuid: "u3", message: %{content: text}}
+        ]
+        |> Enum.map_join("\n", &Jason.encode!/1)
+
+      path = Path.join(dir, "commands.jsonl")
+      File.write!(path, lines)
+      assert {:ok, %{title: "Why does the build fail?"}} = ClaudeCode.parse(path)
+    end
+
     test "handles empty file" do
       path = Path.join(@fixtures_dir, "empty_session.jsonl")
       File.write!(path, "")
@@ -139,4 +200,87 @@ defmodule Panko.Sessions.Parsers.ClaudeCodeTest do
       File.rm!(path)
     end
   end
+
+  describe "parse_lines/1" do
+    test "matches parse/1 without the source path" do
+      path = Path.join(@fixtures_dir, "complex_session.jsonl")
+      {:ok, from_file} = ClaudeCode.parse(path)
+
+      assert {:ok, from_lines} = path |> File.stream!() |> ClaudeCode.parse_lines()
+      assert from_lines == %{from_file | source_path: nil}
+    end
+
+    test "skips lines that are not JSON objects" do
+      lines = [
+        "\0\0\0",
+        <<0xFF, 0xFE, 0x7B>>,
+        "[1, 2]",
+        "42",
+        ~s({"type":"user","sessionId":"s1","message":{"content":"hello"}})
+      ]
+
+      assert {:ok, attrs} = ClaudeCode.parse_lines(lines)
+      assert attrs.external_id == "s1"
+      assert [%{block_type: :user_prompt, content: "hello"}] = attrs.blocks
+    end
+
+    test "recovers a line with junk before its JSON" do
+      line = "\0\0" <> ~s({"type":"user","sessionId":"s1","message":{"content":"hello"}})
+
+      assert {:ok, %{blocks: [%{content: "hello"}]}} = ClaudeCode.parse_lines([line])
+    end
+
+    test "ignores fields of an unexpected shape" do
+      lines = [
+        ~s({"type":"user","sessionId":7,"cwd":[],"timestamp":1,"message":"hello"}),
+        ~s({"type":"assistant","message":{"content":["text",{"type":"text","text":5}]}}),
+        ~s({"type":"assistant","message":{"content":[{"type":"tool_use","name":"Agent",) <>
+          ~s("input":"x"}]}}),
+        ~s({"type":"user","message":{"content":[{"type":"tool_result",) <>
+          ~s("content":[1,{"type":"text"}]}]}})
+      ]
+
+      assert {:ok, attrs} = ClaudeCode.parse_lines(lines)
+      assert attrs.external_id == "unknown"
+      assert attrs.project == nil
+      assert [%{content: nil}, %{block_type: :sub_agent_spawn}] = attrs.blocks
+      assert [%{agent_type: "unknown", description: ""}] = attrs.sub_agents
+    end
+
+    # ExUnit seeds :rand per test, so a failure reproduces with the same --seed.
+    test "survives randomly corrupted session lines" do
+      corpus =
+        ["simple_session.jsonl", "complex_session.jsonl"]
+        |> Enum.map(&File.read!(Path.join(@fixtures_dir, &1)))
+        |> Enum.flat_map(&String.split(&1, "\n", trim: true))
+
+      for _run <- 1..200 do
+        lines = Enum.map(corpus, &mutate/1)
+
+        assert {:ok, attrs} = ClaudeCode.parse_lines(lines), "failed on: #{inspect(lines)}"
+        assert is_binary(attrs.external_id)
+        positions = Enum.map(attrs.blocks, & &1.position)
+        assert positions == Enum.to_list(0..(length(positions) - 1)//1)
+      end
+    end
+  end
+
+  defp mutate(line) do
+    case :rand.uniform(5) do
+      1 -> binary_part(line, 0, :rand.uniform(byte_size(line)))
+      2 -> :rand.bytes(:rand.uniform(8)) <> line
+      3 -> line |> Jason.decode!() |> scramble() |> Jason.encode!()
+      _ -> line
+    end
+  end
+
+  # Replaces random values of a decoded line with values of another shape.
+  defp scramble(value) do
+    cond do
+      :rand.uniform(8) == 1 -> Enum.random([nil, 0, "x", [], %{}, true])
+      is_map(value) -> Map.new(value, fn {key, inner} -> {key, scramble(inner)} end)
+      is_list(value) -> Enum.map(value, &scramble/1)
+      true -> value
+    end
+  end
 end
@@ -0,0 +1,25 @@
+defmodule Panko.Sessions.Parsers.FastJsonTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Parsers.FastJson
+
+  @fixtures_dir Path.join([__DIR__, "../../../fixtures"])
+
+  test "decodes session lines like Jason" do
+    for name <- ["simple_session.jsonl", "complex_session.jsonl"],
+        line <- @fixtures_dir |> Path.join(name) |> File.stream!() do
+      assert FastJson.decode(line) == Jason.decode(line)
+    end
+  end
+
+  test "decodes null to nil" do
+    assert FastJson.decode(~s({"a": null, "b": [1, 2.5, true]})) ==
+             {:ok, %{"a" => nil, "b" => [1, 2.5, true]}}
+  end
+
+  test "rejects invalid documents" do
+    for data <- ["", "{", ~s({"a": 1} x), <<0xFF>>, ~s({"a": "\xFF"})] do
+      assert {:error, _} = FastJson.decode(data)
+    end
+  end
+end
@@ -0,0 +1,76 @@
+defmodule Panko.Sessions.Parsers.PankoJsonTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Exporters
+  alias Panko.Sessions.Parsers.PankoJson
+
+  @fixtures_dir Path.join([__DIR__, "../../../fixtures"])
+
+  defp export_fixture(name, dir) do
+    {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, name))
+    {:ok, json} = Exporters.export(session, :json)
+    path = Path.join(dir, Path.rootname(name) <> ".json")
+    File.write!(path, json)
+    {session, path}
+  end
+
+  describe "parse/1" do
+    @describetag :tmp_dir
+
+    test "round-trips a JSON export", %{tmp_dir: dir} do
+      {original, path} = export_fixture("complex_session.jsonl", dir)
+      assert {:ok, attrs} = PankoJson.parse(path)
+
+      assert attrs.external_id == original.external_id
+      assert attrs.source_type == :claude_code
+      assert attrs.source_path == path
+      assert attrs.project == original.project
+      assert attrs.title == original.title
+      assert length(attrs.blocks) == length(original.blocks)
+      assert [%{agent_type: "explore", status: :running}] = attrs.sub_agents
+
+      edit = Enum.find(attrs.blocks, &(&1.block_type == :file_edit))
+      assert edit.metadata["path"] == "/home/user/complex-project/lib/helper.ex"
+
+      spawn = Enum.find(attrs.blocks, &(&1.block_type == :sub_agent_spawn))
+      assert spawn.metadata["agent_type"] == "explore"
+    end
+
+    test "keeps session token totals", %{tmp_dir: dir} do
+      {original, path} = export_fixture("simple_session.jsonl", dir)
+      {:ok, attrs} = PankoJson.parse(path)
+
+      assert Panko.Sessions.Usage.totals(attrs.blocks) ==
+               Panko.Sessions.Usage.totals(original.blocks)
+    end
+
+    test "ignores timestamps that are not strings", %{tmp_dir: dir} do
+      {_original, path} = export_fixture("simple_session.jsonl", dir)
+
+      document =
+        path
+        |> File.read!()
+        |> Jason.decode!()
+        |> put_in(["session", "started_at"], 1_700_000_000)
+        |> update_in(["blocks"], fn [block | rest] -> [%{block | "timestamp" => %{}} | rest] end)
+
+      File.write!(path, Jason.encode!(document))
+
+      assert {:ok, attrs} = PankoJson.parse(path)
+      assert %DateTime{} = attrs.started_at
+      assert hd(attrs.blocks).timestamp == nil
+    end
+
+    test "rejects other JSON documents", %{tmp_dir: dir} do
+      path = Path.join(dir, "other.json")
+      File.write!(path, ~s({"schema": "something.else", "schema_version": 1}))
+      assert {:error, :not_a_panko_export} = PankoJson.parse(path)
+
+      File.write!(path, ~s({"schema": "panko.session", "schema_version": 99}))
+      assert {:error, {:unsupported_schema_version, 99}} = PankoJson.parse(path)
+
+      File.write!(path, "not json")
+      assert {:error, :invalid_json} = PankoJson.parse(path)
+    end
+  end
+end
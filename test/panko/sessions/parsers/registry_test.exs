@@ -7,7 +7,38 @@ defmodule Panko.Sessions.Parsers.RegistryTest do
     assert {:ok, Panko.Sessions.Parsers.ClaudeCode} = Registry.find_parser("/tmp/session.jsonl")
   end
 
+  test "finds PankoJson parser for .json exports" do
+    assert {:ok, Panko.Sessions.Parsers.PankoJson} = Registry.find_parser("/tmp/session.json")
+  end
+
   test "returns error for unknown file types" do
     assert {:error, :no_parser_found} = Registry.find_parser("/tmp/session.xml")
   end
+
+  describe "content detection" do
+    @describetag :tmp_dir
+
+    test "recognizes Claude Code sessions whatever their name", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "copied-session.txt")
+      File.cp!("test/fixtures/simple_session.jsonl", path)
+
+      assert {:ok, Panko.Sessions.Parsers.ClaudeCode} = Registry.find_parser(path)
+    end
+
+    test "recognizes Panko exports saved as .jsonl", %{tmp_dir: tmp_dir} do
+      {:ok, session} = Panko.Sessions.Exporters.load_file("test/fixtures/simple_session.jsonl")
+      {:ok, json} = Panko.Sessions.Exporters.export(session, :json)
+      path = Path.join(tmp_dir, "archive.jsonl")
+      File.write!(path, json)
+
+      assert {:ok, Panko.Sessions.Parsers.PankoJson} = Registry.find_parser(path)
+    end
+
+    test "falls back to the extension when no format is detected", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "empty.jsonl")
+      File.write!(path, "")
+
+      assert {:ok, Panko.Sessions.Parsers.ClaudeCode} = Registry.find_parser(path)
+    end
+  end
 end
@@ -0,0 +1,143 @@
+defmodule Panko.Sessions.Parsers.TranscriptTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Parsers.Transcript
+
+  @moduletag :tmp_dir
+
+  defp write_json(dir, name, data) do
+    path = Path.join(dir, name)
+    File.write!(path, Jason.encode!(data))
+    path
+  end
+
+  describe "ChatGPT exports" do
+    test "imports the current branch of each conversation", %{tmp_dir: dir} do
+      conversation = %{
+        "id" => "conv-1",
+        "title" => "Regex help",
+        "create_time" => 1_760_000_000.5,
+        "current_node" => "c",
+        "mapping" => %{
+          "root" => %{"id" => "root", "parent" => nil, "message" => nil},
+          "a" => %{"parent" => "root", "message" => message("user", "How do I match digits?")},
+          "old" => %{"parent" => "a", "message" => message("assistant", "Discarded answer")},
+          "c" => %{"parent" => "a", "message" => message("assistant", "Use \\d+")}
+        }
+      }
+
+      path = write_json(dir, "conversations.json", [conversation, conversation])
+      assert {:ok, [attrs, _]} = Transcript.parse_all(path)
+
+      assert attrs.external_id == "chatgpt-conv-1"
+      assert attrs.source_type == :imported
+      assert attrs.title == "Regex help"
+      assert attrs.started_at == DateTime.from_unix!(1_760_000_000)
+
+      assert [
+               %{block_type: :user_prompt, content: "How do I match digits?", position: 0},
+               %{block_type: :assistant_response, content: "Use \\d+", position: 1}
+             ] = attrs.blocks
+    end
+
+    test "derives distinct ids for conversations without a usable id", %{tmp_dir: dir} do
+      conversations = [
+        %{"title" => "First", "create_time" => 1_760_000_000, "mapping" => %{}},
+        %{"title" => "Second", "create_time" => 1_760_000_000, "mapping" => %{}},
+        %{"id" => 42, "title" => "Third", "mapping" => %{}}
+      ]
+
+      path = write_json(dir, "conversations.json", conversations)
+      assert {:ok, sessions} = Transcript.parse_all(path)
+      ids = Enum.map(sessions, & &1.external_id)
+
+      assert Enum.all?(ids, &String.starts_with?(&1, "chatgpt-"))
+      assert ids == Enum.uniq(ids)
+      assert {:ok, again} = Transcript.parse_all(path)
+      assert Enum.map(again, & &1.external_id) == ids
+    end
+  end
+
+  describe "message arrays" do
+    test "imports OpenAI messages with tool calls", %{tmp_dir: dir} do
+      messages = [
+        %{"role" => "system", "content" => "You are helpful."},
+        %{"role" => "user", "content" => "What is the weather?"},
+        %{
+          "role" => "assistant",
+          "content" => nil,
+          "tool_calls" => [
+            %{
+              "id" => "call_1",
+              "type" => "function",
+              "function" => %{"name" => "weather", "arguments" => ~s({"city":"Oslo"})}
+            }
+          ]
+        },
+        %{"role" => "tool", "tool_call_id" => "call_1", "content" => "Rain"},
+        %{"role" => "assistant", "content" => "It is raining."}
+      ]
+
+      path = write_json(dir, "chat.json", %{"messages" => messages})
+      assert {:ok, [attrs]} = Transcript.parse_all(path)
+
+      assert "import-" <> _ = attrs.external_id
+      assert attrs.title == "What is the weather?"
+      assert [prompt, call, response] = attrs.blocks
+      assert prompt.block_type == :user_prompt
+      assert call.block_type == :tool_call
+      assert call.metadata["input"] == %{"city" => "Oslo"}
+      assert call.metadata["output"] == "Rain"
+      assert response.content == "It is raining."
+    end
+
+    test "imports Anthropic content blocks", %{tmp_dir: dir} do
+      messages = [
+        %{"role" => "user", "content" => [%{"type" => "text", "text" => "List files"}]},
+        %{
+          "role" => "assistant",
+          "content" => [
+            %{"type" => "thinking", "thinking" => "Use ls"},
+            %{"type" => "tool_use", "id" => "tu_1", "name" => "Bash", "input" => %{}}
+          ]
+        },
+        %{
+          "role" => "user",
+          "content" => [
+            %{
+              "type" => "tool_result",
+              "tool_use_id" => "tu_1",
+              "content" => [%{"type" => "text", "text" => "mix.exs"}]
+            }
+          ]
+        }
+      ]
+
+      path = write_json(dir, "claude.json", messages)
+      assert {:ok, [attrs]} = Transcript.parse_all(path)
+
+      assert Enum.map(attrs.blocks, & &1.block_type) == [:user_prompt, :thinking, :tool_call]
+      assert List.last(attrs.blocks).metadata["output"] == "mix.exs"
+    end
+
+    test "gives the same content the same external id", %{tmp_dir: dir} do
+      data = [%{"role" => "user", "content" => "hello"}]
+      {:ok, [first]} = Transcript.parse_all(write_json(dir, "a.json", data))
+      {:ok, [second]} = Transcript.parse_all(write_json(dir, "b.json", data))
+      assert first.external_id == second.external_id
+    end
+  end
+
+  test "rejects unknown documents", %{tmp_dir: dir} do
+    assert {:error, :unknown_transcript_format} =
+             Transcript.parse_all(write_json(dir, "other.json", %{"foo" => 1}))
+
+    path = Path.join(dir, "broken.json")
+    File.write!(path, "not json")
+    assert {:error, :invalid_json} = Transcript.parse(path)
+  end
+
+  defp message(role, text) do
+    %{"author" => %{"role" => role}, "content" => %{"parts" => [text]}}
+  end
+end
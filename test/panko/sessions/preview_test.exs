@@ -0,0 +1,37 @@
+defmodule Panko.Sessions.PreviewTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Preview
+
+  test "drops slash commands and their output" do
+    prompt = """
+    <command-message>review is running…</command-message>
+    <command-name>/review</command-name>
+    <command-args>the parser changes</command-args>
+    """
+
+    assert Preview.from_prompt(prompt) == "the parser changes"
+    assert Preview.from_prompt("<command-name>/clear</command-name>") == nil
+    assert Preview.from_prompt("<local-command-stdout>ok</local-command-stdout>") == nil
+  end
+
+  test "drops system reminders and collapses whitespace" do
+    prompt = "Fix the\n\n  failing test<system-reminder>Be brief.</system-reminder>"
+    assert Preview.from_prompt(prompt) == "Fix the failing test"
+  end
+
+  test "keeps paths and markup that are part of the prompt" do
+    assert Preview.from_prompt("/home/me/app.ex fails") == "/home/me/app.ex fails"
+    assert Preview.from_prompt("Why is the <div> misaligned?") == "Why is the <div> misaligned?"
+  end
+
+  test "reads the text parts of structured content" do
+    parts = [%{"type" => "image"}, %{"type" => "text", "text" => "What is in this screenshot?"}]
+    assert Preview.from_prompt(parts) == "What is in this screenshot?"
+    assert Preview.from_prompt([%{"type" => "tool_result", "content" => "ok"}]) == nil
+  end
+
+  test "truncates long prompts" do
+    assert "word " |> String.duplicate(100) |> Preview.from_prompt() |> String.length() == 200
+  end
+end
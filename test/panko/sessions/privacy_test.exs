@@ -0,0 +1,54 @@
+defmodule Panko.Sessions.PrivacyTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Privacy
+
+  defp block(position, content, metadata \\ %{}),
+    do: %{position: position, content: content, metadata: metadata}
+
+  test "finds secrets, emails and internal hosts" do
+    key = "sk-ant-" <> String.duplicate("a", 30)
+
+    session = %{
+      title: "Deploy to api.corp",
+      blocks: [
+        block(0, "Use #{key} and mail ops@example.com"),
+        block(1, nil, %{"input" => %{"command" => "curl http://10.0.3.7:4000"}}),
+        block(2, "Again #{key}")
+      ]
+    }
+
+    findings = Privacy.scan(session)
+
+    assert [
+             %{kind: :secret, rule: :anthropic_key, match: "sk-ant…", position: 0, count: 2},
+             %{kind: :email, match: "ops@example.com", position: 0, count: 1},
+             %{kind: :internal_host, rule: :internal_domain, match: "api.corp", position: nil},
+             %{kind: :internal_host, rule: :private_ip, match: "10.0.3.7", position: 1}
+           ] = findings
+
+    refute Enum.any?(findings, &String.contains?(&1.match, key))
+    assert Privacy.describe(findings) == "1 likely secret, 1 email address, 2 internal hosts"
+  end
+
+  test "flags large tool inputs and outputs" do
+    large = String.duplicate("x", 150_000)
+
+    session = %{
+      title: nil,
+      blocks: [
+        block(3, nil, %{"name" => "Read", "output" => large}),
+        block(4, nil, %{"name" => "Write", "input" => %{"content" => large}})
+      ]
+    }
+
+    assert [%{match: "Read output, 150 KB"}, %{match: "Write input, 150 KB"}] =
+             Privacy.scan(session)
+  end
+
+  test "does not mistake file names for hosts" do
+    session = %{title: nil, blocks: [block(0, "Edit settings.local.json and app.lan.ts")]}
+    assert Privacy.scan(session) == []
+    assert Privacy.describe([]) == nil
+  end
+end
@@ -0,0 +1,85 @@
+defmodule Panko.Sessions.PruneTest do
+  use Panko.DataCase, async: true
+
+  alias Panko.Sessions.Prune
+
+  @moduletag :tmp_dir
+
+  setup %{tmp_dir: tmp_dir} do
+    dir = Path.join(tmp_dir, "-home-user-my-project")
+    File.mkdir_p!(dir)
+    path = Path.join(dir, "simple.jsonl")
+    File.cp!("test/fixtures/simple_session.jsonl", path)
+
+    {:ok, session} = Panko.Sessions.import_from_file(path)
+    %{session: session, path: path}
+  end
+
+  test "selects sessions by age and size", %{session: session} do
+    assert [found] = Prune.candidates(older_than: DateTime.utc_now())
+    assert found.id == session.id
+
+    assert [] = Prune.candidates(older_than: ~U[2020-01-01 00:00:00Z])
+    assert [] = Prune.candidates(max_messages: 1)
+    assert [] = Prune.candidates(max_messages: 100, project: "/elsewhere")
+  end
+
+  test "deletes the file and the session with its shares", %{session: session, path: path} do
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+
+    assert {[_], []} = Prune.prune([session], permanent: true)
+
+    refute File.exists?(path)
+    assert {:error, _} = Panko.Sessions.get_session(session.id)
+    assert {:error, _} = Panko.Sharing.get_share(share.slug)
+  end
+
+  test "moves files to the trash by default", %{session: session, path: path, tmp_dir: tmp_dir} do
+    trash = Path.join(tmp_dir, "Trash")
+
+    assert {[_], []} = Prune.prune([session], trash_dir: trash)
+
+    refute File.exists?(path)
+    assert File.exists?(Path.join([trash, "files", "simple.jsonl"]))
+    assert {:error, _} = Panko.Sessions.get_session(session.id)
+  end
+
+  test "archives files into their project directory", %{
+    session: session,
+    path: path,
+    tmp_dir: tmp_dir
+  } do
+    archive = Path.join(tmp_dir, "archive")
+
+    assert {[_], []} = Prune.prune([session], archive: archive)
+
+    refute File.exists?(path)
+    assert File.exists?(Path.join([archive, "-home-user-my-project", "simple.jsonl"]))
+  end
+
+  test "keeps a file until its last session is pruned", %{tmp_dir: tmp_dir} do
+    path = Path.join(tmp_dir, "conversations.json")
+    File.write!(path, Jason.encode!([conversation("a"), conversation("b")]))
+
+    {:ok, attrs} = Panko.Sessions.Parsers.Transcript.parse_all(path)
+    [first, second] = Enum.map(attrs, &Panko.Sessions.upsert_session!/1)
+
+    assert {[_], []} = Prune.prune([first], permanent: true)
+    assert File.exists?(path)
+    assert {:ok, _} = Panko.Sessions.get_session(second.id)
+
+    assert {[_], []} = Prune.prune([second], permanent: true)
+    refute File.exists?(path)
+  end
+
+  defp conversation(id) do
+    message = %{"author" => %{"role" => "user"}, "content" => %{"parts" => ["Hello " <> id]}}
+
+    %{
+      "id" => id,
+      "title" => "Conversation " <> id,
+      "current_node" => "m",
+      "mapping" => %{"m" => %{"parent" => nil, "message" => message}}
+    }
+  end
+end
@@ -0,0 +1,38 @@
+defmodule Panko.Sessions.SearchTest do
+  use Panko.DataCase, async: true
+
+  alias Panko.Sessions.Search
+
+  setup do
+    {:ok, session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    %{session: session}
+  end
+
+  test "finds sessions by block content, ignoring case", %{session: session} do
+    assert [%{session: found, matches: [match | _]}] = Search.search("CURRENT directory")
+    assert found.id == session.id
+    assert match.line == 1
+    assert match.snippet =~ "current directory"
+  end
+
+  test "filters by project" do
+    assert [_] = Search.search("current directory", project: "/home/user/my-project")
+    assert [] = Search.search("current directory", project: "/elsewhere")
+  end
+
+  test "limits the number of sessions" do
+    {:ok, _} = Panko.Sessions.import_from_file("test/fixtures/complex_session.jsonl")
+
+    assert [_, _] = Search.search("e")
+    assert [_] = Search.search("e", limit: 1)
+  end
+
+  test "matches wildcards literally" do
+    assert [] = Search.search("current%directory")
+    assert [] = Search.search("current_directory")
+  end
+
+  test "returns nothing without matches" do
+    assert [] = Search.search("no such phrase anywhere")
+  end
+end
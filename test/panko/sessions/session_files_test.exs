@@ -0,0 +1,42 @@
+defmodule Panko.Sessions.SessionFilesTest do
+  use ExUnit.Case, async: false
+
+  alias Panko.Sessions.SessionFiles
+
+  @moduletag :tmp_dir
+
+  setup %{tmp_dir: tmp_dir} do
+    original = Application.get_env(:panko, :session_watch_paths)
+    Application.put_env(:panko, :session_watch_paths, tmp_dir)
+    on_exit(fn -> Application.put_env(:panko, :session_watch_paths, original) end)
+
+    app = Path.join(tmp_dir, "-home-me-code-app")
+    lib = Path.join(tmp_dir, "-home-me-code-lib")
+    File.mkdir_p!(app)
+    File.mkdir_p!(lib)
+    File.write!(Path.join(app, "a.jsonl"), "")
+    File.write!(Path.join(lib, "b.jsonl"), "")
+    File.touch!(Path.join(app, "a.jsonl"), {{2026, 1, 1}, {0, 0, 0}})
+
+    %{app: app, lib: lib}
+  end
+
+  test "finds a project's files from its path", %{app: app} do
+    assert SessionFiles.project_files("/home/me/code/app") == [Path.join(app, "a.jsonl")]
+  end
+
+  test "accepts a directory of session files", %{lib: lib} do
+    assert SessionFiles.project_files(lib) == [Path.join(lib, "b.jsonl")]
+  end
+
+  test "picks the most recently modified file", %{lib: lib} do
+    assert SessionFiles.latest(SessionFiles.all_files()) == Path.join(lib, "b.jsonl")
+    assert SessionFiles.latest([]) == nil
+  end
+
+  test "flags sessions whose source file is gone", %{app: app} do
+    refute SessionFiles.source_missing?(%{source_path: Path.join(app, "a.jsonl")})
+    assert SessionFiles.source_missing?(%{source_path: Path.join(app, "gone.jsonl")})
+    refute SessionFiles.source_missing?(%{source_path: nil})
+  end
+end
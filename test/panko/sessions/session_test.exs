@@ -31,4 +31,37 @@ defmodule Panko.Sessions.SessionTest do
                |> Ash.create()
     end
   end
+
+  describe "filtered" do
+    setup do
+      for {id, project, started_at} <- [
+            {"old", "app", ~U[2026-01-01 12:00:00Z]},
+            {"new", "app", ~U[2026-03-09 12:00:00Z]},
+            {"other", "lib", ~U[2026-03-10 12:00:00Z]}
+          ] do
+        Session
+        |> Ash.Changeset.for_create(:create, %{
+          external_id: id,
+          source_type: :claude_code,
+          project: project,
+          started_at: started_at
+        })
+        |> Ash.create!()
+      end
+
+      :ok
+    end
+
+    test "lists all sessions newest first" do
+      sessions = Panko.Sessions.list_filtered_sessions!()
+      assert Enum.map(sessions, & &1.external_id) == ["other", "new", "old"]
+    end
+
+    test "filters by project and start time" do
+      sessions =
+        Panko.Sessions.list_filtered_sessions!(%{project: "app", since: ~U[2026-02-01 00:00:00Z]})
+
+      assert Enum.map(sessions, & &1.external_id) == ["new"]
+    end
+  end
 end
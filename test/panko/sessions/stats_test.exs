@@ -0,0 +1,70 @@
+defmodule Panko.Sessions.StatsTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.{Exporters, Stats}
+
+  @fixtures_dir Path.join([__DIR__, "../../fixtures"])
+
+  setup do
+    {:ok, session} = Exporters.load_file(Path.join(@fixtures_dir, "simple_session.jsonl"))
+    %{session: session}
+  end
+
+  test "session_row/1 summarizes usage and tools", %{session: session} do
+    row = Stats.session_row(session)
+
+    assert row.external_id == "test-abc-123"
+    assert row.project == "/home/user/my-project"
+    assert row.input_tokens == 300
+    assert row.output_tokens == 80
+    assert row.tools == ["Bash"]
+    assert row.messages > 0
+    assert DateTime.compare(row.ended_at, row.started_at) in [:gt, :eq]
+    assert_in_delta row.estimated_cost_usd, (300 * 5 + 80 * 25) / 1_000_000, 1.0e-9
+  end
+
+  test "to_csv/1 writes a header and quotes fields", %{session: session} do
+    row = %{Stats.session_row(session) | title: ~s(Fix "parser", again)}
+    [header, line] = row |> List.wrap() |> Stats.to_csv() |> IO.iodata_to_binary() |> lines()
+
+    assert header == Enum.map_join(Stats.columns(), ",", &Atom.to_string/1)
+    assert line =~ ~s("Fix ""parser"", again")
+    assert line =~ ",0.0035,Bash"
+  end
+
+  test "report/1 totals sessions per project and day", %{session: session} do
+    row = Stats.session_row(session)
+    other = %{row | project: "/home/user/other", estimated_cost_usd: 1.0}
+
+    report = Stats.report([row, row, other])
+
+    assert report.totals.sessions == 3
+    assert report.totals.input_tokens == 900
+    assert [%{project: "/home/user/other", sessions: 1}, %{sessions: 2} = mine] = report.projects
+    assert mine.messages == 2 * row.messages
+    assert [%{date: ~D[2026-03-09], sessions: 3}] = report.busiest_days
+  end
+
+  test "to_csv/2 writes the given columns", %{session: session} do
+    report = Stats.report([Stats.session_row(session)])
+    csv = report.projects |> Stats.to_csv([:project, :sessions]) |> IO.iodata_to_binary()
+
+    assert lines(csv) == ["project,sessions", "/home/user/my-project,1"]
+  end
+
+  test "insights/2 fills every day and ranks projects", %{session: session} do
+    row = Stats.session_row(session)
+    other = %{row | project: "/home/user/other", started_at: ~U[2026-03-01 09:00:00Z]}
+
+    insights = Stats.insights([row, row, other], days: 7, today: ~D[2026-03-10])
+
+    assert length(insights.per_day) == 7
+    assert %{date: ~D[2026-03-09], sessions: 2} = Enum.at(insights.per_day, 5)
+    assert %{date: ~D[2026-03-10], sessions: 0, messages: 0} = List.last(insights.per_day)
+    assert [%{project: "/home/user/my-project", sessions: 2}] = insights.projects
+    assert insights.totals.sessions == 2
+    assert insights.average_duration == DateTime.diff(row.ended_at, row.started_at)
+  end
+
+  defp lines(csv), do: String.split(csv, "\r\n", trim: true)
+end
@@ -0,0 +1,31 @@
+defmodule Panko.Sessions.TableOfContentsTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.TableOfContents
+
+  defp prompt(position, content),
+    do: %{block_type: :user_prompt, position: position, content: content}
+
+  test "lists each user prompt with its anchor" do
+    blocks = [
+      prompt(0, "Fix the login bug"),
+      %{block_type: :assistant_response, position: 1, content: "Done"},
+      prompt(2, "\n  Now add tests\nfor the form")
+    ]
+
+    assert TableOfContents.entries(blocks) == [
+             %{position: 0, anchor: "block-0", title: "Fix the login bug"},
+             %{position: 2, anchor: "block-2", title: "Now add tests"}
+           ]
+  end
+
+  test "truncates long prompts" do
+    [entry, _] = TableOfContents.entries([prompt(0, String.duplicate("a", 200)), prompt(1, "b")])
+    assert String.length(entry.title) == 80
+    assert String.ends_with?(entry.title, "…")
+  end
+
+  test "is empty with fewer than two prompts" do
+    assert TableOfContents.entries([prompt(0, "Only one"), prompt(1, "  ")]) == []
+  end
+end
@@ -0,0 +1,26 @@
+defmodule Panko.Sessions.ToolTimingTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.ToolTiming
+
+  test "formats durations" do
+    assert ToolTiming.format(850) == "850ms"
+    assert ToolTiming.format(4_230) == "4.2s"
+    assert ToolTiming.format(125_000) == "2m 05s"
+  end
+
+  test "reads durations from block metadata" do
+    assert ToolTiming.duration_ms(%{metadata: %{"duration_ms" => 12}}) == 12
+    assert ToolTiming.duration_ms(%{metadata: %{"output" => "ok"}}) == nil
+    assert ToolTiming.duration_ms(%{metadata: nil}) == nil
+  end
+
+  test "summarizes durations per tool, slowest in total first" do
+    summary = ToolTiming.summary([{"Read", 10}, {"Bash", 4_000}, {"Bash", 1_000}, {"Read", 30}])
+
+    assert [
+             %{tool: "Bash", calls: 2, total_ms: 5_000, average_ms: 2_500, max_ms: 4_000},
+             %{tool: "Read", calls: 2, total_ms: 40, average_ms: 20, max_ms: 30}
+           ] = summary
+  end
+end
@@ -0,0 +1,52 @@
+defmodule Panko.Sessions.UsageTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sessions.Usage
+
+  defp block(position, metadata), do: %{position: position, metadata: metadata}
+
+  test "sums usage across blocks" do
+    blocks = [
+      block(0, nil),
+      block(1, %{"usage" => %{"input_tokens" => 10, "output_tokens" => 5}, "message_id" => "a"}),
+      block(2, %{"name" => "Bash"}),
+      block(3, %{"usage" => %{"input_tokens" => 7, "cache_read_input_tokens" => 3}})
+    ]
+
+    assert Usage.totals(blocks) == %{
+             input_tokens: 17,
+             output_tokens: 5,
+             cache_creation_input_tokens: 0,
+             cache_read_input_tokens: 3
+           }
+  end
+
+  test "counts repeated message ids once" do
+    usage = %{"input_tokens" => 10, "output_tokens" => 5}
+
+    blocks = [
+      block(0, %{"usage" => usage, "message_id" => "msg_1"}),
+      block(1, %{"usage" => usage, "message_id" => "msg_1"})
+    ]
+
+    assert %{input_tokens: 10, output_tokens: 5} = Usage.totals(blocks)
+  end
+
+  test "estimates cost from the model of each message" do
+    blocks = [
+      block(0, %{
+        "usage" => %{"input_tokens" => 1_000_000, "output_tokens" => 100_000},
+        "model" => "claude-sonnet-4-5-20250929",
+        "message_id" => "a"
+      }),
+      block(1, %{"usage" => %{"input_tokens" => 10}, "model" => "mystery", "message_id" => "b"})
+    ]
+
+    assert_in_delta Usage.cost(blocks), 3.0 + 1.5, 1.0e-9
+    assert Usage.cost([block(0, %{"usage" => %{"input_tokens" => 10}})]) == nil
+  end
+
+  test "returns zeros without usage" do
+    assert Usage.totals([]) == Usage.empty()
+  end
+end
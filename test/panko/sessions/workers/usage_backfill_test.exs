@@ -0,0 +1,55 @@
+defmodule Panko.Sessions.Workers.UsageBackfillTest do
+  use Panko.DataCase, async: true
+  use Oban.Testing, repo: Panko.Repo
+
+  alias Panko.Sessions.Workers.UsageBackfill
+
+  setup do
+    {:ok, session} =
+      Panko.Sessions.Session
+      |> Ash.Changeset.for_create(:create, %{
+        external_id: "backfill-test",
+        source_type: :claude_code,
+        started_at: ~U[2026-03-09 12:00:00Z]
+      })
+      |> Ash.create()
+
+    for {position, id, usage} <- [
+          {0, "msg_1", %{"input_tokens" => 100, "output_tokens" => 50}},
+          {1, "msg_1", %{"input_tokens" => 100, "output_tokens" => 50}},
+          {2, "msg_2", %{"input_tokens" => 20, "output_tokens" => 5}}
+        ] do
+      Panko.Sessions.Block
+      |> Ash.Changeset.for_create(:create, %{
+        session_id: session.id,
+        position: position,
+        block_type: :assistant_response,
+        content: "Done",
+        metadata: %{"usage" => usage, "message_id" => id}
+      })
+      |> Ash.create!()
+    end
+
+    %{session: session}
+  end
+
+  test "caches usage of sessions created without it", %{session: session} do
+    assert session.input_tokens == nil
+
+    assert :ok = perform_job(UsageBackfill, %{})
+
+    {:ok, session} = Panko.Sessions.get_session(session.id)
+    assert session.input_tokens == 120
+    assert session.output_tokens == 55
+    assert session.cache_read_input_tokens == 0
+    assert Panko.Sessions.list_sessions_missing_usage!() == []
+  end
+
+  test "stats rows queue the backfill and fall back to blocks", %{session: session} do
+    assert [row] = Panko.Sessions.Stats.stored_rows(%{})
+
+    assert row.id == session.id
+    assert row.input_tokens == 120
+    assert_enqueued worker: UsageBackfill
+  end
+end
@@ -0,0 +1,25 @@
+defmodule Panko.Sharing.ExpiryTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sharing.Expiry
+
+  test "parses durations" do
+    assert Expiry.parse("30m") == {:ok, 1800}
+    assert Expiry.parse("12h") == {:ok, 43_200}
+    assert Expiry.parse("7d") == {:ok, 604_800}
+    assert Expiry.parse("2W") == {:ok, 1_209_600}
+    assert Expiry.parse("never") == {:ok, :never}
+  end
+
+  test "rejects invalid durations" do
+    assert Expiry.parse("0d") == {:error, :invalid_expiry}
+    assert Expiry.parse("7 days") == {:error, :invalid_expiry}
+    assert Expiry.parse("soon") == {:error, :invalid_expiry}
+  end
+
+  test "expires_at/2 counts from now" do
+    now = ~U[2026-03-09 12:00:00Z]
+    assert Expiry.expires_at("1d", now) == {:ok, ~U[2026-03-10 12:00:00Z]}
+    assert Expiry.expires_at("never", now) == {:ok, nil}
+  end
+end
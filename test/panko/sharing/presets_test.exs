@@ -0,0 +1,25 @@
+defmodule Panko.Sharing.PresetsTest do
+  use ExUnit.Case, async: true
+
+  alias Panko.Sharing.Presets
+
+  test "parses presets into share params" do
+    assert Presets.parse("demo=expires:2h,snapshot; review=expires:30d,live") ==
+             {:ok,
+              %{
+                "demo" => %{expires_in: "2h", snapshot: true},
+                "review" => %{expires_in: "30d", snapshot: false}
+              }}
+
+    assert Presets.parse(nil) == {:ok, %{}}
+  end
+
+  test "rejects unknown options and malformed presets" do
+    assert {:error, "unknown option \"password\" in preset demo"} =
+             Presets.parse("demo=expires:2h,password")
+
+    assert {:error, _} = Presets.parse("demo=expires:soon")
+    assert {:error, _} = Presets.parse("demo")
+    assert {:error, _} = Presets.parse("Demo Preset=snapshot")
+  end
+end
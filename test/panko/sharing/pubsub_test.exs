@@ -0,0 +1,30 @@
+defmodule Panko.Sharing.PubSubTest do
+  use Panko.DataCase, async: false
+
+  setup do
+    path = Path.join(["test/fixtures", "simple_session.jsonl"])
+    {:ok, session} = Panko.Sessions.import_from_file(path)
+    %{session: session}
+  end
+
+  test "broadcasts share lifecycle events per session", %{session: session} do
+    topic = "shares:session:#{session.id}"
+    PankoWeb.Endpoint.subscribe(topic)
+
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    assert_receive %Phoenix.Socket.Broadcast{topic: ^topic, event: "create"}, 1_000
+
+    {:ok, share} = Panko.Sharing.record_share_view(share)
+    assert_receive %Phoenix.Socket.Broadcast{topic: ^topic, event: "record_view"}, 1_000
+
+    {:ok, _share} = Panko.Sharing.unpublish_share(share)
+    assert_receive %Phoenix.Socket.Broadcast{topic: ^topic, event: "unpublish"}, 1_000
+  end
+
+  test "broadcasts every change on the global topic", %{session: session} do
+    PankoWeb.Endpoint.subscribe("shares:changed")
+
+    {:ok, _share} = Panko.Sharing.create_share(session.id)
+    assert_receive %Phoenix.Socket.Broadcast{topic: "shares:changed", event: "create"}, 1_000
+  end
+end
@@ -0,0 +1,56 @@
+defmodule Panko.Sharing.RetentionTest do
+  use Panko.DataCase, async: true
+
+  alias Panko.Sharing.Retention
+
+  setup do
+    {:ok, session} =
+      Panko.Sessions.Session
+      |> Ash.Changeset.for_create(:create, %{
+        external_id: "retention-test",
+        source_type: :claude_code,
+        started_at: ~U[2026-03-09 12:00:00Z]
+      })
+      |> Ash.create()
+
+    %{session: session}
+  end
+
+  defp stopped_share(session) do
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    {:ok, share} = Panko.Sharing.unpublish_share(share)
+    share
+  end
+
+  test "deletes shares stopped longer ago than the maximum age", %{session: session} do
+    share = stopped_share(session)
+    later = DateTime.add(DateTime.utc_now(), 31 * 86_400)
+
+    assert {:ok, []} = Retention.prune(max_age: "30d", max_history: nil)
+    assert {:ok, [pruned]} = Retention.prune(max_age: "30d", max_history: nil, now: later)
+    assert pruned.id == share.id
+    assert {:error, _} = Panko.Sharing.get_share(share.slug)
+  end
+
+  test "keeps only the newest stopped shares", %{session: session} do
+    shares = for _ <- 1..3, do: stopped_share(session)
+
+    assert {:ok, pruned} = Retention.prune(max_age: "never", max_history: 1)
+    assert length(pruned) == 2
+    assert Enum.all?(pruned, &(&1.id in Enum.map(shares, fn share -> share.id end)))
+  end
+
+  test "never deletes active shares", %{session: session} do
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+
+    assert {:ok, []} = Retention.prune(max_age: "never", max_history: 0)
+    assert {:ok, _} = Panko.Sharing.get_share(share.slug)
+  end
+
+  test "dry runs delete nothing", %{session: session} do
+    share = stopped_share(session)
+
+    assert {:ok, [_]} = Retention.prune(max_age: "never", max_history: 0, dry_run: true)
+    assert {:ok, _} = Panko.Sharing.get_share(share.slug)
+  end
+end
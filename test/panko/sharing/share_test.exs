@@ -24,6 +24,31 @@ defmodule Panko.Sharing.ShareTest do
     assert share.shared_at != nil
   end
 
+  test "creates a named share", %{session: session} do
+    assert {:ok, share} = Panko.Sharing.create_share(session.id, %{slug: "sprint-demo"})
+    assert share.slug == "sprint-demo"
+    assert {:ok, _} = Panko.Sharing.get_share_by_slug("sprint-demo")
+  end
+
+  test "share names are unique", %{session: session} do
+    {:ok, _} = Panko.Sharing.create_share(session.id, %{slug: "sprint-demo"})
+    assert {:error, _} = Panko.Sharing.create_share(session.id, %{slug: "sprint-demo"})
+  end
+
+  test "rejects invalid share names", %{session: session} do
+    assert {:error, _} = Panko.Sharing.create_share(session.id, %{slug: "Sprint Demo"})
+    assert {:error, _} = Panko.Sharing.create_share(session.id, %{slug: "-demo"})
+    assert {:error, _} = Panko.Sharing.create_share(session.id, %{slug: "ab"})
+  end
+
+  test "snapshot shares record the session length", %{session: session} do
+    {:ok, live} = Panko.Sharing.create_share(session.id)
+    {:ok, snapshot} = Panko.Sharing.create_share(session.id, %{snapshot: true})
+
+    assert live.snapshot_blocks == nil
+    assert snapshot.snapshot_blocks == 0
+  end
+
   test "unpublish sets is_shared to false", %{session: session} do
     {:ok, share} = Panko.Sharing.create_share(session.id)
     {:ok, unpublished} = Panko.Sharing.unpublish_share(share)
@@ -39,6 +64,23 @@ defmodule Panko.Sharing.ShareTest do
     assert republished.slug == share.slug
   end
 
+  test "republishing an expired share starts a new expiry", %{session: session} do
+    {:ok, share} =
+      Panko.Sharing.create_share(session.id, %{
+        expires_at: DateTime.add(DateTime.utc_now(), -3600, :second)
+      })
+
+    {:ok, unpublished} = Panko.Sharing.unpublish_share(share)
+    {:ok, republished} =
+      Panko.Sharing.republish_share(unpublished, %{expires_in: "1d"}, load: [:status])
+
+    assert republished.status == :active
+    assert DateTime.compare(republished.expires_at, DateTime.utc_now()) == :gt
+
+    {:ok, republished} = Panko.Sharing.republish_share(republished, %{expires_in: "never"})
+    assert republished.expires_at == nil
+  end
+
   test "get_share_by_slug finds active share", %{session: session} do
     {:ok, share} = Panko.Sharing.create_share(session.id)
     assert {:ok, found} = Panko.Sharing.get_share_by_slug(share.slug)
@@ -50,4 +92,104 @@ defmodule Panko.Sharing.ShareTest do
     {:ok, _} = Panko.Sharing.unpublish_share(share)
     assert {:error, _} = Panko.Sharing.get_share_by_slug(share.slug)
   end
+
+  test "get_share finds shares by slug even when unpublished", %{session: session} do
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    {:ok, _} = Panko.Sharing.unpublish_share(share)
+
+    assert {:ok, found} = Panko.Sharing.get_share(share.slug)
+    assert found.is_shared == false
+  end
+
+  test "record_share_view increments view count", %{session: session} do
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    assert share.view_count == 0
+    assert share.last_viewed_at == nil
+
+    {:ok, share} = Panko.Sharing.record_share_view(share)
+    {:ok, share} = Panko.Sharing.record_share_view(share)
+    assert share.view_count == 2
+    assert share.last_viewed_at != nil
+  end
+
+  test "record_share_view counts unique visitors", %{session: session} do
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    alice = Panko.Sharing.ShareView.visitor_hash({127, 0, 0, 1})
+    bob = Panko.Sharing.ShareView.visitor_hash("10.0.0.2")
+
+    {:ok, _} = Panko.Sharing.record_share_view(share, %{visitor_hash: alice})
+    {:ok, _} = Panko.Sharing.record_share_view(share, %{visitor_hash: alice})
+    {:ok, _} = Panko.Sharing.record_share_view(share, %{visitor_hash: bob})
+    {:ok, _} = Panko.Sharing.record_share_view(share)
+
+    {:ok, share} = Panko.Sharing.get_share(share.slug, load: [:unique_visitors])
+    assert share.view_count == 4
+    assert share.unique_visitors == 2
+  end
+
+  test "record_share_bytes adds to the bytes served", %{session: session} do
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    assert share.bytes_served == 0
+
+    {:ok, _} = Panko.Sharing.record_share_bytes(share, 1_500)
+    {:ok, share} = Panko.Sharing.record_share_bytes(share, 500)
+    assert share.bytes_served == 2_000
+  end
+
+  test "visitor hashes are stable for string and tuple addresses" do
+    hash = Panko.Sharing.ShareView.visitor_hash("192.168.1.20")
+    assert hash == Panko.Sharing.ShareView.visitor_hash({192, 168, 1, 20})
+    assert String.length(hash) == 32
+  end
+
+  describe "status" do
+    test "is active while published", %{session: session} do
+      {:ok, share} = Panko.Sharing.create_share(session.id)
+      assert Ash.load!(share, :status).status == :active
+    end
+
+    test "is stopped once unpublished", %{session: session} do
+      {:ok, share} = Panko.Sharing.create_share(session.id)
+      {:ok, share} = Panko.Sharing.unpublish_share(share)
+      assert Ash.load!(share, :status).status == :stopped
+    end
+
+    test "is expired past expires_at, before and after reaping", %{session: session} do
+      share =
+        Panko.Sharing.Share
+        |> Ash.Changeset.for_create(:create, %{
+          session_id: session.id,
+          expires_at: ~U[2020-01-01 00:00:00Z]
+        })
+        |> Ash.create!()
+
+      assert Ash.load!(share, :status).status == :expired
+
+      {:ok, share} = Panko.Sharing.unpublish_share(share)
+      assert Ash.load!(share, :status).status == :expired
+    end
+  end
+
+  describe "expiry" do
+    test "defaults to the configured expiry", %{session: session} do
+      {:ok, share} = Panko.Sharing.create_share(session.id)
+      {:ok, seconds} = Panko.Sharing.Expiry.parse(Panko.Sharing.Expiry.default())
+
+      assert_in_delta DateTime.diff(share.expires_at, DateTime.utc_now()), seconds, 5
+    end
+
+    test "uses expires_in when given", %{session: session} do
+      {:ok, share} = Panko.Sharing.create_share(session.id, %{expires_in: "12h"})
+      assert_in_delta DateTime.diff(share.expires_at, DateTime.utc_now()), 12 * 3600, 5
+    end
+
+    test "never disables expiry", %{session: session} do
+      {:ok, share} = Panko.Sharing.create_share(session.id, %{expires_in: "never"})
+      assert share.expires_at == nil
+    end
+
+    test "rejects invalid durations", %{session: session} do
+      assert {:error, _} = Panko.Sharing.create_share(session.id, %{expires_in: "soon"})
+    end
+  end
 end
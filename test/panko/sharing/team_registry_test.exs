@@ -0,0 +1,51 @@
+defmodule Panko.Sharing.TeamRegistryTest do
+  use Panko.DataCase, async: false
+  use Oban.Testing, repo: Panko.Repo
+
+  alias Panko.Sharing.TeamRegistry
+  alias Panko.Sharing.Workers.TeamRegistrySync
+
+  setup do
+    {:ok, session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    %{session: session}
+  end
+
+  describe "with a registry configured" do
+    setup do
+      Application.put_env(:panko, :team_registry_url, "http://registry.invalid/shares")
+      on_exit(fn -> Application.delete_env(:panko, :team_registry_url) end)
+    end
+
+    test "queues a sync when shares start and stop", %{session: session} do
+      {:ok, share} = Panko.Sharing.create_share(session.id)
+      assert_enqueued(worker: TeamRegistrySync, args: %{share_id: share.id})
+
+      {:ok, _} = Panko.Sharing.unpublish_share(share)
+      assert [_, _] = all_enqueued(worker: TeamRegistrySync)
+    end
+  end
+
+  test "does not queue syncs without a registry", %{session: session} do
+    {:ok, _share} = Panko.Sharing.create_share(session.id)
+    refute_enqueued(worker: TeamRegistrySync)
+  end
+
+  test "entries describe the share", %{session: session} do
+    {:ok, share} = Panko.Sharing.create_share(session.id, %{slug: "team-demo"})
+    entry = share |> Ash.load!(:session) |> TeamRegistry.entry()
+
+    assert entry.slug == "team-demo"
+    assert entry.url =~ ~r"/s/team-demo$"
+    assert entry.title == session.title
+    assert entry.active
+  end
+
+  test "only active, unexpired entries are live" do
+    now = ~U[2026-10-17 12:00:00Z]
+
+    assert TeamRegistry.live?(%{"active" => true, "expires_at" => nil}, now)
+    assert TeamRegistry.live?(%{"active" => true, "expires_at" => "2026-10-18T00:00:00Z"}, now)
+    refute TeamRegistry.live?(%{"active" => true, "expires_at" => "2026-10-16T00:00:00Z"}, now)
+    refute TeamRegistry.live?(%{"active" => false}, now)
+  end
+end
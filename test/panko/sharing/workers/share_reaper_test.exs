@@ -1,7 +1,9 @@
 defmodule Panko.Sharing.Workers.ShareReaperTest do
-  use Panko.DataCase, async: true
+  use Panko.DataCase, async: false
   use Oban.Testing, repo: Panko.Repo
 
+  import ExUnit.CaptureLog
+
   alias Panko.Sharing.Share
   alias Panko.Sharing.Workers.ShareReaper
 
@@ -53,4 +55,26 @@ defmodule Panko.Sharing.Workers.ShareReaperTest do
     assert {:ok, found} = Panko.Sharing.get_share_by_slug(share.slug)
     assert found.is_shared == true
   end
+
+  test "logs an invalid retention setting instead of failing", %{session: session} do
+    original = Application.get_env(:panko, :max_stopped_share_age)
+    Application.put_env(:panko, :max_stopped_share_age, "soon")
+    on_exit(fn -> Application.put_env(:panko, :max_stopped_share_age, original) end)
+
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    {:ok, _} = Panko.Sharing.unpublish_share(share)
+
+    assert capture_log(fn -> assert :ok = perform_job(ShareReaper, %{}) end) =~
+             "Could not prune stopped shares"
+
+    assert {:ok, _} = Panko.Sharing.get_share(share.slug)
+  end
+
+  test "keeps stopped shares unless a retention limit is set", %{session: session} do
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    {:ok, _} = Panko.Sharing.unpublish_share(share)
+
+    assert :ok = perform_job(ShareReaper, %{})
+    assert {:ok, _} = Panko.Sharing.get_share(share.slug)
+  end
 end
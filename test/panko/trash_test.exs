@@ -0,0 +1,37 @@
+defmodule Panko.TrashTest do
+  use ExUnit.Case, async: true
+
+  @moduletag :tmp_dir
+
+  test "moves files into a freedesktop trash with restore info", %{tmp_dir: dir} do
+    trash = Path.join(dir, "Trash")
+    path = Path.join(dir, "my session.jsonl")
+    File.write!(path, "{}")
+
+    assert :ok = Panko.Trash.move(path, trash)
+
+    refute File.exists?(path)
+    assert File.read!(Path.join([trash, "files", "my session.jsonl"])) == "{}"
+
+    info = File.read!(Path.join([trash, "info", "my session.jsonl.trashinfo"]))
+    assert info =~ "Path=#{URI.encode(path)}\n"
+    assert info =~ ~r/DeletionDate=\d{4}-\d\d-\d\dT\d\d:\d\d:\d\d\n/
+  end
+
+  test "keeps earlier trashed files with the same name", %{tmp_dir: dir} do
+    trash = Path.join(dir, "Trash")
+    path = Path.join(dir, "session.jsonl")
+
+    File.write!(path, "first")
+    :ok = Panko.Trash.move(path, trash)
+    File.write!(path, "second")
+    :ok = Panko.Trash.move(path, trash)
+
+    assert File.read!(Path.join([trash, "files", "session.jsonl"])) == "first"
+    assert File.read!(Path.join([trash, "files", "session.1.jsonl"])) == "second"
+  end
+
+  test "fails for missing files", %{tmp_dir: dir} do
+    assert {:error, :enoent} = Panko.Trash.move(Path.join(dir, "missing"), dir)
+  end
+end
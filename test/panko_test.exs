@@ -0,0 +1,19 @@
+defmodule PankoTest do
+  use Panko.DataCase, async: true
+
+  @fixture Path.join([__DIR__, "fixtures", "simple_session.jsonl"])
+
+  test "loads and exports a file without storing it" do
+    assert {:ok, session} = Panko.load_file(@fixture)
+    assert {:ok, markdown} = Panko.export(session, :markdown)
+    assert IO.iodata_to_binary(markdown) =~ "#"
+    assert {:error, _} = Panko.Sessions.get_session_by_external_id(session.external_id)
+  end
+
+  test "imports and shares a session" do
+    assert {:ok, session} = Panko.import_file(@fixture)
+    assert {:ok, share} = Panko.share(session, slug: "facade-demo", expires_in: "1d")
+    assert share.slug == "facade-demo"
+    assert Panko.share_url(share) =~ ~r"/s/facade-demo$"
+  end
+end
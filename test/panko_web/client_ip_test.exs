@@ -0,0 +1,26 @@
+defmodule PankoWeb.ClientIPTest do
+  use ExUnit.Case, async: true
+
+  alias PankoWeb.ClientIP
+
+  @proxy {10, 0, 0, 1}
+
+  test "ignores X-Forwarded-For from peers that are not trusted proxies" do
+    assert ClientIP.from({203, 0, 113, 9}, ["198.51.100.1"], []) == {203, 0, 113, 9}
+    assert ClientIP.from({203, 0, 113, 9}, ["198.51.100.1"], ["10.0.0.1"]) == {203, 0, 113, 9}
+  end
+
+  test "takes the forwarded client from a trusted proxy" do
+    assert ClientIP.from(@proxy, ["198.51.100.1"], ["10.0.0.1"]) == "198.51.100.1"
+  end
+
+  test "skips spoofed entries and chained trusted proxies" do
+    forwarded = ["1.2.3.4, 198.51.100.1", "10.0.0.2"]
+
+    assert ClientIP.from(@proxy, forwarded, ["10.0.0.1", "10.0.0.2"]) == "198.51.100.1"
+  end
+
+  test "falls back to the peer without a forwarded client" do
+    assert ClientIP.from(@proxy, [], ["10.0.0.1"]) == @proxy
+  end
+end
@@ -0,0 +1,30 @@
+defmodule PankoWeb.Components.BlocksTest do
+  use ExUnit.Case, async: true
+
+  alias PankoWeb.Components.Blocks
+
+  defmodule TodoRenderer do
+    @behaviour PankoWeb.Components.Blocks.Renderer
+
+    @impl true
+    def render(_assigns), do: nil
+  end
+
+  @todo %{block_type: :tool_call, metadata: %{"name" => "TodoWrite"}}
+  @bash %{block_type: :tool_call, metadata: %{"name" => "Bash"}}
+
+  test "uses the built-in renderer by block type" do
+    assert Blocks.renderer(@todo, %{}) == Blocks.ToolCall
+    assert Blocks.renderer(%{block_type: :thinking, metadata: nil}, %{}) == Blocks.Thinking
+  end
+
+  test "prefers renderers registered for a tool, then for a type" do
+    assert Blocks.renderer(@todo, %{{:tool_call, "TodoWrite"} => TodoRenderer}) == TodoRenderer
+    assert Blocks.renderer(@bash, %{{:tool_call, "TodoWrite"} => TodoRenderer}) == Blocks.ToolCall
+    assert Blocks.renderer(@bash, %{tool_call: TodoRenderer}) == TodoRenderer
+  end
+
+  test "renders nothing for unknown block types" do
+    assert Blocks.renderer(%{block_type: :unknown, metadata: nil}, %{}) == nil
+  end
+end
@@ -126,6 +126,18 @@ defmodule PankoWeb.Components.ContentRendererTest do
       assert html =~ "<code"
     end
 
+    test "adds a copy button to code blocks" do
+      result = ContentRenderer.render_markdown("```elixir\nIO.puts(\"hi\")\n```\n\ntext")
+      html = Phoenix.HTML.safe_to_string(result)
+      assert html =~ ~r{<div class="relative"><button type="button" data-copy-code[^>]*>Copy</button><pre>}
+      assert length(String.split(html, "data-copy-code")) == 2
+    end
+
+    test "strips copy buttons written in the markdown itself" do
+      result = ContentRenderer.render_markdown(~S[<button data-copy-code>Copy</button>])
+      refute Phoenix.HTML.safe_to_string(result) =~ "data-copy-code"
+    end
+
     test "sanitizes script tags from markdown" do
       result = ContentRenderer.render_markdown("<script>alert('xss')</script>")
       html = Phoenix.HTML.safe_to_string(result)
@@ -138,5 +150,65 @@ defmodule PankoWeb.Components.ContentRendererTest do
       html = Phoenix.HTML.safe_to_string(result)
       refute html =~ "onerror"
     end
+
+    test "drops javascript links, iframes and inline styles" do
+      markdown = """
+      [click](javascript:alert(1))
+
+      <iframe src="https://example.com"></iframe>
+
+      <p style="position:fixed">overlay</p>
+      """
+
+      html = markdown |> ContentRenderer.render_markdown() |> Phoenix.HTML.safe_to_string()
+      refute html =~ "javascript:"
+      refute html =~ "<iframe"
+      refute html =~ "style="
+    end
+
+    test "escapes markup inside code blocks" do
+      result = ContentRenderer.render_markdown("```html\n<script>alert(1)</script>\n```")
+      html = Phoenix.HTML.safe_to_string(result)
+      refute html =~ "<script"
+      assert html =~ "&lt;script&gt;"
+    end
+
+    test "keeps a fence quoted inside another in one code block" do
+      markdown = "```markdown\nRun:\n```sh\nmix test\n```\nDone\n```\n\nAfter"
+      html = markdown |> ContentRenderer.render_markdown() |> Phoenix.HTML.safe_to_string()
+
+      assert length(String.split(html, "<pre>")) == 2
+      assert html =~ "```sh"
+      assert html =~ "<p>After</p>"
+    end
+
+    test "closes a fence left open" do
+      result = ContentRenderer.render_markdown("Output:\n\n```\n<b>not bold</b>")
+      html = Phoenix.HTML.safe_to_string(result)
+      assert html =~ "<pre>"
+      assert html =~ "&lt;b&gt;not bold&lt;/b&gt;"
+      refute html =~ "<b>"
+    end
+  end
+
+  describe "balance_fences/1" do
+    test "leaves well-formed fences alone" do
+      markdown = "```elixir\na\n```\n\ntext\n\n~~~\nb\n~~~"
+      assert ContentRenderer.balance_fences(markdown) == markdown
+    end
+
+    test "lengthens fences around nested ones" do
+      assert ContentRenderer.balance_fences("```md\n```sh\nls\n```\n```") ==
+               "````md\n```sh\nls\n```\n````"
+    end
+
+    test "ignores fences of the other kind" do
+      markdown = "~~~\n```\n~~~\nafter"
+      assert ContentRenderer.balance_fences(markdown) == markdown
+    end
+
+    test "closes an open fence at the end" do
+      assert ContentRenderer.balance_fences("```sh\nls") == "```sh\nls\n```"
+    end
   end
 end
@@ -0,0 +1,40 @@
+defmodule PankoWeb.Components.EditorLinkTest do
+  use ExUnit.Case, async: true
+
+  import Phoenix.LiveViewTest
+
+  alias PankoWeb.Components.EditorLink
+
+  describe "url/3" do
+    test "builds preset editor URLs" do
+      assert EditorLink.url("vscode", "/home/me/a.ex") == "vscode://file/home/me/a.ex:1"
+      assert EditorLink.url("zed", "/srv/my app/b.ex", 12) == "zed://file/srv/my%20app/b.ex:12"
+    end
+
+    test "fills in custom templates" do
+      template = "idea://open?file={path}&line={line}"
+      assert EditorLink.url(template, "/a/b.ex", 3) == "idea://open?file=/a/b.ex&line=3"
+    end
+
+    test "skips relative paths and missing config" do
+      assert EditorLink.url(nil, "/a/b.ex") == nil
+      assert EditorLink.url("vscode", "unknown") == nil
+      assert EditorLink.url("vscode", nil) == nil
+      assert EditorLink.url("not-a-template", "/a/b.ex") == nil
+    end
+  end
+
+  test "renders tool blocks with an editor link" do
+    block = %{
+      id: Ash.UUID.generate(),
+      block_type: :tool_call,
+      timestamp: nil,
+      metadata: %{"name" => "Read", "input" => %{"file_path" => "/a/b.ex"}}
+    }
+
+    html = render_component(&PankoWeb.Components.Blocks.block/1, block: block, editor: "vscode")
+    assert html =~ ~s(href="vscode://file/a/b.ex:1")
+
+    refute render_component(&PankoWeb.Components.Blocks.block/1, block: block) =~ "vscode://"
+  end
+end
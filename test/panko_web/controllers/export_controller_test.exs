@@ -0,0 +1,44 @@
+defmodule PankoWeb.ExportControllerTest do
+  use PankoWeb.ConnCase
+
+  setup %{conn: conn} do
+    {:ok, _session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    %{conn: conn}
+  end
+
+  test "downloads a project as one markdown document", %{conn: conn} do
+    conn =
+      conn
+      |> log_in_user(register_user())
+      |> get(~p"/projects/export?#{[project: "/home/user/my-project"]}")
+
+    assert response(conn, 200) =~ "# List the files in the current directory"
+    assert [disposition] = get_resp_header(conn, "content-disposition")
+    assert disposition =~ "my-project.md"
+  end
+
+  test "downloads a project as a zip", %{conn: conn} do
+    conn =
+      conn
+      |> log_in_user(register_user())
+      |> get(~p"/projects/export?#{[project: "/home/user/my-project", bundle: "zip"]}")
+
+    assert {:ok, files} = :zip.unzip(response(conn, 200), [:memory])
+    assert Enum.any?(files, fn {name, _} -> name == ~c"index.md" end)
+  end
+
+  test "returns 404 for unknown projects", %{conn: conn} do
+    conn = conn |> log_in_user(register_user()) |> get(~p"/projects/export?project=/nowhere")
+    assert response(conn, 404)
+  end
+
+  test "returns 400 without a project", %{conn: conn} do
+    conn = conn |> log_in_user(register_user()) |> get(~p"/projects/export")
+    assert response(conn, 400) == "Missing project"
+  end
+
+  test "requires a signed-in user", %{conn: conn} do
+    conn = get(conn, ~p"/projects/export?project=/home/user/my-project")
+    assert redirected_to(conn) == "/sign-in"
+  end
+end
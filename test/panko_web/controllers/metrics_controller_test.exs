@@ -0,0 +1,19 @@
+defmodule PankoWeb.MetricsControllerTest do
+  use PankoWeb.ConnCase
+
+  test "serves Prometheus metrics to local requests", %{conn: conn} do
+    {:ok, _session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+
+    conn = get(conn, ~p"/metrics")
+
+    body = response(conn, 200)
+    assert body =~ "# TYPE panko_sessions gauge"
+    assert body =~ ~r/^panko_sessions 1$/m
+    assert body =~ ~r/^panko_shares_active 0$/m
+  end
+
+  test "rejects remote requests when no API key is set", %{conn: conn} do
+    conn = get(%{conn | remote_ip: {203, 0, 113, 7}}, ~p"/metrics")
+    assert response(conn, 401)
+  end
+end
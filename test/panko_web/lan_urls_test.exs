@@ -0,0 +1,37 @@
+defmodule PankoWeb.LanUrlsTest do
+  use ExUnit.Case, async: true
+
+  alias PankoWeb.LanUrls
+
+  @ifaddrs [
+    {~c"lo", [flags: [:up, :loopback], addr: {127, 0, 0, 1}, addr: {0, 0, 0, 0, 0, 0, 0, 1}]},
+    {~c"eth0",
+     [
+       flags: [:up, :broadcast],
+       addr: {192, 168, 1, 20},
+       netmask: {255, 255, 255, 0},
+       addr: {0xFE80, 0, 0, 0, 1, 2, 3, 4},
+       addr: {0x2001, 0xDB8, 0, 0, 0, 0, 0, 5}
+     ]},
+    {~c"tailscale0", [flags: [:up], addr: {100, 101, 102, 103}]},
+    {~c"wlan0", [flags: [:up], addr: {169, 254, 3, 4}]}
+  ]
+
+  test "lists non-loopback IPv4 addresses and marks Tailscale ones" do
+    assert LanUrls.from_ifaddrs(@ifaddrs, 4000) == [
+             %{interface: "eth0", url: "http://192.168.1.20:4000", tailscale: false},
+             %{interface: "tailscale0", url: "http://100.101.102.103:4000", tailscale: true}
+           ]
+  end
+
+  test "includes global IPv6 addresses when listening on ::" do
+    urls = @ifaddrs |> LanUrls.from_ifaddrs(4000, ipv6: true) |> Enum.map(& &1.url)
+    assert "http://[2001:db8::5]:4000" in urls
+    refute Enum.any?(urls, &String.contains?(&1, "fe80"))
+  end
+
+  test "is empty unless listening on all interfaces" do
+    assert LanUrls.list({127, 0, 0, 1}, 4000) == []
+    assert LanUrls.list({192, 168, 1, 20}, 4000) == []
+  end
+end
@@ -0,0 +1,31 @@
+defmodule PankoWeb.InsightsLiveTest do
+  use PankoWeb.ConnCase, async: true
+
+  import Phoenix.LiveViewTest
+
+  setup %{conn: conn} do
+    %{conn: log_in_user(conn, register_user())}
+  end
+
+  test "summarizes recent sessions", %{conn: conn} do
+    Panko.Sessions.Session
+    |> Ash.Changeset.for_create(:create, %{
+      external_id: "insights-test",
+      source_type: :claude_code,
+      project: "/home/user/my-project",
+      started_at: DateTime.utc_now() |> DateTime.truncate(:second)
+    })
+    |> Ash.create!()
+
+    {:ok, view, html} = live(conn, ~p"/insights")
+
+    assert html =~ "Insights"
+    assert html =~ "~/my-project"
+    assert has_element?(view, "#sessions-per-day div[title$=': 1 sessions']")
+  end
+
+  test "shows an empty state", %{conn: conn} do
+    {:ok, _view, html} = live(conn, ~p"/insights")
+    assert html =~ "No sessions yet."
+  end
+end
@@ -32,6 +32,103 @@ defmodule PankoWeb.SessionLiveTest do
     assert html =~ "blocks"
   end
 
+  test "offers each block as Markdown and raw text to copy", %{conn: conn, session: session} do
+    {:ok, view, _html} = live(conn, ~p"/sessions/#{session.id}")
+
+    assert view |> element("#block-markdown-0") |> render() =~ "## User"
+    assert view |> element("#block-raw-0") |> render() =~ "List the files"
+    assert has_element?(view, "#block-0 button", "Copy Markdown")
+  end
+
+  test "replays blocks one at a time", %{conn: conn, session: session} do
+    {:ok, view, _html} = live(conn, ~p"/sessions/#{session.id}")
+    {:ok, session} = Panko.Sessions.get_session(session.id, load: [:blocks])
+    total = length(session.blocks)
+
+    html = view |> element("button", "Replay") |> render_click()
+    assert html =~ "1 / #{total}"
+    refute html =~ "Bash"
+
+    html = view |> element("button", "Stop") |> render_click()
+    assert html =~ "Replay"
+    assert html =~ "Bash"
+  end
+
+  test "adds comments to blocks", %{conn: conn, session: session, user: user} do
+    {:ok, view, _html} = live(conn, ~p"/sessions/#{session.id}")
+
+    view
+    |> form("#comment-form-0-0", %{body: "Clear prompt"})
+    |> render_submit()
+
+    assert [%{position: 0, author: "Owner"}] = Panko.Sessions.list_session_comments!(session.id)
+    assert render(view) =~ "Clear prompt"
+    refute render(view) =~ to_string(user.email)
+  end
+
+  test "ignores comments on invalid positions", %{conn: conn, session: session} do
+    {:ok, view, _html} = live(conn, ~p"/sessions/#{session.id}")
+
+    view
+    |> element("#comment-form-0-0")
+    |> render_submit(%{position: "first", body: "Lost"})
+
+    assert Panko.Sessions.list_session_comments!(session.id) == []
+  end
+
+  test "bookmarks blocks and lists them", %{conn: conn, session: session} do
+    {:ok, view, _html} = live(conn, ~p"/sessions/#{session.id}")
+    refute has_element?(view, "#bookmarks")
+
+    view |> element("#bookmark-0") |> render_click()
+    assert [%{position: 0}] = Panko.Sessions.list_session_bookmarks!(session.id)
+    assert view |> element("#bookmarks") |> render() =~ "List the files"
+
+    view |> element("#bookmark-0") |> render_click()
+    assert Panko.Sessions.list_session_bookmarks!(session.id) == []
+    refute has_element?(view, "#bookmarks")
+  end
+
+  @tag :tmp_dir
+  test "shows new blocks when the session is re-imported", %{conn: conn, tmp_dir: tmp_dir} do
+    path = Path.join(tmp_dir, "growing.jsonl")
+    File.cp!("test/fixtures/simple_session.jsonl", path)
+    {:ok, session} = Panko.Sessions.import_from_file(path)
+
+    {:ok, view, html} = live(conn, ~p"/sessions/#{session.id}?follow=1")
+    assert html =~ ~s(aria-pressed="true")
+    refute html =~ "Refactor the parser"
+
+    line = %{
+      "type" => "user",
+      "sessionId" => session.external_id,
+      "uuid" => "u-follow",
+      "timestamp" => "2026-03-09T12:10:00.000Z",
+      "message" => %{"role" => "user", "content" => "Refactor the parser"}
+    }
+
+    File.write!(path, Jason.encode!(line) <> "\n", [:append])
+    {:ok, _} = Panko.Sessions.import_from_file(path)
+
+    assert render(view) =~ "Refactor the parser"
+    assert_push_event(view, "follow", %{})
+  end
+
+  @tag :tmp_dir
+  test "warns when the source file disappears", %{conn: conn, tmp_dir: tmp_dir} do
+    path = Path.join(tmp_dir, "removed.jsonl")
+    File.cp!("test/fixtures/complex_session.jsonl", path)
+    {:ok, session} = Panko.Sessions.import_from_file(path)
+
+    {:ok, view, html} = live(conn, ~p"/sessions/#{session.id}")
+    refute html =~ "no longer exists"
+
+    File.rm!(path)
+    PankoWeb.Endpoint.broadcast("sessions:source_removed", "removed", %{path: path})
+    assert render(view) =~ "no longer exists"
+    assert render(view) =~ "Create a helper module"
+  end
+
   test "redirects for invalid session id", %{conn: conn} do
     assert {:error, {:live_redirect, %{to: "/"}}} =
              live(conn, ~p"/sessions/#{Ash.UUID.generate()}")
@@ -25,4 +25,50 @@ defmodule PankoWeb.SessionsLiveTest do
 
     assert render(view) =~ "List the files"
   end
+
+  @tag :tmp_dir
+  test "filters sessions by agent", %{conn: conn, tmp_dir: tmp_dir} do
+    {:ok, _} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+
+    transcript = Path.join(tmp_dir, "chat.json")
+    File.write!(transcript, ~s([{"role": "user", "content": "Translate this"}]))
+    {:ok, _} = Panko.Sessions.import_from_file(transcript)
+
+    {:ok, view, _html} = live(conn, ~p"/")
+    assert view |> element("#agent-filter") |> render() =~ "claude"
+    assert has_element?(view, "button[phx-value-project=\"/home/user/my-project\"]")
+
+    view |> element("#agent-filter button[phx-value-agent=imported]") |> render_click()
+    refute has_element?(view, "button[phx-value-project=\"/home/user/my-project\"]")
+
+    view |> element("[data-cycle-agent]") |> render_click()
+    assert has_element?(view, "button[phx-value-project=\"/home/user/my-project\"]")
+  end
+
+  test "restores the view state sent by the browser", %{conn: conn} do
+    {:ok, session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+
+    view_state =
+      Jason.encode!(%{
+        query: "list",
+        agent: "claude_code",
+        expanded: ["/home/user/my-project", "/gone"],
+        last_session: session.id
+      })
+
+    {:ok, view, _html} = conn |> put_connect_params(%{"view_state" => view_state}) |> live(~p"/")
+
+    assert has_element?(view, "#session-search[value=list]")
+    assert has_element?(view, "#agent-filter [phx-value-agent=claude_code][aria-pressed=true]")
+    assert has_element?(view, "#session-#{session.id}[aria-current=true]")
+    assert_push_event(view, "focus", %{id: "session-" <> _})
+
+    view |> element("#agent-filter button[phx-value-agent=claude_code]") |> render_click()
+    assert_push_event(view, "save-view", %{query: "list", expanded: ["/home/user/my-project"]})
+  end
+
+  test "ignores an unreadable view state", %{conn: conn} do
+    {:ok, view, _html} = conn |> put_connect_params(%{"view_state" => "{"}) |> live(~p"/")
+    assert render(view) =~ "No sessions found"
+  end
 end
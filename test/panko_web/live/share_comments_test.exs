@@ -0,0 +1,50 @@
+defmodule PankoWeb.ShareCommentsTest do
+  use PankoWeb.ConnCase, async: false
+
+  import Phoenix.LiveViewTest
+
+  setup do
+    original = Application.get_env(:panko, :share_comments)
+    Application.put_env(:panko, :share_comments, true)
+    on_exit(fn -> Application.put_env(:panko, :share_comments, original) end)
+
+    {:ok, session} = Panko.Sessions.import_from_file("test/fixtures/simple_session.jsonl")
+    {:ok, share} = Panko.Sharing.create_share(session.id)
+    %{session: session, share: share}
+  end
+
+  test "viewers comment under a name of their choice", %{conn: conn, share: share} do
+    {:ok, view, _html} = live(conn, ~p"/s/#{share.slug}")
+
+    view
+    |> form("#comment-form-0-0", %{name: "ana", body: "Nice prompt"})
+    |> render_submit()
+
+    assert [%{author: "ana"}] = Panko.Sessions.list_session_comments!(share.session_id)
+    assert render(view) =~ "Nice prompt"
+  end
+
+  test "masks secrets and email addresses in comments", %{conn: conn, share: share} do
+    {:ok, _} =
+      Panko.Sessions.add_comment(share.session_id, 0, "Use token=abcdef123456", %{
+        author: "dev@example.com"
+      })
+
+    {:ok, _view, html} = live(conn, ~p"/s/#{share.slug}")
+
+    assert html =~ "Use token=[REDACTED]"
+    refute html =~ "abcdef123456"
+    refute html =~ "dev@example.com"
+  end
+
+  test "rejects comments once the share is unpublished", %{conn: conn, share: share} do
+    {:ok, view, _html} = live(conn, ~p"/s/#{share.slug}")
+    {:ok, _} = Panko.Sharing.unpublish_share(share)
+
+    view
+    |> form("#comment-form-0-0", %{body: "Too late"})
+    |> render_submit()
+
+    assert Panko.Sessions.list_session_comments!(share.session_id) == []
+  end
+end
@@ -16,6 +16,60 @@ defmodule PankoWeb.ShareLiveTest do
     assert html =~ "Panko"
   end
 
+  test "marks up landmarks and the block feed", %{conn: conn, share: share} do
+    {:ok, view, _html} = live(conn, ~p"/s/#{share.slug}")
+    assert has_element?(view, "main#main [role=feed]")
+    assert has_element?(view, "header h1")
+    assert has_element?(view, "footer a", "Panko")
+  end
+
+  test "records a view for connected visitors", %{conn: conn, share: share} do
+    {:ok, _view, _html} = live(conn, ~p"/s/#{share.slug}")
+    assert {:ok, %{view_count: 1}} = Panko.Sharing.get_share_by_slug(share.slug)
+  end
+
+  test "counts the bytes of the share page", %{conn: conn, share: share} do
+    conn = get(conn, ~p"/s/#{share.slug}")
+    {:ok, share} = Panko.Sharing.get_share(share.slug)
+    assert share.bytes_served == byte_size(html_response(conn, 200))
+  end
+
+  test "snapshot shares leave out blocks added after sharing", %{conn: conn, session: session} do
+    {:ok, share} = Panko.Sharing.create_share(session.id, %{snapshot: true})
+
+    Panko.Sessions.Block
+    |> Ash.Changeset.for_create(:create, %{
+      session_id: session.id,
+      position: share.snapshot_blocks,
+      block_type: :user_prompt,
+      content: "Added after sharing"
+    })
+    |> Ash.create!()
+
+    {:ok, _view, html} = live(conn, ~p"/s/#{share.slug}")
+    assert html =~ "List the files"
+    assert html =~ "Snapshot of the session"
+    refute html =~ "Added after sharing"
+  end
+
+  test "ignores bookmark events from share viewers", %{conn: conn, share: share} do
+    {:ok, view, _html} = live(conn, ~p"/s/#{share.slug}")
+
+    for position <- ["0", "junk"] do
+      view |> with_target("#block-0") |> render_click("toggle_bookmark", %{position: position})
+    end
+
+    assert Panko.Sessions.list_session_bookmarks!(share.session_id) == []
+  end
+
+  test "ignores invalid replay speeds", %{conn: conn, share: share} do
+    {:ok, view, _html} = live(conn, ~p"/s/#{share.slug}")
+    view |> element("button", "Replay") |> render_click()
+
+    html = view |> with_target("#block-0") |> render_click("speed", %{speed: "fast"})
+    assert html =~ "Pause"
+  end
+
   test "shows 404 for invalid slug", %{conn: conn} do
     {:ok, _view, html} = live(conn, ~p"/s/nonexistent")
     assert html =~ "404"
@@ -3,6 +3,8 @@ defmodule PankoWeb.ShareUITest do
 
   import Phoenix.LiveViewTest
 
+  alias Panko.Sessions.Builder
+
   setup %{conn: conn} do
     user = register_user()
     conn = log_in_user(conn, user)
@@ -36,6 +38,17 @@ defmodule PankoWeb.ShareUITest do
       assert html =~ "Shared"
     end
 
+    test "reflects share changes made elsewhere", %{conn: conn, session: session} do
+      {:ok, share} = Panko.Sharing.create_share(session.id)
+      {:ok, view, _html} = live(conn, ~p"/sessions/#{session.id}")
+
+      {:ok, _share} = Panko.Sharing.unpublish_share(share)
+
+      # The broadcast triggers a send_update, which is handled after the first render.
+      _ = render(view)
+      assert render(view) =~ "Reshare"
+    end
+
     test "unpublish changes button back to Reshare", %{conn: conn, session: session} do
       {:ok, _share} = Panko.Sharing.create_share(session.id)
 
@@ -70,6 +83,27 @@ defmodule PankoWeb.ShareUITest do
       {:ok, _view, html} = live(conn, ~p"/sessions/#{session.id}")
       assert html =~ "/s/"
     end
+
+    @tag :tmp_dir
+    test "asks to confirm privacy findings before sharing", %{conn: conn, tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "private.jsonl")
+
+      :ok =
+        Builder.new("private-session", cwd: "/app", started_at: ~U[2026-10-17 09:00:00Z])
+        |> Builder.user("Send the report to ops@example.com")
+        |> Builder.write(path)
+
+      {:ok, session} = Panko.Sessions.import_from_file(path)
+      {:ok, view, _html} = live(conn, ~p"/sessions/#{session.id}")
+
+      html = view |> element("button", "Share") |> render_click()
+      assert html =~ "Review before sharing"
+      assert html =~ "ops@example.com"
+      assert Panko.Sharing.find_share_for_session!(session.id) == []
+
+      view |> element("#share-anyway-share-#{session.id}") |> render_click()
+      assert [%{is_shared: true}] = Panko.Sharing.find_share_for_session!(session.id)
+    end
   end
 
   describe "SessionsLive share indicator" do
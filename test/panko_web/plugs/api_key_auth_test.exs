@@ -27,6 +27,32 @@ defmodule PankoWeb.Plugs.ApiKeyAuthTest do
     end
   end
 
+  describe "local_only without an API key" do
+    setup do
+      Application.put_env(:panko, :api_key, nil)
+      :ok
+    end
+
+    test "passes loopback requests", %{conn: conn} do
+      conn = ApiKeyAuth.call(conn, local_only: true)
+      refute conn.halted
+    end
+
+    test "blocks remote requests", %{conn: conn} do
+      conn = ApiKeyAuth.call(%{conn | remote_ip: {192, 168, 1, 20}}, local_only: true)
+      assert conn.status == 401
+    end
+
+    test "blocks requests forwarded by a proxy", %{conn: conn} do
+      conn =
+        conn
+        |> put_req_header("x-forwarded-for", "203.0.113.7")
+        |> ApiKeyAuth.call(local_only: true)
+
+      assert conn.status == 401
+    end
+  end
+
   describe "when API key is configured" do
     setup do
       Application.put_env(:panko, :api_key, "secret123")
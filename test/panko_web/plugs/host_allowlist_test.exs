@@ -0,0 +1,43 @@
+defmodule PankoWeb.Plugs.HostAllowlistTest do
+  use PankoWeb.ConnCase, async: false
+
+  alias PankoWeb.Plugs.HostAllowlist
+
+  setup do
+    on_exit(fn -> Application.delete_env(:panko, :allowed_hosts) end)
+  end
+
+  test "passes every host when no allowlist is set", %{conn: conn} do
+    refute HostAllowlist.call(%{conn | host: "scanner.invalid"}, []).halted
+  end
+
+  test "refuses hosts outside the allowlist", %{conn: conn} do
+    Application.put_env(:panko, :allowed_hosts, ["panko.example.com"])
+
+    refute HostAllowlist.call(%{conn | host: "panko.example.com"}, []).halted
+    refute HostAllowlist.call(%{conn | host: "localhost", remote_ip: {127, 0, 0, 1}}, []).halted
+
+    conn = HostAllowlist.call(%{conn | host: "203.0.113.7"}, [])
+    assert conn.halted
+    assert conn.status == 421
+  end
+
+  test "only allows loopback hosts from loopback addresses", %{conn: conn} do
+    Application.put_env(:panko, :allowed_hosts, ["panko.example.com"])
+
+    refute HostAllowlist.call(%{conn | host: "localhost", remote_ip: {127, 0, 0, 1}}, []).halted
+    assert HostAllowlist.call(%{conn | host: "localhost", remote_ip: {203, 0, 113, 7}}, []).halted
+
+    forwarded = put_req_header(%{conn | host: "localhost"}, "x-forwarded-for", "203.0.113.7")
+    assert HostAllowlist.call(forwarded, []).halted
+  end
+
+  test "matches subdomain wildcards and ignores case" do
+    hosts = ["*.ts.net", "Panko.Example.com"]
+
+    assert HostAllowlist.allowed?("laptop.tailnet.ts.net", hosts)
+    assert HostAllowlist.allowed?("panko.example.COM", hosts)
+    refute HostAllowlist.allowed?("ts.net", hosts)
+    refute HostAllowlist.allowed?("evil-ts.net", hosts)
+  end
+end
@@ -0,0 +1,56 @@
+defmodule PankoWeb.Plugs.LocaleTest do
+  use PankoWeb.ConnCase, async: false
+
+  alias PankoWeb.Plugs.Locale
+
+  setup do
+    original = Application.get_env(:panko, :locale)
+    on_exit(fn -> Application.put_env(:panko, :locale, original) end)
+    :ok
+  end
+
+  describe "from_accept_language/2" do
+    test "picks the highest quality known locale" do
+      header = "fr;q=0.5, de-DE;q=0.9, en;q=0.8"
+      assert Locale.from_accept_language(header, ["en", "de", "fr"]) == "de"
+    end
+
+    test "prefers an exact region match over the language" do
+      assert Locale.from_accept_language("pt-BR", ["pt", "pt_BR"]) == "pt_BR"
+      assert Locale.from_accept_language("pt-PT", ["pt", "pt_BR"]) == "pt"
+    end
+
+    test "returns nil when nothing matches" do
+      assert Locale.from_accept_language("ja, ko;q=0.5", ["en"]) == nil
+      assert Locale.from_accept_language(nil, ["en"]) == nil
+      assert Locale.from_accept_language("en;q=abc", ["en"]) == nil
+    end
+  end
+
+  describe "call/2" do
+    test "stores the Accept-Language locale in the session", %{conn: conn} do
+      Application.put_env(:panko, :locale, nil)
+
+      conn =
+        conn
+        |> Plug.Test.init_test_session(%{})
+        |> put_req_header("accept-language", "xx, en;q=0.5")
+        |> Locale.call([])
+
+      assert get_session(conn, "locale") == "en"
+      assert Gettext.get_locale(PankoWeb.Gettext) == "en"
+    end
+
+    test "uses the configured locale over the header", %{conn: conn} do
+      Application.put_env(:panko, :locale, "de")
+
+      conn =
+        conn
+        |> Plug.Test.init_test_session(%{})
+        |> put_req_header("accept-language", "en")
+        |> Locale.call([])
+
+      assert get_session(conn, "locale") == "de"
+    end
+  end
+end
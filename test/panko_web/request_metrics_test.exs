@@ -0,0 +1,30 @@
+defmodule PankoWeb.RequestMetricsTest do
+  use PankoWeb.ConnCase
+
+  alias PankoWeb.RequestMetrics
+
+  test "counts requests per route pattern", %{conn: conn} do
+    before = requests("/s/:slug")
+
+    get(conn, ~p"/s/no-such-share")
+    get(conn, ~p"/s/another-missing-share")
+
+    assert requests("/s/:slug") >= before + 2
+  end
+
+  test "renders labelled samples for Prometheus", %{conn: conn} do
+    get(conn, ~p"/s/no-such-share")
+
+    body = conn |> get(~p"/metrics") |> response(200)
+
+    assert body =~
+             ~s(panko_http_requests_total{method="GET",route="/s/:slug",status="200"} )
+
+    assert body =~ ~s(panko_http_request_duration_seconds_count{method="GET",route="/s/:slug"} )
+  end
+
+  defp requests(route) do
+    {_name, _type, _help, samples} = hd(RequestMetrics.snapshot())
+    Enum.sum(for {labels, count} <- samples, labels[:route] == route, do: count)
+  end
+end